@@ -0,0 +1,5 @@
+-- Display metadata for the GitHub settings UI (repo picker + installation card)
+ALTER TABLE github_installations ADD COLUMN IF NOT EXISTS github_account_avatar_url TEXT;
+
+ALTER TABLE github_repositories ADD COLUMN IF NOT EXISTS description TEXT;
+ALTER TABLE github_repositories ADD COLUMN IF NOT EXISTS html_url TEXT;
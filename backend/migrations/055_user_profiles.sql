@@ -0,0 +1,8 @@
+-- Persistent cache of Clerk user profiles (L2 behind the in-process cache).
+-- Avoids re-fetching every user from the Clerk API after each deploy.
+CREATE TABLE IF NOT EXISTS user_profiles (
+    user_id TEXT PRIMARY KEY,
+    display_name TEXT,
+    email TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,7 @@
+-- Track which API instance claimed a sync job so it can release its own
+-- in-flight jobs on shutdown (and so stuck claims can be told apart).
+ALTER TABLE github_sync_jobs ADD COLUMN IF NOT EXISTS claimed_by TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_gh_sync_jobs_processing
+    ON github_sync_jobs(claimed_by, started_at)
+    WHERE status = 'processing';
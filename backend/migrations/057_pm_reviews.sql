@@ -0,0 +1,11 @@
+-- Persisted PM full-review runs, so plans can be compared week over week.
+CREATE TABLE IF NOT EXISTS pm_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id TEXT NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    request JSONB NOT NULL DEFAULT '{}'::jsonb,
+    response JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_pm_reviews_org_generated
+    ON pm_reviews(org_id, generated_at DESC);
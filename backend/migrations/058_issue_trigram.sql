@@ -0,0 +1,5 @@
+-- Trigram similarity for duplicate-issue detection (GET /issues/{id}/similar).
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_issues_title_desc_trgm
+    ON issues USING GIN ((title || ' ' || COALESCE(description, '')) gin_trgm_ops);
@@ -0,0 +1 @@
+ALTER TABLE issues DROP COLUMN IF EXISTS merged_into_id;
@@ -0,0 +1,3 @@
+-- Issues merged into another (POST /issues/{id}/merge) are archived and point
+-- at the surviving issue.
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS merged_into_id UUID REFERENCES issues(id) ON DELETE SET NULL;
@@ -0,0 +1 @@
+ALTER TABLE projects DROP COLUMN IF EXISTS public_submit_spam_filter;
@@ -0,0 +1,3 @@
+-- Per-project toggle for the public-submit spam checks (honeypot, per-IP
+-- interval, keyword/link heuristics).
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS public_submit_spam_filter BOOLEAN NOT NULL DEFAULT true;
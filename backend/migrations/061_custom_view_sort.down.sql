@@ -0,0 +1 @@
+ALTER TABLE custom_views DROP COLUMN IF EXISTS sort;
@@ -0,0 +1,2 @@
+-- Saved views remember their sort so GET /views/{id}/issues can apply it.
+ALTER TABLE custom_views ADD COLUMN IF NOT EXISTS sort TEXT;
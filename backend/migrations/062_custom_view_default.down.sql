@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_views_one_default;
+ALTER TABLE custom_views DROP COLUMN IF EXISTS is_default;
@@ -0,0 +1,5 @@
+-- A user's default view per project (project_id NULL = their org-wide default).
+ALTER TABLE custom_views ADD COLUMN IF NOT EXISTS is_default BOOLEAN NOT NULL DEFAULT false;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_views_one_default
+    ON custom_views (org_id, created_by, COALESCE(project_id, '00000000-0000-0000-0000-000000000000'::uuid))
+    WHERE is_default;
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS trg_issues_reject_archived_project ON issues;
+DROP FUNCTION IF EXISTS issues_reject_archived_project();
+ALTER TABLE projects DROP COLUMN IF EXISTS archived_at;
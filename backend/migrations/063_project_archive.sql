@@ -0,0 +1,17 @@
+-- DELETE /projects/{id} archives; POST /projects/{id}/purge does the real delete.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+-- Backstop for every issue-creating path (API, public form, email, Slack,
+-- recurring, import, AI tools): archived projects take no new issues.
+CREATE OR REPLACE FUNCTION issues_reject_archived_project() RETURNS trigger AS $$
+BEGIN
+  IF EXISTS (SELECT 1 FROM projects WHERE id = NEW.project_id AND archived_at IS NOT NULL) THEN
+    RAISE EXCEPTION 'project % is archived', NEW.project_id USING ERRCODE = 'check_violation';
+  END IF;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_issues_reject_archived_project ON issues;
+CREATE TRIGGER trg_issues_reject_archived_project BEFORE INSERT ON issues
+  FOR EACH ROW EXECUTE FUNCTION issues_reject_archived_project();
@@ -0,0 +1,2 @@
+ALTER TABLE projects DROP COLUMN IF EXISTS members_seeded_at;
+DROP TABLE IF EXISTS project_members;
@@ -0,0 +1,15 @@
+-- Project roster: who works on a project (assignee pickers, round-robin,
+-- mention validation). Seeded from Clerk org membership on first read.
+CREATE TABLE IF NOT EXISTS project_members (
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  user_id TEXT NOT NULL,
+  role TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('admin', 'member')),
+  added_by TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (project_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_members_user ON project_members(user_id);
+
+-- Set once the roster has been seeded, so removing everyone doesn't re-seed.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS members_seeded_at TIMESTAMPTZ;
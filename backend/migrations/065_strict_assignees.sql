@@ -0,0 +1,2 @@
+-- When set, issue assignees must be on the project roster (project_members).
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS strict_assignees BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,20 @@
+-- Dead-letter queue for Novu triggers that failed (non-2xx or network error).
+-- The retry worker replays `retrying` rows with backoff and deletes them on
+-- success; rows that exhaust their attempts stay as `failed` for inspection.
+CREATE TABLE IF NOT EXISTS notification_failures (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  workflow_id TEXT NOT NULL,
+  subscriber_id TEXT NOT NULL,
+  subscriber_email TEXT,
+  subscriber_name TEXT,
+  payload JSONB NOT NULL DEFAULT '{}',
+  error TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'retrying' CHECK (status IN ('retrying', 'failed')),
+  attempts INT NOT NULL DEFAULT 1,
+  next_retry_at TIMESTAMPTZ NOT NULL DEFAULT now() + interval '1 minute',
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_failures_retry
+  ON notification_failures (next_retry_at) WHERE status = 'retrying';
@@ -0,0 +1,2 @@
+-- Email public-submit reporters when their issue is resolved (done/cancelled).
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS notify_reporters BOOLEAN NOT NULL DEFAULT true;
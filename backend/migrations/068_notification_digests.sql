@@ -0,0 +1,10 @@
+-- One row per user/org/day once the daily digest has been sent, so restarts
+-- and multiple replicas never send the same digest twice. Users opt in with a
+-- `daily_digest` row in notification_preferences.
+CREATE TABLE IF NOT EXISTS notification_digests (
+  user_id TEXT NOT NULL,
+  org_id TEXT NOT NULL,
+  digest_date DATE NOT NULL,
+  sent_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (user_id, org_id, digest_date)
+);
@@ -0,0 +1,6 @@
+-- Per-channel rows can't be represented once `channel` is gone.
+DELETE FROM notification_preferences WHERE channel <> 'all';
+ALTER TABLE notification_preferences DROP CONSTRAINT IF EXISTS notification_preferences_pkey;
+ALTER TABLE notification_preferences DROP COLUMN IF EXISTS channel;
+ALTER TABLE notification_preferences RENAME COLUMN workflow TO type;
+ALTER TABLE notification_preferences ADD PRIMARY KEY (user_id, org_id, type);
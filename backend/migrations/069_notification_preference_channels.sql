@@ -0,0 +1,7 @@
+-- Preferences become per workflow and channel. `type` is renamed `workflow`
+-- (a Novu workflow id or an in-app notification type); `channel` is 'email',
+-- 'in_app', or 'all' for rows written through /notifications/preferences.
+ALTER TABLE notification_preferences RENAME COLUMN type TO workflow;
+ALTER TABLE notification_preferences ADD COLUMN IF NOT EXISTS channel TEXT NOT NULL DEFAULT 'all';
+ALTER TABLE notification_preferences DROP CONSTRAINT IF EXISTS notification_preferences_pkey;
+ALTER TABLE notification_preferences ADD PRIMARY KEY (user_id, org_id, workflow, channel);
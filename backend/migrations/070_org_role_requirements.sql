@@ -0,0 +1,11 @@
+-- Per-org override of the Clerk role needed for guarded actions
+-- (projects.update, projects.delete, api_keys.manage, github.disconnect).
+-- No row means org:admin.
+CREATE TABLE IF NOT EXISTS org_role_requirements (
+  org_id TEXT NOT NULL,
+  action TEXT NOT NULL,
+  role TEXT NOT NULL CHECK (role IN ('org:admin', 'org:member')),
+  updated_by TEXT,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (org_id, action)
+);
@@ -0,0 +1,17 @@
+-- Org-level audit trail for sensitive actions (API keys, GitHub connection,
+-- project deletion, invites). Superadmin actions stay in admin_audit_log.
+CREATE TABLE IF NOT EXISTS audit_log (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  org_id TEXT NOT NULL,
+  actor_id TEXT NOT NULL,
+  actor_name TEXT,
+  action TEXT NOT NULL,
+  target_type TEXT NOT NULL,
+  target_id TEXT NOT NULL,
+  details JSONB NOT NULL DEFAULT '{}',
+  ip_address TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_org_created ON audit_log(org_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_log_org_action ON audit_log(org_id, action, created_at DESC);
@@ -0,0 +1,12 @@
+-- Durable invite short links (/api/v1/invite/{code} → Clerk invitation URL).
+-- Previously kept in process memory, so links broke on restart and across instances.
+CREATE TABLE IF NOT EXISTS invite_short_links (
+  code TEXT PRIMARY KEY,
+  url TEXT NOT NULL,
+  invitation_id TEXT NOT NULL,
+  org_id TEXT NOT NULL,
+  expires_at TIMESTAMPTZ NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_invite_short_links_expires ON invite_short_links(expires_at);
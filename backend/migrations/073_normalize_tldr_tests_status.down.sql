@@ -0,0 +1,2 @@
+-- Data-only migration: the original free-form values are not recoverable.
+SELECT 1;
@@ -0,0 +1,9 @@
+-- Fold free-form tests_status values onto passed / failed / skipped / none.
+-- Unrecognized values become 'none'; new writes are validated by the API.
+UPDATE tldrs SET tests_status = CASE
+  WHEN lower(trim(tests_status)) IN ('passed', 'pass', 'passing', 'ok', 'green', 'success', 'succeeded') THEN 'passed'
+  WHEN lower(trim(tests_status)) IN ('failed', 'fail', 'failing', 'failure', 'red', 'error', 'broken') THEN 'failed'
+  WHEN lower(trim(tests_status)) IN ('skipped', 'skip', 'skipping') THEN 'skipped'
+  ELSE 'none'
+END
+WHERE tests_status NOT IN ('passed', 'failed', 'skipped', 'none');
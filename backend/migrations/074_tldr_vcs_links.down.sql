@@ -0,0 +1,2 @@
+ALTER TABLE tldrs DROP COLUMN IF EXISTS commit_sha;
+ALTER TABLE tldrs DROP COLUMN IF EXISTS github_pr_link_id;
@@ -0,0 +1,10 @@
+-- Tie TLDRs to the structured GitHub data: the PR link whose URL matches
+-- `pr_url` (same issue), and an optional commit SHA reported by the agent.
+ALTER TABLE tldrs ADD COLUMN IF NOT EXISTS github_pr_link_id UUID REFERENCES github_pr_links(id) ON DELETE SET NULL;
+ALTER TABLE tldrs ADD COLUMN IF NOT EXISTS commit_sha TEXT;
+
+UPDATE tldrs t SET github_pr_link_id = l.id
+FROM github_pr_links l
+WHERE l.issue_id = t.issue_id
+  AND l.pr_url = rtrim(t.pr_url, '/')
+  AND t.github_pr_link_id IS NULL;
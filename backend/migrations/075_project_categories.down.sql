@@ -0,0 +1,2 @@
+ALTER TABLE projects DROP COLUMN IF EXISTS validate_categories;
+DROP TABLE IF EXISTS project_categories;
@@ -0,0 +1,15 @@
+-- Managed category definitions per project, mirroring project_tags.
+CREATE TABLE IF NOT EXISTS project_categories (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  color TEXT NOT NULL DEFAULT '#6b7280',
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE(project_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_categories_project ON project_categories(project_id);
+
+-- When set, issue `category` values must be defined in project_categories.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS validate_categories BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Per-person completion reports are opt-in per org (off by default).
+-- Orgs are created lazily, so the settings endpoint upserts the row.
+ALTER TABLE organizations ADD COLUMN IF NOT EXISTS assignee_reports_enabled BOOLEAN NOT NULL DEFAULT false;
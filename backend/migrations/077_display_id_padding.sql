@@ -0,0 +1,4 @@
+-- Zero-pad new display_id numbers (BAA-0007) to this many digits. 0 = no padding.
+-- Existing issues keep their ids.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS display_id_padding INTEGER NOT NULL DEFAULT 0
+  CHECK (display_id_padding BETWEEN 0 AND 8);
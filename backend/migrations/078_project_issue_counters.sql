@@ -0,0 +1,14 @@
+-- One counter row per project for display_id numbers. Incrementing it with
+-- INSERT … ON CONFLICT DO UPDATE locks the row until the creating transaction
+-- ends, so concurrent creates get distinct numbers and a rollback frees its number.
+CREATE TABLE IF NOT EXISTS project_issue_counters (
+  project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+  last_number BIGINT NOT NULL
+);
+
+INSERT INTO project_issue_counters (project_id, last_number)
+SELECT p.id, COALESCE(MAX((SPLIT_PART(i.display_id, '-', 2))::bigint), 0)
+FROM projects p
+LEFT JOIN issues i ON i.project_id = p.id AND i.display_id ~ '^[^-]+-[0-9]+$'
+GROUP BY p.id
+ON CONFLICT (project_id) DO NOTHING;
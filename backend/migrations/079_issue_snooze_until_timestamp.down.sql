@@ -0,0 +1,9 @@
+DELETE FROM issue_notifications WHERE type = 'snooze_ended';
+ALTER TABLE issue_notifications DROP CONSTRAINT IF EXISTS issue_notifications_type_check;
+ALTER TABLE issue_notifications ADD CONSTRAINT issue_notifications_type_check
+  CHECK (type IN ('assigned', 'mentioned', 'status_changed', 'comment_added', 'issue_created', 'sla_breach'));
+
+DROP INDEX IF EXISTS idx_issues_snoozed_until;
+ALTER TABLE issues DROP COLUMN IF EXISTS snoozed_by;
+ALTER TABLE issues ALTER COLUMN snoozed_until TYPE DATE
+  USING (snoozed_until AT TIME ZONE 'UTC')::date;
@@ -0,0 +1,12 @@
+-- Snooze to a point in time ("remind me at 3pm") instead of a whole day, and
+-- remember who snoozed so they can be told when the issue resurfaces.
+-- Existing dates become midnight UTC, which keeps their visibility unchanged.
+ALTER TABLE issues ALTER COLUMN snoozed_until TYPE TIMESTAMPTZ
+  USING snoozed_until::timestamp AT TIME ZONE 'UTC';
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS snoozed_by TEXT;
+CREATE INDEX IF NOT EXISTS idx_issues_snoozed_until
+  ON issues (snoozed_until) WHERE snoozed_until IS NOT NULL;
+
+ALTER TABLE issue_notifications DROP CONSTRAINT IF EXISTS issue_notifications_type_check;
+ALTER TABLE issue_notifications ADD CONSTRAINT issue_notifications_type_check
+  CHECK (type IN ('assigned', 'mentioned', 'status_changed', 'comment_added', 'issue_created', 'sla_breach', 'snooze_ended'));
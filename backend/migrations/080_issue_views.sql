@@ -0,0 +1,9 @@
+-- Per-user "recently viewed" history; one row per issue, bumped on each view.
+-- Trimmed to the latest 50 per user by the API.
+CREATE TABLE IF NOT EXISTS issue_views (
+  user_id TEXT NOT NULL,
+  issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+  viewed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (user_id, issue_id)
+);
+CREATE INDEX IF NOT EXISTS idx_issue_views_user_recent ON issue_views (user_id, viewed_at DESC);
@@ -0,0 +1,9 @@
+-- Per-user starred issues and projects. entity_id is not a foreign key (it points
+-- at either table); rows for deleted entities simply stop resolving.
+CREATE TABLE IF NOT EXISTS user_favorites (
+  user_id TEXT NOT NULL,
+  entity_type TEXT NOT NULL CHECK (entity_type IN ('issue', 'project')),
+  entity_id UUID NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (user_id, entity_type, entity_id)
+);
@@ -0,0 +1,13 @@
+-- Time logged against issues (for client billing). Project/org scope comes
+-- through the issue.
+CREATE TABLE IF NOT EXISTS time_entries (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+  user_id TEXT NOT NULL,
+  user_name TEXT,
+  minutes INTEGER NOT NULL CHECK (minutes > 0 AND minutes <= 1440),
+  note TEXT,
+  logged_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_time_entries_issue ON time_entries (issue_id, logged_at DESC);
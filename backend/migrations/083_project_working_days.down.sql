@@ -0,0 +1,2 @@
+ALTER TABLE projects DROP COLUMN IF EXISTS holidays;
+ALTER TABLE projects DROP COLUMN IF EXISTS working_days;
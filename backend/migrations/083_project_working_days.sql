@@ -0,0 +1,5 @@
+-- Working-day calendar for auto due dates. working_days holds ISO weekdays
+-- (1 = Monday … 7 = Sunday); NULL means every day counts. Holidays are skipped too.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS working_days INTEGER[]
+  CHECK (working_days IS NULL OR (cardinality(working_days) > 0 AND working_days <@ ARRAY[1,2,3,4,5,6,7]));
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS holidays DATE[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,13 @@
+DROP INDEX IF EXISTS idx_issues_sla_open;
+ALTER TABLE issues DROP COLUMN IF EXISTS sla_warned_at;
+ALTER TABLE issues DROP COLUMN IF EXISTS sla_responded_at;
+ALTER TABLE issues DROP COLUMN IF EXISTS sla_response_due_at;
+ALTER TABLE issues RENAME COLUMN sla_resolution_due_at TO sla_deadline;
+
+ALTER TABLE sla_policies DROP CONSTRAINT IF EXISTS sla_policies_target_check;
+ALTER TABLE sla_policies ADD COLUMN deadline_hours INT;
+UPDATE sla_policies SET deadline_hours = GREATEST(1, COALESCE(resolution_minutes, response_minutes) / 60);
+ALTER TABLE sla_policies ALTER COLUMN deadline_hours SET NOT NULL;
+ALTER TABLE sla_policies DROP COLUMN resolution_minutes;
+ALTER TABLE sla_policies DROP COLUMN response_minutes;
+ALTER TABLE sla_policies RENAME TO sla_rules;
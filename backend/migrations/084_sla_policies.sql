@@ -0,0 +1,23 @@
+-- SLA policies: response and resolution targets (minutes) per priority.
+-- Replaces sla_rules; existing deadlines become resolution targets.
+ALTER TABLE sla_rules RENAME TO sla_policies;
+ALTER TABLE sla_policies ADD COLUMN IF NOT EXISTS response_minutes INTEGER CHECK (response_minutes > 0);
+ALTER TABLE sla_policies ADD COLUMN IF NOT EXISTS resolution_minutes INTEGER CHECK (resolution_minutes > 0);
+UPDATE sla_policies SET resolution_minutes = deadline_hours * 60;
+ALTER TABLE sla_policies DROP COLUMN deadline_hours;
+ALTER TABLE sla_policies ADD CONSTRAINT sla_policies_target_check
+  CHECK (response_minutes IS NOT NULL OR resolution_minutes IS NOT NULL);
+
+-- Per-issue targets. A response is the first status change or comment from
+-- someone other than the reporter; sla_warned_at dedupes "breach imminent".
+ALTER TABLE issues RENAME COLUMN sla_deadline TO sla_resolution_due_at;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS sla_response_due_at TIMESTAMPTZ;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS sla_responded_at TIMESTAMPTZ;
+ALTER TABLE issues ADD COLUMN IF NOT EXISTS sla_warned_at TIMESTAMPTZ;
+CREATE INDEX IF NOT EXISTS idx_issues_sla_open ON issues (sla_response_due_at, sla_resolution_due_at)
+  WHERE sla_breached IS NOT TRUE AND (sla_response_due_at IS NOT NULL OR sla_resolution_due_at IS NOT NULL);
+
+-- Nothing flagged breaches before; do it now so the first sweep doesn't
+-- notify about long-gone deadlines.
+UPDATE issues SET sla_breached = true
+WHERE sla_resolution_due_at < now() AND COALESCE(closed_at, now()) > sla_resolution_due_at;
@@ -0,0 +1,4 @@
+ALTER TABLE github_repo_mappings DROP COLUMN IF EXISTS label_mapping;
+ALTER TABLE github_repo_mappings DROP COLUMN IF EXISTS default_tags;
+ALTER TABLE github_repo_mappings DROP COLUMN IF EXISTS default_priority;
+ALTER TABLE github_repo_mappings DROP COLUMN IF EXISTS default_issue_type;
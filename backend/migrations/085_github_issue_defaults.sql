@@ -0,0 +1,9 @@
+-- Defaults for issues auto-created from GitHub issues, plus a GitHub label map:
+-- { "bug": { "type": "bug", "priority": "high" }, "docs": "documentation" }
+-- (a string value is a tag; an object may set type, priority and tags).
+ALTER TABLE github_repo_mappings ADD COLUMN IF NOT EXISTS default_issue_type TEXT
+  CHECK (default_issue_type IN ('bug', 'feature', 'improvement', 'question'));
+ALTER TABLE github_repo_mappings ADD COLUMN IF NOT EXISTS default_priority TEXT
+  CHECK (default_priority IN ('urgent', 'high', 'medium', 'low'));
+ALTER TABLE github_repo_mappings ADD COLUMN IF NOT EXISTS default_tags TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE github_repo_mappings ADD COLUMN IF NOT EXISTS label_mapping JSONB NOT NULL DEFAULT '{}';
@@ -0,0 +1,14 @@
+-- Job that pushes Baaton tag changes to the linked GitHub issue's labels
+ALTER TABLE github_sync_jobs DROP CONSTRAINT IF EXISTS github_sync_jobs_job_type_check;
+ALTER TABLE github_sync_jobs ADD CONSTRAINT github_sync_jobs_job_type_check
+  CHECK (job_type IN (
+    'sync_issue_to_github',
+    'sync_issue_from_github',
+    'sync_pr',
+    'sync_comment_to_github',
+    'sync_comment_from_github',
+    'sync_status',
+    'sync_labels_to_github',
+    'initial_import',
+    'full_resync'
+  ));
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_gh_events_status_created;
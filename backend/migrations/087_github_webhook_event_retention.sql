@@ -0,0 +1,2 @@
+-- Supports pruning old webhook events by status and age
+CREATE INDEX IF NOT EXISTS idx_gh_events_status_created ON github_webhook_events(status, created_at);
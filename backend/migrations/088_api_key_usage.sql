@@ -0,0 +1,12 @@
+-- Per-request log of API key activity, for spotting leaked keys
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    id BIGSERIAL PRIMARY KEY,
+    api_key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    status SMALLINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_usage_key_created ON api_key_usage(api_key_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_api_key_usage_created ON api_key_usage(created_at);
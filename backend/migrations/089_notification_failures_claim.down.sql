@@ -0,0 +1,4 @@
+UPDATE notification_failures SET status = 'retrying' WHERE status = 'sending';
+ALTER TABLE notification_failures DROP CONSTRAINT IF EXISTS notification_failures_status_check;
+ALTER TABLE notification_failures
+  ADD CONSTRAINT notification_failures_status_check CHECK (status IN ('retrying', 'failed'));
@@ -0,0 +1,5 @@
+-- The retry worker claims rows by flipping them to `sending`, so replicas
+-- never replay the same failure twice.
+ALTER TABLE notification_failures DROP CONSTRAINT IF EXISTS notification_failures_status_check;
+ALTER TABLE notification_failures
+  ADD CONSTRAINT notification_failures_status_check CHECK (status IN ('retrying', 'sending', 'failed'));
@@ -89,17 +89,27 @@ pub async fn find_linked_issue(
     Ok(None)
 }
 
-/// Look up a Baaton issue by its display_id within a project.
+/// Look up a Baaton issue by its display_id within a project. Leading zeros
+/// in the number are ignored, so "BAA-7" finds "BAA-0007" and vice versa.
 async fn find_issue_by_display_id(
     pool: &PgPool,
     project_id: Uuid,
     display_id: &str,
 ) -> Result<Option<Uuid>, anyhow::Error> {
+    let Some((prefix, number)) = display_id.split_once('-') else {
+        return Ok(None);
+    };
     let result: Option<(Uuid,)> = sqlx::query_as(
-        "SELECT id FROM issues WHERE project_id = $1 AND display_id = $2",
+        r#"SELECT id FROM issues
+           WHERE project_id = $1
+             AND SPLIT_PART(display_id, '-', 1) = $2
+             AND LTRIM(SPLIT_PART(display_id, '-', 2), '0') = LTRIM($3, '0')
+           ORDER BY created_at ASC
+           LIMIT 1"#,
     )
     .bind(project_id)
-    .bind(display_id)
+    .bind(prefix)
+    .bind(number)
     .fetch_optional(pool)
     .await?;
 
@@ -1,14 +1,154 @@
-use std::time::Duration;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 use sqlx::PgPool;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::github::client::GitHubClient;
+use crate::github::label_mapper::tag_label;
+use crate::models::github::GitHubRepoMapping;
+
+/// Identifies this process in `github_sync_jobs.claimed_by`.
+/// Host (Railway replica / container hostname) plus a per-boot UUID.
+pub fn instance_id() -> &'static str {
+    static INSTANCE_ID: OnceLock<String> = OnceLock::new();
+    INSTANCE_ID.get_or_init(|| {
+        let host = std::env::var("RAILWAY_REPLICA_ID")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| "local".to_string());
+        format!("{}:{}", host, uuid::Uuid::new_v4())
+    })
+}
+
+/// GitHub App client for jobs that write to GitHub; None when the App isn't
+/// configured. Built once so installation tokens stay cached.
+fn github_client() -> Option<&'static GitHubClient> {
+    static CLIENT: OnceLock<Option<GitHubClient>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| match GitHubClient::from_env() {
+            Ok(c) => Some(c),
+            Err(e) => {
+                tracing::warn!("GitHub App not configured, label sync disabled: {}", e);
+                None
+            }
+        })
+        .as_ref()
+}
+
+/// Queue a `sync_labels_to_github` job for a tag change on a Baaton issue
+/// linked to a GitHub issue, when the repo mapping syncs issues toward
+/// GitHub. Tags are translated through the mapping's label map; tags that
+/// stay on the Baaton side (see `tag_label`) are skipped.
+pub async fn enqueue_label_sync(
+    pool: &PgPool,
+    issue_id: Uuid,
+    old_tags: &[String],
+    new_tags: &[String],
+) -> Result<(), anyhow::Error> {
+    let mapping = sqlx::query_as::<_, GitHubRepoMapping>(
+        r#"SELECT m.* FROM github_repo_mappings m
+           JOIN github_issue_links l ON l.github_repo_id = m.github_repo_id
+           JOIN issues i ON i.id = l.issue_id AND i.project_id = m.project_id
+           WHERE l.issue_id = $1 AND m.is_active AND m.sync_issues
+             AND m.sync_direction <> 'github_to_baaton'"#,
+    )
+    .bind(issue_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some(mapping) = mapping else {
+        return Ok(());
+    };
+
+    let labels = |tags: &[String], other: &[String]| -> Vec<String> {
+        let mut out: Vec<String> = tags
+            .iter()
+            .filter(|t| !other.contains(t))
+            .filter_map(|t| tag_label(&mapping, t))
+            .collect();
+        out.sort();
+        out.dedup();
+        out
+    };
+    let add = labels(new_tags, old_tags);
+    let remove = labels(old_tags, new_tags);
+    if add.is_empty() && remove.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"INSERT INTO github_sync_jobs (job_type, issue_id, github_repo_id, payload)
+           VALUES ('sync_labels_to_github', $1, $2, $3)"#,
+    )
+    .bind(issue_id)
+    .bind(mapping.github_repo_id)
+    .bind(serde_json::json!({ "add": add, "remove": remove }))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// A job still `processing` after this long is assumed orphaned by a crashed instance.
+const STUCK_JOB_MINUTES: i32 = 5;
+/// How often the reaper pass runs.
+const REAPER_INTERVAL: Duration = Duration::from_secs(60);
+/// How often old webhook events are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+/// Days to keep `completed` webhook events (`GITHUB_WEBHOOK_RETENTION_DAYS`).
+const DEFAULT_WEBHOOK_RETENTION_DAYS: i32 = 30;
+/// Days to keep `failed` webhook events, for debugging
+/// (`GITHUB_WEBHOOK_FAILED_RETENTION_DAYS`).
+const DEFAULT_FAILED_WEBHOOK_RETENTION_DAYS: i32 = 90;
+/// Rows deleted per statement, so a large backlog doesn't hold one long lock.
+const PRUNE_BATCH_SIZE: i64 = 1000;
+
+/// Retention in days from `var`, falling back to `default`.
+fn retention_days(var: &str, default: i32) -> i32 {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse::<i32>().ok())
+        .filter(|&d| d > 0)
+        .unwrap_or(default)
+}
+
+/// Sleep for `dur`, returning early if shutdown is signalled.
+async fn sleep_or_shutdown(dur: Duration, shutdown: &mut watch::Receiver<bool>) {
+    tokio::select! {
+        _ = tokio::time::sleep(dur) => {}
+        _ = shutdown.changed() => {}
+    }
+}
 
 /// Start the background job processor.
 ///
 /// Polls `github_sync_jobs` for pending jobs and `github_webhook_events`
-/// for events that need retry. Runs forever as a tokio task.
-pub async fn start_job_runner(pool: PgPool) {
-    tracing::info!("GitHub sync job runner started");
+/// for events that need retry, and hourly prunes webhook events past
+/// their retention. Runs until `shutdown` flips to true: the
+/// in-flight job is allowed to finish, no new jobs are claimed, and any
+/// `processing` rows still owned by this instance are put back to `pending`.
+pub async fn start_job_runner(pool: PgPool, mut shutdown: watch::Receiver<bool>) {
+    tracing::info!(instance_id = %instance_id(), "GitHub sync job runner started");
+
+    let mut last_reap: Option<Instant> = None;
+    let mut last_prune: Option<Instant> = None;
+    while !*shutdown.borrow() {
+        // 0. Reclaim jobs orphaned by crashed instances
+        if last_reap.is_none_or(|t| t.elapsed() >= REAPER_INTERVAL) {
+            last_reap = Some(Instant::now());
+            match reclaim_stuck_jobs(&pool).await {
+                Ok(n) if n > 0 => tracing::warn!("Reclaimed {} stuck sync jobs", n),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Stuck job reaper error: {}", e),
+            }
+        }
+        if last_prune.is_none_or(|t| t.elapsed() >= PRUNE_INTERVAL) {
+            last_prune = Some(Instant::now());
+            match prune_webhook_events(&pool).await {
+                Ok(n) if n > 0 => tracing::info!("Pruned {} old webhook events", n),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Webhook event pruning error: {}", e),
+            }
+        }
 
-    loop {
         // 1. Retry failed webhook events
         match retry_failed_events(&pool).await {
             Ok(count) if count > 0 => {
@@ -26,14 +166,94 @@ pub async fn start_job_runner(pool: PgPool) {
             Ok(true) => continue,
             Ok(false) => {
                 // Nothing to do — sleep before next poll
-                tokio::time::sleep(Duration::from_secs(2)).await;
+                sleep_or_shutdown(Duration::from_secs(2), &mut shutdown).await;
             }
             Err(e) => {
                 tracing::error!("Job runner error: {}", e);
-                tokio::time::sleep(Duration::from_secs(5)).await;
+                sleep_or_shutdown(Duration::from_secs(5), &mut shutdown).await;
             }
         }
     }
+
+    match release_claimed_jobs(&pool).await {
+        Ok(n) if n > 0 => tracing::info!("Released {} in-flight sync jobs back to pending", n),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to release claimed sync jobs: {}", e),
+    }
+    tracing::info!("GitHub sync job runner stopped");
+}
+
+/// Reset jobs stuck in `processing` for longer than [`STUCK_JOB_MINUTES`]
+/// (instance crashed mid-job). Counts as a failed attempt, so a job that
+/// keeps killing its worker eventually lands in `dead`.
+async fn reclaim_stuck_jobs(pool: &PgPool) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query(
+        r#"UPDATE github_sync_jobs SET
+            status = CASE WHEN retry_count + 1 >= max_retries THEN 'dead' ELSE 'pending' END,
+            retry_count = retry_count + 1,
+            last_error = 'Reclaimed after being stuck in processing (claimed by ' || COALESCE(claimed_by, 'unknown') || ')',
+            started_at = NULL,
+            claimed_by = NULL
+           WHERE status = 'processing'
+             AND started_at < now() - make_interval(mins => $1)"#,
+    )
+    .bind(STUCK_JOB_MINUTES)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Delete `completed` / `skipped` webhook events older than the retention
+/// window and `failed` ones older than the (longer) failed retention.
+/// Pending and processing events are never pruned.
+async fn prune_webhook_events(pool: &PgPool) -> Result<u64, anyhow::Error> {
+    let completed_days =
+        retention_days("GITHUB_WEBHOOK_RETENTION_DAYS", DEFAULT_WEBHOOK_RETENTION_DAYS);
+    let failed_days = retention_days(
+        "GITHUB_WEBHOOK_FAILED_RETENTION_DAYS",
+        DEFAULT_FAILED_WEBHOOK_RETENTION_DAYS,
+    );
+
+    let mut total = 0;
+    let groups: [(&[&str], i32); 2] = [
+        (&["completed", "skipped"], completed_days),
+        (&["failed"], failed_days),
+    ];
+    for (statuses, days) in groups {
+        loop {
+            let result = sqlx::query(
+                r#"DELETE FROM github_webhook_events
+                   WHERE id IN (
+                       SELECT id FROM github_webhook_events
+                       WHERE status = ANY($1) AND created_at < now() - make_interval(days => $2)
+                       LIMIT $3
+                   )"#,
+            )
+            .bind(statuses)
+            .bind(days)
+            .bind(PRUNE_BATCH_SIZE)
+            .execute(pool)
+            .await?;
+            total += result.rows_affected();
+            if result.rows_affected() < PRUNE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Put any `processing` jobs claimed by this instance back to `pending`.
+async fn release_claimed_jobs(pool: &PgPool) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query(
+        r#"UPDATE github_sync_jobs SET
+            status = 'pending', started_at = NULL, claimed_by = NULL
+           WHERE status = 'processing' AND claimed_by = $1"#,
+    )
+    .bind(instance_id())
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
 }
 
 /// Retry webhook events that failed processing (status='pending', retry_count > 0).
@@ -61,14 +281,17 @@ async fn retry_failed_events(pool: &PgPool) -> Result<i64, anyhow::Error> {
     Ok(count)
 }
 
+/// (id, job_type, payload, issue_id, github_repo_id) of a claimed job.
+type ClaimedJob = (Uuid, String, serde_json::Value, Option<Uuid>, Option<i64>);
+
 /// Claim and process the next pending sync job.
 ///
 /// Uses SELECT FOR UPDATE SKIP LOCKED for safe multi-instance operation.
 async fn process_next_job(pool: &PgPool) -> Result<bool, anyhow::Error> {
     // Atomic claim
-    let job: Option<(uuid::Uuid, String, serde_json::Value)> = sqlx::query_as(
+    let job: Option<ClaimedJob> = sqlx::query_as(
         r#"UPDATE github_sync_jobs SET
-            status = 'processing', started_at = now()
+            status = 'processing', started_at = now(), claimed_by = $1
            WHERE id = (
                SELECT id FROM github_sync_jobs
                WHERE status = 'pending' AND scheduled_at <= now()
@@ -76,12 +299,13 @@ async fn process_next_job(pool: &PgPool) -> Result<bool, anyhow::Error> {
                LIMIT 1
                FOR UPDATE SKIP LOCKED
            )
-           RETURNING id, job_type, payload"#,
+           RETURNING id, job_type, payload, issue_id, github_repo_id"#,
     )
+    .bind(instance_id())
     .fetch_optional(pool)
     .await?;
 
-    let (job_id, job_type, _payload) = match job {
+    let (job_id, job_type, payload, issue_id, github_repo_id) = match job {
         Some(j) => j,
         None => return Ok(false),
     };
@@ -91,6 +315,7 @@ async fn process_next_job(pool: &PgPool) -> Result<bool, anyhow::Error> {
     // For now, just mark as completed.
     // Full sync logic (sync_issue_to_github, etc.) will be implemented in Phase 6.
     let result: Result<(), anyhow::Error> = match job_type.as_str() {
+        "sync_labels_to_github" => sync_labels_to_github(pool, issue_id, github_repo_id, &payload).await,
         "sync_issue_to_github"
         | "sync_issue_from_github"
         | "sync_pr"
@@ -139,3 +364,56 @@ async fn process_next_job(pool: &PgPool) -> Result<bool, anyhow::Error> {
 
     Ok(true)
 }
+
+/// Apply a queued label change to the linked GitHub issue. Labels missing
+/// from the repo are created by GitHub; removing a label the issue no longer
+/// has is not an error.
+async fn sync_labels_to_github(
+    pool: &PgPool,
+    issue_id: Option<Uuid>,
+    github_repo_id: Option<i64>,
+    payload: &serde_json::Value,
+) -> Result<(), anyhow::Error> {
+    let (Some(issue_id), Some(github_repo_id)) = (issue_id, github_repo_id) else {
+        return Ok(()); // Issue deleted since the job was queued
+    };
+    let client = github_client().ok_or_else(|| anyhow::anyhow!("GitHub App not configured"))?;
+
+    let target: Option<(i64, String, String, i32)> = sqlx::query_as(
+        r#"SELECT r.installation_id, r.owner, r.name, l.github_issue_number
+           FROM github_issue_links l
+           JOIN github_repositories r ON r.github_repo_id = l.github_repo_id
+           WHERE l.issue_id = $1 AND l.github_repo_id = $2"#,
+    )
+    .bind(issue_id)
+    .bind(github_repo_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some((installation_id, owner, name, number)) = target else {
+        return Ok(()); // Unlinked since the job was queued
+    };
+
+    let strings = |key: &str| -> Vec<String> {
+        payload[key]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+    let crab = client.for_installation(installation_id as u64).await?;
+    let issues = crab.issues(&owner, &name);
+
+    let add = strings("add");
+    if !add.is_empty() {
+        issues.add_labels(number as u64, &add).await?;
+    }
+    for label in strings("remove") {
+        match issues.remove_label(number as u64, &label).await {
+            Ok(_) => {}
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code == 404 => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    tracing::info!(issue_id = %issue_id, github_repo_id, number, "Synced labels to GitHub");
+    Ok(())
+}
@@ -0,0 +1,323 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::github::GitHubRepoMapping;
+
+const ISSUE_TYPES: &[&str] = &["bug", "feature", "improvement", "question"];
+const PRIORITIES: &[&str] = &["urgent", "high", "medium", "low"];
+
+/// Type, priority and tags for an issue auto-created from a GitHub issue.
+#[derive(Debug, PartialEq)]
+pub struct ImportedIssueFields {
+    pub issue_type: String,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// The `label_mapping` entry for `label`, matched case-insensitively.
+fn mapping_entry<'a>(mapping: &'a GitHubRepoMapping, label: &str) -> Option<&'a serde_json::Value> {
+    mapping
+        .label_mapping
+        .as_object()?
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(label))
+        .map(|(_, v)| v)
+}
+
+/// Baaton tags for a GitHub label: the mapped tag, the mapped object's
+/// `tags` (possibly none, for labels that only set type/priority), or the
+/// label's own name when it isn't mapped.
+pub fn label_tags(mapping: &GitHubRepoMapping, label: &str) -> Vec<String> {
+    match mapping_entry(mapping, label) {
+        Some(serde_json::Value::String(tag)) => vec![tag.clone()],
+        Some(serde_json::Value::Object(entry)) => entry
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|t| t.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => vec![label.to_string()],
+    }
+}
+
+/// GitHub label for a Baaton tag: the label whose mapping produces the tag,
+/// else the tag itself. None for tags that stay on the Baaton side: the
+/// mapping's `default_tags` and automatic `auto:` markers.
+pub fn tag_label(mapping: &GitHubRepoMapping, tag: &str) -> Option<String> {
+    if tag.starts_with("auto:") || mapping.default_tags.iter().any(|t| t == tag) {
+        return None;
+    }
+    let mapped = mapping.label_mapping.as_object().and_then(|entries| {
+        entries.iter().find_map(|(label, v)| {
+            let produces = match v {
+                serde_json::Value::String(t) => t == tag,
+                serde_json::Value::Object(entry) => entry
+                    .get("tags")
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|a| a.iter().any(|t| t.as_str() == Some(tag))),
+                _ => false,
+            };
+            produces.then(|| label.clone())
+        })
+    });
+    Some(mapped.unwrap_or_else(|| tag.to_string()))
+}
+
+/// Bring a linked Baaton issue's tags in line with a GitHub `labeled` /
+/// `unlabeled` (or any other issue/PR) event. `labels` is the item's current
+/// label set from the payload; their tags are added. On `unlabeled`, the
+/// removed label's tags are dropped unless a remaining label still maps to
+/// them. Sets the sync lock like `apply_status_mapping`. Returns whether
+/// the tags changed.
+pub async fn apply_github_labels(
+    pool: &PgPool,
+    mapping: &GitHubRepoMapping,
+    issue_id: Uuid,
+    labels: &[&str],
+    removed_label: Option<&str>,
+) -> Result<bool, anyhow::Error> {
+    let mut add: Vec<String> = labels.iter().flat_map(|l| label_tags(mapping, l)).collect();
+    add.sort();
+    add.dedup();
+    let remove: Vec<String> = removed_label
+        .map(|l| label_tags(mapping, l))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|t| !add.contains(t))
+        .collect();
+
+    let updated = sqlx::query(
+        r#"UPDATE issues SET
+            tags = ARRAY(
+                SELECT t FROM unnest(tags) WITH ORDINALITY AS u(t, n)
+                WHERE t <> ALL($3) ORDER BY n
+            ) || ARRAY(SELECT unnest($2::text[]) EXCEPT SELECT unnest(tags)),
+            sync_source = 'github',
+            sync_lock_until = now() + interval '5 seconds',
+            updated_at = now()
+           WHERE id = $1 AND (NOT $2::text[] <@ tags OR tags && $3::text[])"#,
+    )
+    .bind(issue_id)
+    .bind(&add)
+    .bind(&remove)
+    .execute(pool)
+    .await?;
+
+    let changed = updated.rows_affected() > 0;
+    if changed {
+        tracing::info!(issue_id = %issue_id, added = ?add, removed = ?remove, "Applied GitHub labels");
+    }
+    Ok(changed)
+}
+
+/// Resolve the fields of an issue imported through `mapping` from the
+/// GitHub issue's `labels`.
+///
+/// Starts from the mapping's defaults, then applies each label through
+/// `label_mapping` (matched case-insensitively). A string value adds that
+/// tag; an object may set `type`, `priority` and `tags`. The first label
+/// that sets a type wins, and the most urgent priority wins. Unmapped
+/// labels become tags of the same name.
+pub fn resolve_imported_fields(
+    mapping: &GitHubRepoMapping,
+    labels: &[&str],
+) -> ImportedIssueFields {
+    let mut issue_type: Option<String> = None;
+    let mut priority = mapping.default_priority.clone();
+    let mut mapped_priority: Option<String> = None;
+    let mut tags = mapping.default_tags.clone();
+
+    for label in labels {
+        tags.extend(label_tags(mapping, label));
+        if let Some(serde_json::Value::Object(entry)) = mapping_entry(mapping, label) {
+            if issue_type.is_none() {
+                issue_type = entry
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .filter(|t| ISSUE_TYPES.contains(t))
+                    .map(str::to_string);
+            }
+            if let Some(p) = entry
+                .get("priority")
+                .and_then(|v| v.as_str())
+                .filter(|p| PRIORITIES.contains(p))
+            {
+                let rank = |p: &str| PRIORITIES.iter().position(|x| *x == p);
+                if mapped_priority
+                    .as_deref()
+                    .is_none_or(|cur| rank(p) < rank(cur))
+                {
+                    mapped_priority = Some(p.to_string());
+                }
+            }
+        }
+    }
+    if mapped_priority.is_some() {
+        priority = mapped_priority;
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    tags.retain(|t| !t.trim().is_empty() && seen.insert(t.clone()));
+
+    ImportedIssueFields {
+        issue_type: issue_type
+            .or_else(|| mapping.default_issue_type.clone())
+            .unwrap_or_else(|| "feature".to_string()),
+        priority,
+        tags,
+    }
+}
+
+/// Check a mapping's issue defaults before storing them. An empty type or
+/// priority means "clear". Returns a message for the first problem found.
+pub fn validate_issue_defaults(
+    default_issue_type: Option<&str>,
+    default_priority: Option<&str>,
+    label_mapping: Option<&serde_json::Value>,
+) -> Result<(), String> {
+    if let Some(t) = default_issue_type.filter(|t| !t.is_empty() && !ISSUE_TYPES.contains(t)) {
+        return Err(format!(
+            "default_issue_type '{t}' is not one of {}",
+            ISSUE_TYPES.join(", ")
+        ));
+    }
+    if let Some(p) = default_priority.filter(|p| !p.is_empty() && !PRIORITIES.contains(p)) {
+        return Err(format!(
+            "default_priority '{p}' is not one of {}",
+            PRIORITIES.join(", ")
+        ));
+    }
+    label_mapping.map_or(Ok(()), validate_label_mapping)
+}
+
+/// Check a `label_mapping` value before storing it. Returns a message for
+/// the first problem found.
+pub fn validate_label_mapping(label_mapping: &serde_json::Value) -> Result<(), String> {
+    let Some(entries) = label_mapping.as_object() else {
+        return Err(
+            "label_mapping must be an object of GitHub label → tag or { type, priority, tags }"
+                .into(),
+        );
+    };
+    for (label, value) in entries {
+        match value {
+            serde_json::Value::String(tag) if !tag.trim().is_empty() => {}
+            serde_json::Value::Object(entry) => {
+                for (key, v) in entry {
+                    let ok = match key.as_str() {
+                        "type" => v.as_str().is_some_and(|t| ISSUE_TYPES.contains(&t)),
+                        "priority" => v.as_str().is_some_and(|p| PRIORITIES.contains(&p)),
+                        "tags" => v
+                            .as_array()
+                            .is_some_and(|a| a.iter().all(|t| t.as_str().is_some())),
+                        _ => false,
+                    };
+                    if !ok {
+                        return Err(format!("label_mapping[\"{label}\"].{key} is invalid"));
+                    }
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "label_mapping[\"{label}\"] must be a tag or an object"
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(label_mapping: serde_json::Value) -> GitHubRepoMapping {
+        GitHubRepoMapping {
+            id: uuid::Uuid::nil(),
+            project_id: uuid::Uuid::nil(),
+            github_repo_id: 1,
+            sync_direction: "bidirectional".into(),
+            sync_issues: true,
+            sync_prs: true,
+            sync_comments: true,
+            auto_create_issues: true,
+            status_mapping: serde_json::json!({}),
+            default_issue_type: Some("question".into()),
+            default_priority: Some("low".into()),
+            default_tags: vec!["github".into()],
+            label_mapping,
+            is_active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_imported_fields() {
+        let m = mapping(serde_json::json!({
+            "bug": { "type": "bug", "priority": "high" },
+            "P0": { "priority": "urgent", "tags": ["incident"] },
+            "docs": "documentation",
+        }));
+
+        assert_eq!(
+            resolve_imported_fields(&m, &["Bug", "docs", "wontfix"]),
+            ImportedIssueFields {
+                issue_type: "bug".into(),
+                priority: Some("high".into()),
+                tags: vec!["github".into(), "documentation".into(), "wontfix".into()],
+            }
+        );
+        // Most urgent mapped priority wins
+        assert_eq!(
+            resolve_imported_fields(&m, &["bug", "p0"])
+                .priority
+                .as_deref(),
+            Some("urgent")
+        );
+        // No mapped labels: defaults
+        assert_eq!(
+            resolve_imported_fields(&m, &[]),
+            ImportedIssueFields {
+                issue_type: "question".into(),
+                priority: Some("low".into()),
+                tags: vec!["github".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_label_tag_round_trip() {
+        let m = mapping(serde_json::json!({
+            "bug": { "type": "bug" },
+            "P0": { "priority": "urgent", "tags": ["incident"] },
+            "docs": "documentation",
+        }));
+
+        assert_eq!(label_tags(&m, "DOCS"), vec!["documentation"]);
+        assert_eq!(label_tags(&m, "p0"), vec!["incident"]);
+        assert!(label_tags(&m, "bug").is_empty());
+        assert_eq!(label_tags(&m, "security"), vec!["security"]);
+
+        assert_eq!(tag_label(&m, "documentation").as_deref(), Some("docs"));
+        assert_eq!(tag_label(&m, "incident").as_deref(), Some("P0"));
+        assert_eq!(tag_label(&m, "security").as_deref(), Some("security"));
+        assert_eq!(tag_label(&m, "github"), None);
+        assert_eq!(tag_label(&m, "auto:status:done"), None);
+    }
+
+    #[test]
+    fn test_validate_label_mapping() {
+        assert!(validate_label_mapping(
+            &serde_json::json!({"bug": {"type": "bug"}, "docs": "documentation"})
+        )
+        .is_ok());
+        assert!(validate_label_mapping(&serde_json::json!({"bug": {"type": "defect"}})).is_err());
+        assert!(validate_label_mapping(&serde_json::json!({"bug": 3})).is_err());
+        assert!(validate_label_mapping(&serde_json::json!(["bug"])).is_err());
+    }
+}
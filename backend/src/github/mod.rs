@@ -2,4 +2,5 @@ pub mod client;
 pub mod webhook_processor;
 pub mod issue_linker;
 pub mod status_mapper;
+pub mod label_mapper;
 pub mod jobs;
@@ -1,54 +1,88 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
+/// A status change applied by `apply_status_mapping`, for the activity log.
+#[derive(Debug)]
+pub struct AppliedStatusChange {
+    pub old_status: String,
+    pub new_status: String,
+}
+
+/// The `status_mapping` key for a PR in `pr_state` ("open", "draft",
+/// "merged" or "closed").
+pub fn pr_mapping_key(pr_state: &str) -> Option<&'static str> {
+    match pr_state {
+        "open" | "draft" => Some("pr_opened"),
+        "merged" => Some("pr_merged"),
+        "closed" => Some("pr_closed"),
+        _ => None,
+    }
+}
+
+/// The status `mapping_key` maps to. None when the key is null or missing,
+/// meaning the event doesn't change the status.
+pub fn mapped_status<'a>(
+    status_mapping: &'a serde_json::Value,
+    mapping_key: &str,
+) -> Option<&'a str> {
+    status_mapping.get(mapping_key).and_then(|v| v.as_str())
+}
+
 /// Apply a status mapping for a GitHub event.
 ///
 /// Looks up `mapping_key` (e.g. "pr_opened", "pr_merged") in the mapping's
 /// `status_mapping` JSON. If a non-null value is found, updates the Baaton
 /// issue's status — but only if the sync lock has expired (anti-echo).
 ///
-/// The `actor_name` is used for the activity log.
+/// Returns the change when the status actually moved, so the caller can
+/// record it against the GitHub bot actor.
 pub async fn apply_status_mapping(
     pool: &PgPool,
     issue_id: Uuid,
     status_mapping: &serde_json::Value,
     mapping_key: &str,
-    _actor_name: &str,
-) -> Result<(), anyhow::Error> {
-    let new_status = match status_mapping.get(mapping_key) {
-        Some(serde_json::Value::String(s)) => s.clone(),
-        _ => return Ok(()), // null or missing → don't change status
+) -> Result<Option<AppliedStatusChange>, anyhow::Error> {
+    let Some(new_status) = mapped_status(status_mapping, mapping_key).map(str::to_string) else {
+        return Ok(None); // null or missing → don't change status
     };
 
     // Update issue status with sync lock to prevent echo loops.
     // Only update if there is no active sync lock (lock expired or never set).
-    let result = sqlx::query(
+    let old_status: Option<String> = sqlx::query_scalar(
         r#"UPDATE issues SET
             status = $2,
             sync_source = 'github',
             sync_lock_until = now() + interval '5 seconds',
             updated_at = now()
-           WHERE id = $1
-             AND (sync_lock_until IS NULL OR sync_lock_until < now())"#,
+           FROM (SELECT id, status AS old_status FROM issues WHERE id = $1 FOR UPDATE) prev
+           WHERE issues.id = prev.id
+             AND (sync_lock_until IS NULL OR sync_lock_until < now())
+           RETURNING prev.old_status"#,
     )
     .bind(issue_id)
     .bind(&new_status)
-    .execute(pool)
+    .fetch_optional(pool)
     .await?;
 
-    if result.rows_affected() > 0 {
-        tracing::info!(
-            issue_id = %issue_id,
-            new_status = %new_status,
-            mapping_key = %mapping_key,
-            "Applied GitHub status mapping"
-        );
-    } else {
-        tracing::debug!(
-            issue_id = %issue_id,
-            "Skipped status mapping (sync locked)"
-        );
+    match old_status {
+        Some(old_status) => {
+            tracing::info!(
+                issue_id = %issue_id,
+                new_status = %new_status,
+                mapping_key = %mapping_key,
+                "Applied GitHub status mapping"
+            );
+            Ok((old_status != new_status).then_some(AppliedStatusChange {
+                old_status,
+                new_status,
+            }))
+        }
+        None => {
+            tracing::debug!(
+                issue_id = %issue_id,
+                "Skipped status mapping (sync locked)"
+            );
+            Ok(None)
+        }
     }
-
-    Ok(())
 }
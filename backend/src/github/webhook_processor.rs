@@ -1,19 +1,26 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::github::status_mapper::AppliedStatusChange;
 use crate::models::github::{GitHubRepoMapping, GitHubWebhookEvent};
 
 // ─── Activity helper ──────────────────────────────────
 
-/// Look up the org_id for a project and record a GitHub-sourced activity entry.
-/// Uses "github:<sender_login>" as the synthetic user_id so it shows in the feed
-/// without polluting personal streaks (log_activity skips gamification for github: users).
+/// Synthetic actor for every GitHub-driven change, so timelines read
+/// "moved to done by GitHub (PR #42)". The `github:` prefix keeps it out of
+/// gamification (see `log_activity`).
+pub const GITHUB_BOT_ACTOR_ID: &str = "github:bot";
+pub const GITHUB_BOT_ACTOR_NAME: &str = "GitHub";
+
+/// Look up the org_id for a project and record a GitHub-sourced activity entry
+/// attributed to the GitHub bot. `metadata` should carry the repo and the
+/// PR/commit/issue reference; the human who triggered it goes in `sender`.
 async fn record_github_activity(
     pool: &PgPool,
     project_id: Uuid,
     issue_id: Option<Uuid>,
-    sender_login: &str,
     action: &str,
+    change: Option<&AppliedStatusChange>,
     metadata: serde_json::Value,
 ) {
     let org_id: Option<String> = sqlx::query_scalar(
@@ -27,22 +34,51 @@ async fn record_github_activity(
 
     let Some(org_id) = org_id else { return };
 
-    let user_id   = format!("github:{}", sender_login);
-    let user_name = format!("@{}", sender_login);
-
     crate::routes::activity::log_activity(
         pool,
         &org_id,
         Some(project_id),
         issue_id,
-        &user_id,
-        Some(&user_name),
+        GITHUB_BOT_ACTOR_ID,
+        Some(GITHUB_BOT_ACTOR_NAME),
         action,
-        None, None, None,
+        change.map(|_| "status"),
+        change.map(|c| c.old_status.as_str()),
+        change.map(|c| c.new_status.as_str()),
         Some(metadata),
     ).await;
 }
 
+// ─── Event types ──────────────────────────────────────
+
+/// The GitHub webhook event types we act on. The webhook handler drops every
+/// other type without storing it, so adding a variant here is all it takes
+/// to start receiving a new event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandledEvent {
+    Installation,
+    InstallationRepositories,
+    PullRequest,
+    PullRequestReview,
+    Push,
+    Issues,
+}
+
+impl HandledEvent {
+    /// Parse an `X-GitHub-Event` value; None for event types we ignore.
+    pub fn parse(event_type: &str) -> Option<Self> {
+        match event_type {
+            "installation" => Some(Self::Installation),
+            "installation_repositories" => Some(Self::InstallationRepositories),
+            "pull_request" => Some(Self::PullRequest),
+            "pull_request_review" => Some(Self::PullRequestReview),
+            "push" => Some(Self::Push),
+            "issues" => Some(Self::Issues),
+            _ => None,
+        }
+    }
+}
+
 /// Process a webhook event that was previously stored in github_webhook_events.
 ///
 /// Called from the webhook handler's spawned task AND from the job runner
@@ -67,14 +103,15 @@ pub async fn process_webhook_event(
     .fetch_one(pool)
     .await?;
 
-    let result = match event.event_type.as_str() {
-        "installation" => handle_installation_event(pool, &event).await,
-        "installation_repositories" => handle_installation_repos_event(pool, &event).await,
-        "pull_request" => handle_pull_request_event(pool, &event).await,
-        "pull_request_review" => handle_pr_review_event(pool, &event).await,
-        "push" => handle_push_event(pool, &event).await,
-        "issues" => handle_issues_event(pool, &event).await,
-        _ => {
+    let result = match HandledEvent::parse(&event.event_type) {
+        Some(HandledEvent::Installation) => handle_installation_event(pool, &event).await,
+        Some(HandledEvent::InstallationRepositories) => handle_installation_repos_event(pool, &event).await,
+        Some(HandledEvent::PullRequest) => handle_pull_request_event(pool, &event).await,
+        Some(HandledEvent::PullRequestReview) => handle_pr_review_event(pool, &event).await,
+        Some(HandledEvent::Push) => handle_push_event(pool, &event).await,
+        Some(HandledEvent::Issues) => handle_issues_event(pool, &event).await,
+        None => {
+            // Stored before the handler started filtering event types
             tracing::debug!("Ignoring unhandled event type: {}", event.event_type);
             Ok(())
         }
@@ -324,22 +361,64 @@ async fn handle_pull_request_event(
     .execute(pool)
     .await?;
 
+    // PR labels → tags on the linked issue
+    if matches!(action, "labeled" | "unlabeled") && mapping.sync_direction != "baaton_to_github" {
+        let removed = payload["label"]["name"].as_str().filter(|_| action == "unlabeled");
+        crate::github::label_mapper::apply_github_labels(
+            pool,
+            &mapping,
+            issue_id,
+            &payload_labels(pr),
+            removed,
+        )
+        .await?;
+    }
+
+    // Attach TLDRs that already cite this PR by URL
+    sqlx::query(
+        r#"UPDATE tldrs t SET github_pr_link_id = l.id
+           FROM github_pr_links l
+           WHERE l.github_repo_id = $1 AND l.pr_number = $2
+             AND t.issue_id = l.issue_id AND t.github_pr_link_id IS NULL
+             AND rtrim(t.pr_url, '/') = l.pr_url"#,
+    )
+    .bind(github_repo_id)
+    .bind(pr_number)
+    .execute(pool)
+    .await?;
+
     // Apply status mapping
-    let mapping_key = match pr_state {
-        "open" | "draft" => "pr_opened",
-        "merged" => "pr_merged",
-        "closed" => "pr_closed",
-        _ => return Ok(()),
+    let Some(mapping_key) = crate::github::status_mapper::pr_mapping_key(pr_state) else {
+        return Ok(());
     };
 
-    crate::github::status_mapper::apply_status_mapping(
+    let sender_login = event.sender_login.as_deref().unwrap_or("unknown");
+    let pr_ref = serde_json::json!({
+        "repo":      repo["full_name"].as_str().unwrap_or(""),
+        "pr_number": pr_number,
+        "pr_title":  pr_title,
+        "pr_url":    pr["html_url"].as_str().unwrap_or(""),
+        "branch":    head_branch,
+        "sender":    sender_login,
+    });
+
+    let status_change = crate::github::status_mapper::apply_status_mapping(
         pool,
         issue_id,
         &mapping.status_mapping,
         mapping_key,
-        &format!("GitHub PR #{}", pr_number),
     )
     .await?;
+    if let Some(change) = &status_change {
+        record_github_activity(
+            pool,
+            mapping.project_id,
+            Some(issue_id),
+            "status_changed",
+            Some(change),
+            pr_ref.clone(),
+        ).await;
+    }
 
     // Update merged metadata
     if action == "closed" && pr["merged"].as_bool() == Some(true) {
@@ -364,19 +443,13 @@ async fn handle_pull_request_event(
         _ => None,
     };
     if let Some(gh_action) = gh_action {
-        let sender_login = event.sender_login.as_deref().unwrap_or("unknown");
         record_github_activity(
             pool,
             mapping.project_id,
             Some(issue_id),
-            sender_login,
             gh_action,
-            serde_json::json!({
-                "repo":      pr["base"]["repo"]["full_name"].as_str().unwrap_or(""),
-                "pr_number": pr_number,
-                "pr_title":  pr_title,
-                "branch":    head_branch,
-            }),
+            None,
+            pr_ref,
         ).await;
     }
 
@@ -490,13 +563,14 @@ async fn handle_push_event(
             pool,
             mapping.project_id,
             Some(issue_id),
-            sender_login,
             "github_push",
+            None,
             serde_json::json!({
                 "repo":         repo["full_name"].as_str().unwrap_or(""),
                 "branch":       branch,
                 "sha":          sha,
                 "message":      message,
+                "sender":       sender_login,
             }),
         ).await;
     }
@@ -546,6 +620,36 @@ async fn handle_issues_event(
     .await?;
 
     match action {
+        "opened" if existing.is_none() && mapping.auto_create_issues => {
+            if let Some(issue_id) = create_issue_from_github(pool, &mapping, issue).await? {
+                record_github_activity(
+                    pool,
+                    mapping.project_id,
+                    Some(issue_id),
+                    "issue_created",
+                    None,
+                    serde_json::json!({
+                        "repo":         repo["full_name"].as_str().unwrap_or(""),
+                        "issue_number": github_issue_number,
+                        "issue_url":    issue["html_url"].as_str().unwrap_or(""),
+                        "sender":       event.sender_login.as_deref().unwrap_or("unknown"),
+                    }),
+                ).await;
+            }
+        }
+        "labeled" | "unlabeled" => {
+            if let Some((issue_id,)) = existing {
+                let removed = payload["label"]["name"].as_str().filter(|_| action == "unlabeled");
+                crate::github::label_mapper::apply_github_labels(
+                    pool,
+                    &mapping,
+                    issue_id,
+                    &payload_labels(issue),
+                    removed,
+                )
+                .await?;
+            }
+        }
         "closed" | "reopened" => {
             // Apply status mapping if we have a linked issue
             if let Some((issue_id,)) = existing {
@@ -555,14 +659,28 @@ async fn handle_issues_event(
                     _ => return Ok(()),
                 };
 
-                crate::github::status_mapper::apply_status_mapping(
+                let status_change = crate::github::status_mapper::apply_status_mapping(
                     pool,
                     issue_id,
                     &mapping.status_mapping,
                     mapping_key,
-                    &format!("GitHub issue #{}", github_issue_number),
                 )
                 .await?;
+                if let Some(change) = &status_change {
+                    record_github_activity(
+                        pool,
+                        mapping.project_id,
+                        Some(issue_id),
+                        "status_changed",
+                        Some(change),
+                        serde_json::json!({
+                            "repo":         repo["full_name"].as_str().unwrap_or(""),
+                            "issue_number": github_issue_number,
+                            "issue_url":    issue["html_url"].as_str().unwrap_or(""),
+                            "sender":       event.sender_login.as_deref().unwrap_or("unknown"),
+                        }),
+                    ).await;
+                }
             }
         }
         _ => {
@@ -576,3 +694,93 @@ async fn handle_issues_event(
 
     Ok(())
 }
+
+/// Label names of a GitHub issue or PR payload.
+fn payload_labels(item: &serde_json::Value) -> Vec<&str> {
+    item["labels"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|l| l["name"].as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Create a Baaton issue (and its link) for a GitHub issue opened in a repo
+/// whose mapping has `auto_create_issues`. Status comes from the
+/// `issue_opened` status mapping; type, priority and tags from the mapping's
+/// defaults and label map. Returns None when the project is archived.
+async fn create_issue_from_github(
+    pool: &PgPool,
+    mapping: &GitHubRepoMapping,
+    gh_issue: &serde_json::Value,
+) -> Result<Option<Uuid>, anyhow::Error> {
+    let Some((prefix, archived)) = sqlx::query_as::<_, (String, bool)>(
+        "SELECT prefix, archived_at IS NOT NULL FROM projects WHERE id = $1",
+    )
+    .bind(mapping.project_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+    if archived {
+        return Ok(None);
+    }
+
+    let fields = crate::github::label_mapper::resolve_imported_fields(mapping, &payload_labels(gh_issue));
+    let status = mapping.status_mapping["issue_opened"].as_str().unwrap_or("backlog");
+    let title: String = gh_issue["title"].as_str().unwrap_or("Untitled GitHub issue").chars().take(500).collect();
+    let description = gh_issue["body"].as_str().map(crate::routes::issues::sanitize_description);
+    let author = gh_issue["user"]["login"].as_str();
+
+    let mut tx = pool.begin().await?;
+    let display_id = crate::routes::issues::next_display_id(&mut *tx, mapping.project_id, &prefix)
+        .await
+        .map_err(|(_, body)| anyhow::anyhow!("next_display_id failed: {}", body.0))?;
+
+    let issue_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO issues (
+               project_id, display_id, title, description, type, status, priority,
+               tags, source, position, created_by_id, created_by_name,
+               sync_source, sync_lock_until
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'github',
+                   (SELECT COALESCE(MAX(position), 0) + 1000 FROM issues WHERE project_id = $1 AND status = $6),
+                   $9, $10, 'github', now() + interval '5 seconds')
+           RETURNING id"#,
+    )
+    .bind(mapping.project_id)
+    .bind(&display_id)
+    .bind(&title)
+    .bind(&description)
+    .bind(&fields.issue_type)
+    .bind(status)
+    .bind(&fields.priority)
+    .bind(&fields.tags)
+    .bind(GITHUB_BOT_ACTOR_ID)
+    .bind(author.map(|a| format!("{a} (GitHub)")).as_deref().unwrap_or(GITHUB_BOT_ACTOR_NAME))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"INSERT INTO github_issue_links
+           (issue_id, github_repo_id, github_issue_number, github_issue_id, last_synced_at)
+           VALUES ($1, $2, $3, $4, now())"#,
+    )
+    .bind(issue_id)
+    .bind(mapping.github_repo_id)
+    .bind(gh_issue["number"].as_i64().unwrap_or(0) as i32)
+    .bind(gh_issue["id"].as_i64().unwrap_or(0))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    crate::routes::sla::apply_sla_policy(pool, issue_id, mapping.project_id, fields.priority.as_deref()).await;
+
+    tracing::info!(
+        issue_id = %issue_id,
+        display_id = %display_id,
+        github_repo_id = mapping.github_repo_id,
+        "Created issue from GitHub"
+    );
+    Ok(Some(issue_id))
+}
@@ -2,19 +2,20 @@ use axum::{middleware as axum_mw, routing::get, Router};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tower_http::cors::{AllowOrigin, Any, CorsLayer};
-use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod filter;
 mod github;
 mod middleware;
+mod migrate;
 mod models;
 mod novu;
 mod routes;
+mod storage;
+mod util;
 
-use middleware::{fetch_jwks_keys, jwks_refresh_task, JwksKeys};
+use middleware::{clerk_issuers, fetch_jwks_keys_all, jwks_refresh_task, merge_jwks, JwksKeys};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -30,170 +31,84 @@ async fn main() -> anyhow::Result<()> {
 
     // Database
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let max_conns: u32 = std::env::var("DB_MAX_CONNECTIONS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(5);
+    let env_or = |key: &str, default: u64| -> u64 {
+        std::env::var(key)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(default)
+    };
+    let max_conns = env_or("DB_MAX_CONNECTIONS", 5) as u32;
+    let min_conns = env_or("DB_MIN_CONNECTIONS", 0) as u32;
+    let acquire_timeout_secs = env_or("DB_ACQUIRE_TIMEOUT_SECS", 5);
+    let idle_timeout_secs = env_or("DB_IDLE_TIMEOUT_SECS", 600);
+    // Server-side cap on any single statement (0 disables). Timeouts surface as
+    // 503 `query_timeout` on the heavy read endpoints.
+    let statement_timeout_ms = env_or("DB_STATEMENT_TIMEOUT_MS", 30_000);
     let connect_opts = database_url
         .parse::<sqlx::postgres::PgConnectOptions>()?
-        .statement_cache_capacity(0);
+        .statement_cache_capacity(0)
+        .options([("statement_timeout", statement_timeout_ms.to_string())]);
     let pool = sqlx::postgres::PgPoolOptions::new()
         .max_connections(max_conns)
-        .acquire_timeout(std::time::Duration::from_secs(5))
+        .min_connections(min_conns.min(max_conns))
+        .acquire_timeout(std::time::Duration::from_secs(acquire_timeout_secs))
+        .idle_timeout(
+            (idle_timeout_secs > 0).then(|| std::time::Duration::from_secs(idle_timeout_secs)),
+        )
         .connect_with(connect_opts)
         .await?;
 
-    tracing::info!("Connected to database");
-
-    // ── Migration tracking ─────────────────────────────
-    sqlx::raw_sql("CREATE TABLE IF NOT EXISTS _migrations (version INT PRIMARY KEY, applied_at TIMESTAMPTZ DEFAULT now())")
-        .execute(&pool)
-        .await?;
-
-    let migrations: &[(i32, &str)] = &[
-        (1, include_str!("../migrations/001_init.sql")),
-        (2, include_str!("../migrations/002_sprints.sql")),
-        (3, include_str!("../migrations/003_project_tags.sql")),
-        (4, include_str!("../migrations/004_issue_category.sql")),
-        (5, include_str!("../migrations/005_org_upsert.sql")),
-        (6, include_str!("../migrations/006_github_integration.sql")),
-        (
-            7,
-            include_str!("../migrations/007_issue_creator_duedate.sql"),
-        ),
-        (8, include_str!("../migrations/008_activity_log.sql")),
-        (
-            9,
-            include_str!("../migrations/009_openclaw_integration.sql"),
-        ),
-        (
-            10,
-            include_str!("../migrations/010_milestone_enhancements.sql"),
-        ),
-        (11, include_str!("../migrations/011_saved_views.sql")),
-        (
-            12,
-            include_str!("../migrations/012_templates_estimates.sql"),
-        ),
-        (
-            13,
-            include_str!("../migrations/013_project_auto_assign.sql"),
-        ),
-        (15, include_str!("../migrations/015_public_submit.sql")),
-        (16, include_str!("../migrations/016_issue_timestamps.sql")),
-        (
-            17,
-            include_str!("../migrations/017_api_key_project_scope.sql"),
-        ),
-        (18, include_str!("../migrations/018_webhooks.sql")),
-        (
-            19,
-            include_str!("../migrations/019_project_github_metadata.sql"),
-        ),
-        (20, include_str!("../migrations/020_label_colors.sql")),
-        (21, include_str!("../migrations/021_search_vector.sql")),
-        (22, include_str!("../migrations/022_snooze.sql")),
-        (23, include_str!("../migrations/023_activity_log.sql")),
-        (24, include_str!("../migrations/024_auto_archive.sql")),
-        (25, include_str!("../migrations/025_issue_relations.sql")),
-        (26, include_str!("../migrations/026_recurring_issues.sql")),
-        (27, include_str!("../migrations/027_cycles.sql")),
-        (28, include_str!("../migrations/028_notifications.sql")),
-        (29, include_str!("../migrations/029_custom_views.sql")),
-        (30, include_str!("../migrations/030_sla.sql")),
-        (31, include_str!("../migrations/031_templates.sql")),
-        (32, include_str!("../migrations/032_automations.sql")),
-        (33, include_str!("../migrations/033_pricing.sql")),
-        (34, include_str!("../migrations/034_initiatives.sql")),
-        (35, include_str!("../migrations/035_attachments.sql")),
-        (36, include_str!("../migrations/036_slack.sql")),
-        (37, include_str!("../migrations/037_ai_usage.sql")),
-        (371, include_str!("../migrations/037_agent_config.sql")), // was duplicate v37, re-indexed as 371
-        (38, include_str!("../migrations/038_superadmin.sql")),
-        (39, include_str!("../migrations/039_admin_audit_log.sql")),
-        (40, include_str!("../migrations/040_user_plans.sql")),
-        (41, include_str!("../migrations/041_gamification.sql")),
-        (42, include_str!("../migrations/042_gamification_v2.sql")),
-        (
-            43,
-            include_str!("../migrations/043_backfill_gamification.sql"),
-        ),
-        (
-            44,
-            include_str!("../migrations/044_custom_fields_estimates.sql"),
-        ),
-        (45, include_str!("../migrations/045_plans_per_user.sql")),
-        (46, include_str!("../migrations/046_approval_workflow.sql")),
-        (47, include_str!("../migrations/047_advanced_api.sql")),
-        (48, include_str!("../migrations/048_agent_sessions.sql")),
-        (49, include_str!("../migrations/049_project_context.sql")),
-        (
-            50,
-            include_str!("../migrations/050_api_keys_created_by.sql"),
-        ),
-        (51, include_str!("../migrations/051_api_key_org_scopes.sql")),
-        (52, include_str!("../migrations/052_api_key_org_scope_mode.sql")),
-        (53, include_str!("../migrations/053_source_ai.sql")),
-    ];
-
-    for &(version, sql) in migrations {
-        let applied: bool =
-            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM _migrations WHERE version = $1)")
-                .bind(version)
-                .fetch_one(&pool)
-                .await
-                .unwrap_or(false);
-        if !applied {
-            match sqlx::raw_sql(sql).execute(&pool).await {
-                Ok(_) => {
-                    let _ = sqlx::query("INSERT INTO _migrations (version) VALUES ($1)")
-                        .bind(version)
-                        .execute(&pool)
-                        .await;
-                    tracing::info!("Applied migration {}", version);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Migration {} failed (will retry on next restart): {}",
-                        version,
-                        e
-                    );
-                }
-            }
-        }
+    tracing::info!(
+        max_connections = max_conns,
+        min_connections = min_conns.min(max_conns),
+        acquire_timeout_secs,
+        idle_timeout_secs,
+        statement_timeout_ms,
+        "Connected to database"
+    );
+
+    // ── Migrations ─────────────────────────────────────
+    // MIGRATE_DOWN=<version> rolls back to that version and exits instead of serving
+    if let Some(target) = migrate::down_target() {
+        migrate::rollback(&pool, target).await?;
+        return Ok(());
     }
-    tracing::info!("Migrations applied");
+    migrate::run(&pool).await?;
 
     // ── JWKS setup ─────────────────────────────────────
-    let clerk_issuer =
-        std::env::var("CLERK_ISSUER").unwrap_or_else(|_| "https://clerk.baaton.dev".to_string());
-
-    let jwks_keys = match fetch_jwks_keys(&clerk_issuer).await {
-        Ok(keys) => {
-            tracing::info!("Fetched {} JWKS keys from Clerk", keys.len());
-            keys
+    // CLERK_ISSUER may list several instances (e.g. staging + prod during migration)
+    let clerk_issuers = clerk_issuers();
+
+    let mut jwks_keys = std::collections::HashMap::new();
+    match fetch_jwks_keys_all(&clerk_issuers).await {
+        Ok(fetched) => {
+            let count = merge_jwks(&mut jwks_keys, fetched);
+            tracing::info!("Fetched {} JWKS keys from Clerk", count);
         }
         Err(e) => {
             tracing::warn!(
                 "Failed to fetch initial JWKS (will retry on first request): {}",
                 e
             );
-            std::collections::HashMap::new()
         }
-    };
+    }
     let jwks_state: JwksKeys = Arc::new(RwLock::new(jwks_keys));
 
     // Background JWKS refresh
     let jwks_bg = jwks_state.clone();
-    let issuer_bg = clerk_issuer.clone();
+    let issuers_bg = clerk_issuers.clone();
     tokio::spawn(async move {
-        jwks_refresh_task(jwks_bg, issuer_bg).await;
+        jwks_refresh_task(jwks_bg, issuers_bg).await;
     });
 
+    // Shutdown signal fan-out (flipped to true on SIGTERM / Ctrl+C)
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
     // Start GitHub sync job runner
     let job_pool = pool.clone();
-    tokio::spawn(async move {
-        github::jobs::start_job_runner(job_pool).await;
+    let job_shutdown_rx = shutdown_rx.clone();
+    let job_runner = tokio::spawn(async move {
+        github::jobs::start_job_runner(job_pool, job_shutdown_rx).await;
     });
 
     // Start webhook retry worker
@@ -202,49 +117,77 @@ async fn main() -> anyhow::Result<()> {
         routes::webhooks::retry_worker(webhook_pool).await;
     });
 
+    // Evict expired issue presence entries
+    tokio::spawn(routes::presence::cleanup_task());
+
     // ── SSE broadcast channel ───────────────────────────
     // Buffer 256 events — slow clients get a Lagged notification
     let (sse_tx, _) = tokio::sync::broadcast::channel::<routes::sse::SseEvent>(256);
 
     // Novu notifications (None if NOVU_SECRET_KEY unset)
-    let novu_client = novu::NovuClient::from_env();
-
-    // CORS — restrict origins in production, permissive in dev
-    let cors = {
-        let allowed_origins = std::env::var("CORS_ORIGINS").unwrap_or_default();
-        if allowed_origins.is_empty() || allowed_origins == "*" {
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any)
-        } else {
-            let origins: Vec<axum::http::HeaderValue> = allowed_origins
-                .split(',')
-                .filter_map(|s| s.trim().parse().ok())
-                .collect();
-            CorsLayer::new()
-                .allow_origin(AllowOrigin::list(origins))
-                .allow_methods(Any)
-                .allow_headers(Any)
-                .allow_credentials(true)
-        }
-    };
+    let novu_client =
+        novu::NovuClient::from_env().map(|client| client.with_failure_queue(pool.clone()));
+    if let Some(ref client) = novu_client {
+        tokio::spawn(novu::retry_worker(client.clone(), pool.clone()));
+        tokio::spawn(routes::notifications::digest_worker(
+            client.clone(),
+            pool.clone(),
+        ));
+    }
+    // Resurface snoozed issues once their time is up
+    tokio::spawn(routes::issues::snooze_worker(pool.clone(), sse_tx.clone()));
+    // Drop API key usage rows past retention
+    tokio::spawn(routes::api_keys::usage_retention_worker(pool.clone()));
+    // Flag SLA breaches and warn before they happen
+    tokio::spawn(routes::sla::sla_worker(
+        pool.clone(),
+        novu_client.clone(),
+        sse_tx.clone(),
+    ));
+    let storage_client = storage::StorageClient::from_env();
+
+    // CORS — origin allowlist; CORS_ALLOW_ALL=true for local dev
+    let cors = middleware::security::cors_layer();
 
     // Router
     let app = Router::new()
+        // Liveness: the process is up. Readiness also checks Postgres.
         .route("/health", get(|| async { "ok" }))
+        .route(
+            "/health/ready",
+            get(routes::health::ready).layer(axum::Extension(jwks_state.clone())),
+        )
+        .route("/metrics", get(routes::health::metrics))
         .nest(
             "/api/v1",
             routes::api_router(pool.clone(), jwks_state.clone()),
         )
         .layer(axum::Extension(novu_client))
+        .layer(axum::Extension(storage_client))
         .layer(axum::Extension(sse_tx))
+        .layer(axum::Extension(routes::sse::ShutdownSignal(shutdown_rx)))
         .layer(axum::Extension(pool.clone()))
         .layer(axum_mw::from_fn(middleware::security::security_headers))
         .layer(cors)
-        // Default body limit: 2MB (attachment routes override to 20MB)
-        .layer(RequestBodyLimitLayer::new(2 * 1024 * 1024))
-        .layer(TraceLayer::new_for_http());
+        // Every event logged while handling a request carries its request_id
+        .layer(
+            TraceLayer::new_for_http().make_span_with(|req: &axum::http::Request<_>| {
+                let request_id = req
+                    .extensions()
+                    .get::<middleware::request_id::RequestId>()
+                    .map(|r| r.0.as_str())
+                    .unwrap_or("-");
+                tracing::info_span!(
+                    "request",
+                    method = %req.method(),
+                    // Path only: the SSE route may carry a token in the query
+                    path = %req.uri().path(),
+                    request_id = %request_id,
+                )
+            }),
+        )
+        // Outermost so the ID exists before TraceLayer builds its span
+        .layer(axum_mw::from_fn(middleware::request_id::request_id));
 
     // Serve
     let port: u16 = std::env::var("PORT")
@@ -256,9 +199,18 @@ async fn main() -> anyhow::Result<()> {
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            let _ = shutdown_tx.send(true);
+        })
         .await?;
 
+    // Let the job runner finish its current job and release its claims
+    match tokio::time::timeout(std::time::Duration::from_secs(25), job_runner).await {
+        Ok(_) => tracing::info!("GitHub job runner drained"),
+        Err(_) => tracing::warn!("GitHub job runner did not drain within 25s"),
+    }
+
     tracing::info!("Baaton API shut down gracefully");
     Ok(())
 }
@@ -0,0 +1,221 @@
+//! API-key project scoping — runs after `auth_middleware` and keeps keys with
+//! `project_ids` set on those projects. Paths that address a project, or a
+//! row belonging to one, are checked against the key's scope; of the
+//! org-wide routes, only those whose handlers filter by project are open.
+//! Clerk JWT users and keys without project scope are never checked here.
+
+use axum::{
+    extract::Request,
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+
+/// What a request path says about the project it touches.
+#[derive(Debug, PartialEq)]
+pub enum ProjectTarget {
+    /// `/projects/{id}/…`
+    Project(Uuid),
+    /// `/projects/by-slug/{slug}/…`
+    Slug(String),
+    /// `/{resource}/{id}/…`: the query returns the row's `project_id`.
+    Row(&'static str, Uuid),
+    /// Org-wide route whose handler filters by the key's projects.
+    Filtered,
+    /// Org-wide route a project-scoped key may not use.
+    OrgWide,
+}
+
+/// Query returning the `project_id` of a `/{segment}/{id}` row.
+fn owning_project_query(segment: &str) -> Option<&'static str> {
+    Some(match segment {
+        "issues" => "SELECT project_id FROM issues WHERE id = $1",
+        "cycles" => "SELECT project_id FROM cycles WHERE id = $1",
+        "milestones" => "SELECT project_id FROM milestones WHERE id = $1",
+        "sprints" => "SELECT project_id FROM sprints WHERE id = $1",
+        "templates" => "SELECT project_id FROM issue_templates WHERE id = $1",
+        "sla-policies" | "sla-rules" => "SELECT project_id FROM sla_policies WHERE id = $1",
+        "automations" => "SELECT project_id FROM automation_rules WHERE id = $1",
+        "recurring" => "SELECT project_id FROM recurrence_rules WHERE id = $1",
+        "custom-fields" => "SELECT project_id FROM custom_field_definitions WHERE id = $1",
+        "tags" => "SELECT project_id FROM project_tags WHERE id = $1",
+        "categories" => "SELECT project_id FROM project_categories WHERE id = $1",
+        "tldr" => {
+            "SELECT i.project_id FROM tldrs t JOIN issues i ON i.id = t.issue_id WHERE t.id = $1"
+        }
+        _ => return None,
+    })
+}
+
+/// Classify a request for project scoping.
+pub fn project_target(method: &Method, path: &str) -> ProjectTarget {
+    let path = path.strip_prefix("/api/v1").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match segments.as_slice() {
+        ["projects", "by-slug", slug, ..] => return ProjectTarget::Slug(slug.to_string()),
+        ["projects", id, ..] => {
+            if let Ok(id) = Uuid::parse_str(id) {
+                return ProjectTarget::Project(id);
+            }
+        }
+        [segment, id, ..] => {
+            if let (Some(query), Ok(id)) = (owning_project_query(segment), Uuid::parse_str(id)) {
+                return ProjectTarget::Row(query, id);
+            }
+        }
+        _ => {}
+    }
+
+    // Issue creation checks the body's project_id itself
+    match (method, segments.as_slice()) {
+        (&Method::GET, ["projects"] | ["issues"] | ["search"] | ["me"]) => ProjectTarget::Filtered,
+        (&Method::POST, ["issues"]) => ProjectTarget::Filtered,
+        _ => ProjectTarget::OrgWide,
+    }
+}
+
+/// Whether a project-scoped key may make this request. Rows that don't
+/// exist pass through so the handler can answer 404.
+async fn is_in_scope(
+    pool: &PgPool,
+    auth: &AuthUser,
+    target: ProjectTarget,
+) -> Result<bool, sqlx::Error> {
+    let scope = &auth.scoped_project_ids;
+    Ok(match target {
+        ProjectTarget::Filtered => true,
+        ProjectTarget::OrgWide => false,
+        ProjectTarget::Project(id) => scope.contains(&id),
+        ProjectTarget::Slug(slug) => {
+            let ids: Vec<Uuid> =
+                sqlx::query_scalar("SELECT id FROM projects WHERE slug = $1 AND org_id = ANY($2)")
+                    .bind(&slug)
+                    .bind(&auth.scoped_org_ids)
+                    .fetch_all(pool)
+                    .await?;
+            ids.is_empty() || ids.iter().any(|id| scope.contains(id))
+        }
+        ProjectTarget::Row(query, id) => {
+            let project_id: Option<Option<Uuid>> = sqlx::query_scalar(query)
+                .bind(id)
+                .fetch_optional(pool)
+                .await?;
+            match project_id {
+                None => true,
+                // Org-level rows (e.g. templates without a project) are out of scope
+                Some(project_id) => project_id.is_some_and(|p| scope.contains(&p)),
+            }
+        }
+    })
+}
+
+/// Layer enforcing API-key project scope. Must run after `auth_middleware`.
+pub async fn enforce_api_key_project_scope(req: Request, next: Next) -> Response {
+    let Some(auth) = req
+        .extensions()
+        .get::<AuthUser>()
+        .filter(|auth| !auth.scoped_project_ids.is_empty())
+        .cloned()
+    else {
+        return next.run(req).await;
+    };
+    let Some(pool) = req.extensions().get::<PgPool>().cloned() else {
+        tracing::error!("PgPool extension missing for project scope check");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "Auth error"})),
+        )
+            .into_response();
+    };
+
+    let target = project_target(req.method(), req.uri().path());
+    let org_wide = target == ProjectTarget::OrgWide;
+    match is_in_scope(&pool, &auth, target).await {
+        Ok(true) => next.run(req).await,
+        Ok(false) => {
+            tracing::debug!(
+                method = %req.method(),
+                path = %req.uri().path(),
+                user_id = %auth.user_id,
+                "API key project scope check failed"
+            );
+            let error = if org_wide {
+                "API key is limited to specific projects and cannot use org-wide endpoints"
+            } else {
+                "API key does not have access to this project. Check project_ids scope on the key."
+            };
+            (
+                StatusCode::FORBIDDEN,
+                Json(json!({"error": error, "code": "project_out_of_scope"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "API key project scope lookup failed");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": "Auth error"})),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_target() {
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            project_target(&Method::GET, &format!("/api/v1/projects/{id}/issues")),
+            ProjectTarget::Project(id)
+        );
+        assert_eq!(
+            project_target(&Method::GET, "/projects/by-slug/web/board"),
+            ProjectTarget::Slug("web".to_string())
+        );
+        assert!(matches!(
+            project_target(&Method::PATCH, &format!("/issues/{id}")),
+            ProjectTarget::Row(_, row) if row == id
+        ));
+        assert!(matches!(
+            project_target(&Method::DELETE, &format!("/sprints/{id}")),
+            ProjectTarget::Row(_, row) if row == id
+        ));
+
+        assert_eq!(
+            project_target(&Method::GET, "/api/v1/issues"),
+            ProjectTarget::Filtered
+        );
+        assert_eq!(
+            project_target(&Method::POST, "/issues"),
+            ProjectTarget::Filtered
+        );
+        assert_eq!(
+            project_target(&Method::GET, "/projects"),
+            ProjectTarget::Filtered
+        );
+        assert_eq!(
+            project_target(&Method::POST, "/projects"),
+            ProjectTarget::OrgWide
+        );
+        assert_eq!(
+            project_target(&Method::PATCH, "/issues/batch"),
+            ProjectTarget::OrgWide
+        );
+        assert_eq!(
+            project_target(&Method::GET, "/views"),
+            ProjectTarget::OrgWide
+        );
+    }
+}
@@ -2,7 +2,10 @@
 // Rate-limit infra is partially used today (constants for plan-based quotas)
 // while the per-request enforcement helpers wait for their guard wiring.
 
+use axum::extract::Request;
 use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use chrono::{Datelike, Timelike};
 use serde_json::json;
 use sqlx::PgPool;
@@ -224,3 +227,116 @@ pub fn hourly_rate_limit_headers(result: &HourlyRateLimitResult) -> HeaderMap {
     }
     headers
 }
+
+// ─── Per-org hourly limits (expensive endpoints) ──────
+
+/// Org-wide hourly budgets. Keyed on the org rather than the user/IP so one
+/// org cannot exhaust shared upstream quotas (Gemini) for everyone else.
+pub const HOURLY_LIMIT_ORG_AI_CHAT: i64 = 600;
+pub const HOURLY_LIMIT_ORG_ISSUE_CREATE: i64 = 2000;
+
+/// Proxies in front of us that append to `X-Forwarded-For` (Railway's edge
+/// by default). Override with `TRUSTED_PROXY_HOPS`; 0 ignores the header.
+const DEFAULT_TRUSTED_PROXY_HOPS: usize = 1;
+
+fn trusted_proxy_hops() -> usize {
+    std::env::var("TRUSTED_PROXY_HOPS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_TRUSTED_PROXY_HOPS)
+}
+
+/// Client IP as recorded by the outermost trusted proxy. Entries to its left
+/// in `X-Forwarded-For` come from the client and can be forged, so only the
+/// `TRUSTED_PROXY_HOPS`-th entry from the right is used.
+pub(crate) fn client_ip(headers: &HeaderMap) -> Option<String> {
+    forwarded_client_ip(headers, trusted_proxy_hops())
+}
+
+fn forwarded_client_ip(headers: &HeaderMap, hops: usize) -> Option<String> {
+    let entries: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let index = entries.len().checked_sub(hops).filter(|_| hops > 0)?;
+    Some(entries[index].to_string())
+}
+
+/// Rate key + limit for a bucket: `org:<id>:<bucket>` for authenticated
+/// requests, `ip:<addr>:<bucket>` (at the unauthenticated limit) otherwise.
+fn org_rate_key(req: &Request, bucket: &str, org_limit: i64) -> (String, i64) {
+    let org_id = req
+        .extensions()
+        .get::<crate::middleware::AuthUser>()
+        .and_then(|auth| auth.org_id.clone());
+    match org_id {
+        Some(org_id) => (format!("org:{}:{}", org_id, bucket), org_limit),
+        None => {
+            let ip = client_ip(req.headers()).unwrap_or_else(|| "unknown".to_string());
+            (format!("ip:{}:{}", ip, bucket), HOURLY_LIMIT_UNAUTHENTICATED)
+        }
+    }
+}
+
+async fn enforce_org_hourly(req: Request, next: Next, bucket: &str, org_limit: i64) -> Response {
+    let Some(pool) = req.extensions().get::<PgPool>().cloned() else {
+        return next.run(req).await;
+    };
+
+    let (rate_key, limit) = org_rate_key(&req, bucket, org_limit);
+    // Fail open: a counter outage must not take the endpoint down
+    match check_hourly(&pool, &rate_key, limit).await {
+        Ok(rl) if !rl.allowed => {
+            tracing::warn!(rate_key = %rate_key, limit = rl.limit, "org_rate_limit.exceeded");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                hourly_rate_limit_headers(&rl),
+                axum::Json(json!({
+                    "error": "Hourly limit reached for your organization. Try again later.",
+                    "code": "org_rate_limited",
+                    "limit": rl.limit,
+                    "reset": rl.reset_epoch_ms,
+                })),
+            )
+                .into_response()
+        }
+        _ => next.run(req).await,
+    }
+}
+
+/// Route layer for AI chat endpoints — per-org hourly budget.
+pub async fn org_limit_ai_chat(req: Request, next: Next) -> Response {
+    enforce_org_hourly(req, next, "ai_chat", HOURLY_LIMIT_ORG_AI_CHAT).await
+}
+
+/// Route layer for issue creation (authenticated and public submit).
+pub async fn org_limit_issue_create(req: Request, next: Next) -> Response {
+    enforce_org_hourly(req, next, "issue_create", HOURLY_LIMIT_ORG_ISSUE_CREATE).await
+}
+
+/// Route layer for the public issue-status lookup. Always unauthenticated, so
+/// this is an IP bucket separate from submissions.
+pub async fn org_limit_public_lookup(req: Request, next: Next) -> Response {
+    enforce_org_hourly(req, next, "public_lookup", HOURLY_LIMIT_UNAUTHENTICATED).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forwarded_client_ip_ignores_client_entries() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("6.6.6.6, 1.2.3.4, 10.0.0.1"));
+
+        assert_eq!(forwarded_client_ip(&headers, 1).as_deref(), Some("10.0.0.1"));
+        assert_eq!(forwarded_client_ip(&headers, 2).as_deref(), Some("1.2.3.4"));
+        assert_eq!(forwarded_client_ip(&headers, 0), None);
+        assert_eq!(forwarded_client_ip(&headers, 4), None);
+        assert_eq!(forwarded_client_ip(&HeaderMap::new(), 1), None);
+    }
+}
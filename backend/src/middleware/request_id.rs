@@ -0,0 +1,38 @@
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Correlation ID for the current request, stored in request extensions.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Accept a caller-supplied ID only if it's short, printable ASCII.
+fn sanitize(id: &str) -> Option<String> {
+    let id = id.trim();
+    let valid = !id.is_empty()
+        && id.len() <= 128
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'));
+    valid.then(|| id.to_string())
+}
+
+/// Reads `X-Request-Id` (or generates a UUID), stores it in extensions for
+/// the trace span, and echoes it back on the response.
+/// Must be layered outside `TraceLayer` so the span can pick it up.
+pub async fn request_id(mut req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(sanitize)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(id.clone()));
+
+    let mut response = next.run(req).await;
+    if let Ok(v) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, v);
+    }
+    response
+}
@@ -0,0 +1,185 @@
+//! Org-role guard for destructive and org-wide actions.
+//! Each action defaults to `org:admin`; an org can loosen (or re-tighten) it
+//! through `org_role_requirements`.
+//!
+//! Usage:
+//! ```rust
+//! require_action_role(&pool, &auth, RoleAction::ProjectDelete).await?;
+//! ```
+
+use axum::{http::StatusCode, Json};
+use serde_json::json;
+use sqlx::PgPool;
+
+use crate::middleware::scopes::has_scope;
+use crate::middleware::AuthUser;
+
+pub const ROLE_ADMIN: &str = "org:admin";
+pub const ROLE_MEMBER: &str = "org:member";
+
+/// API-key permission standing in for `org:admin`.
+const ADMIN_FULL: &str = "admin:full";
+
+/// Actions behind a role check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleAction {
+    ProjectUpdate,
+    ProjectDelete,
+    ApiKeysManage,
+    GithubDisconnect,
+}
+
+impl RoleAction {
+    pub const ALL: &'static [RoleAction] = &[
+        Self::ProjectUpdate,
+        Self::ProjectDelete,
+        Self::ApiKeysManage,
+        Self::GithubDisconnect,
+    ];
+
+    /// Stable key stored in `org_role_requirements.action`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::ProjectUpdate => "projects.update",
+            Self::ProjectDelete => "projects.delete",
+            Self::ApiKeysManage => "api_keys.manage",
+            Self::GithubDisconnect => "github.disconnect",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|a| a.key() == key)
+    }
+
+    /// Permission an API key needs for the action; keys carry no org role.
+    pub fn api_key_permission(self) -> &'static str {
+        match self {
+            Self::ProjectUpdate => "projects:write",
+            Self::ProjectDelete | Self::ApiKeysManage | Self::GithubDisconnect => ADMIN_FULL,
+        }
+    }
+}
+
+/// Whether a Clerk org role (`org:admin`, `org:member`, custom `org:*_admin`…)
+/// meets `required`. Any org role meets `org:member`.
+pub fn role_satisfies(org_role: Option<&str>, required: &str) -> bool {
+    match required {
+        ROLE_MEMBER => org_role.is_some(),
+        _ => org_role.map(|r| r.contains("admin")).unwrap_or(false),
+    }
+}
+
+/// Reject an API key that lacks `permission`. Clerk users pass.
+fn require_key_permission(
+    auth: &AuthUser,
+    permission: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    match auth.api_key_permissions {
+        Some(ref permissions) if !has_scope(permissions, permission) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": format!("API key is missing the '{}' permission", permission),
+                "code": "insufficient_scope",
+                "required": permission,
+            })),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Reject the caller unless their org role meets `required`. API keys carry no
+/// org role and need `admin:full` instead.
+pub fn require_role(
+    auth: &AuthUser,
+    required: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if auth.api_key_permissions.is_some() {
+        return require_key_permission(auth, ADMIN_FULL);
+    }
+    if role_satisfies(auth.org_role.as_deref(), required) {
+        return Ok(());
+    }
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": format!("This action requires the {} role", required),
+            "code": "forbidden",
+            "required_role": required,
+        })),
+    ))
+}
+
+/// The role the caller's org requires for `action` (default `org:admin`).
+pub async fn required_role(pool: &PgPool, org_id: &str, action: RoleAction) -> String {
+    sqlx::query_scalar("SELECT role FROM org_role_requirements WHERE org_id = $1 AND action = $2")
+        .bind(org_id)
+        .bind(action.key())
+        .fetch_optional(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "org_role_requirements lookup failed");
+            None
+        })
+        .unwrap_or_else(|| ROLE_ADMIN.to_string())
+}
+
+/// `require_role` with the org's configured requirement for `action`. API
+/// keys need the action's `api_key_permission` instead.
+pub async fn require_action_role(
+    pool: &PgPool,
+    auth: &AuthUser,
+    action: RoleAction,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if auth.api_key_permissions.is_some() {
+        return require_key_permission(auth, action.api_key_permission());
+    }
+    let Some(org_id) = auth.org_id.as_deref() else {
+        return require_role(auth, ROLE_ADMIN);
+    };
+    let required = required_role(pool, org_id, action).await;
+    require_role(auth, &required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_satisfies() {
+        assert!(role_satisfies(Some("org:admin"), ROLE_ADMIN));
+        assert!(!role_satisfies(Some("org:member"), ROLE_ADMIN));
+        assert!(!role_satisfies(None, ROLE_ADMIN));
+        assert!(role_satisfies(Some("org:member"), ROLE_MEMBER));
+        assert!(!role_satisfies(None, ROLE_MEMBER));
+        assert_eq!(
+            RoleAction::from_key("projects.delete"),
+            Some(RoleAction::ProjectDelete)
+        );
+    }
+
+    #[test]
+    fn test_api_keys_need_permission() {
+        let key = |permissions: &[&str]| AuthUser {
+            user_id: "apikey:test".to_string(),
+            org_id: Some("org_1".to_string()),
+            org_slug: None,
+            org_role: None,
+            email: None,
+            display_name: None,
+            scoped_org_ids: vec!["org_1".to_string()],
+            scoped_project_ids: vec![],
+            api_key_permissions: Some(permissions.iter().map(|p| p.to_string()).collect()),
+        };
+
+        assert!(require_role(&key(&["projects:write"]), ROLE_ADMIN).is_err());
+        assert!(require_role(&key(&["admin:full"]), ROLE_ADMIN).is_ok());
+        assert_eq!(RoleAction::ProjectDelete.api_key_permission(), "admin:full");
+        assert_eq!(
+            RoleAction::ProjectUpdate.api_key_permission(),
+            "projects:write"
+        );
+        let err = require_key_permission(&key(&["projects:write"]), "admin:full").unwrap_err();
+        assert_eq!(err.0, StatusCode::FORBIDDEN);
+        assert_eq!(err.1 .0["code"], "insufficient_scope");
+    }
+}
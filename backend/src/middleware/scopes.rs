@@ -0,0 +1,269 @@
+//! API-key permission scopes — runs after `auth_middleware` and rejects requests
+//! whose API key lacks the permission for the resource/method being hit.
+//! Clerk JWT users carry no `api_key_permissions` and are never checked here.
+
+use axum::{
+    extract::Request,
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use crate::middleware::AuthUser;
+
+/// Grants every permission.
+const ADMIN_FULL: &str = "admin:full";
+
+/// Path segment → permission resource. When several segments match
+/// (`/projects/{id}/issues`), the most specific (last) one wins.
+fn resource_for_segment(segment: &str) -> Option<&'static str> {
+    Some(match segment {
+        "issues" | "search" | "children" | "relations" | "dependencies" | "attachments"
+        | "custom-values" | "activity" | "recurring" | "upload" => "issues",
+        "projects" | "public-submit" | "auto-assign" | "refresh-github" | "export" | "import"
+        | "custom-fields" | "sla-policies" | "sla-rules" | "initiatives" => "projects",
+        "comments" | "tldr" | "approval-request" | "approval-response" => "comments",
+        "tags" | "categories" => "labels",
+        "milestones" => "milestones",
+        "sprints" | "cycles" => "sprints",
+        "automations" => "automations",
+        "webhooks" => "webhooks",
+        "members" | "invites" | "invite" => "members",
+        "context" => "context",
+        "templates" | "project-templates" => "templates",
+        "ai" | "agent-sessions" | "agent-config" => "ai",
+        "triage" => "triage",
+        "billing" => "billing",
+        "views" | "favorites" => "views",
+        "org" | "api-keys" | "github" | "integrations" | "audit" | "admin" => "org",
+        _ => return None,
+    })
+}
+
+/// Resources with a dedicated `:delete` permission; others fall back to `:write`.
+const DELETE_SCOPED: &[&str] = &["issues", "projects", "comments"];
+
+fn is_read_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// The permission required for a request, or `None` when the path isn't
+/// mapped to a resource (reads are allowed then, writes need `admin:full`).
+pub fn required_scope(method: &Method, path: &str) -> Option<String> {
+    let path = path.strip_prefix("/api/v1").unwrap_or(path);
+    let resource = path
+        .split('/')
+        .filter_map(resource_for_segment)
+        .next_back()?;
+
+    let scope = match resource {
+        "ai" => "ai:chat".to_string(),
+        "triage" => "ai:triage".to_string(),
+        "members" if is_read_method(method) => "members:read".to_string(),
+        "members" => "members:invite".to_string(),
+        "billing" if is_read_method(method) => "billing:read".to_string(),
+        "billing" => ADMIN_FULL.to_string(),
+        // Org settings, keys and integrations have no narrower permission
+        "org" => ADMIN_FULL.to_string(),
+        _ if is_read_method(method) => format!("{}:read", resource),
+        // Purge is the real delete behind DELETE-as-archive
+        _ if (*method == Method::DELETE || path.ends_with("/purge"))
+            && DELETE_SCOPED.contains(&resource) =>
+        {
+            format!("{}:delete", resource)
+        }
+        _ => format!("{}:write", resource),
+    };
+    Some(scope)
+}
+
+/// Whether `permissions` satisfy `required`. `:write` implies `:read`.
+pub fn has_scope(permissions: &[String], required: &str) -> bool {
+    if permissions.iter().any(|p| p == ADMIN_FULL || p == required) {
+        return true;
+    }
+    match required.strip_suffix(":read") {
+        Some(resource) => {
+            let write = format!("{}:write", resource);
+            permissions.contains(&write)
+        }
+        None => false,
+    }
+}
+
+/// Check an API key's permissions against a request. Returns the missing
+/// scope on failure.
+pub fn check_request(permissions: &[String], method: &Method, path: &str) -> Result<(), String> {
+    match required_scope(method, path) {
+        Some(required) if !has_scope(permissions, &required) => Err(required),
+        Some(_) => Ok(()),
+        // Unmapped route: reads are open, writes need a full-access key
+        None if is_read_method(method) => Ok(()),
+        None if has_scope(permissions, ADMIN_FULL) => Ok(()),
+        None => Err(ADMIN_FULL.to_string()),
+    }
+}
+
+/// Layer enforcing API-key scopes. Must run after `auth_middleware`.
+pub async fn enforce_api_key_scopes(req: Request, next: Next) -> Response {
+    let permissions = req
+        .extensions()
+        .get::<AuthUser>()
+        .and_then(|auth| auth.api_key_permissions.clone());
+
+    if let Some(permissions) = permissions {
+        if let Err(required) = check_request(&permissions, req.method(), req.uri().path()) {
+            tracing::debug!(
+                method = %req.method(),
+                path = %req.uri().path(),
+                required = %required,
+                "API key scope check failed"
+            );
+            return (
+                StatusCode::FORBIDDEN,
+                Json(json!({
+                    "error": format!("API key is missing the '{}' permission", required),
+                    "code": "insufficient_scope",
+                    "required": required,
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn perms(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn api_key_user(permissions: &[&str]) -> AuthUser {
+        AuthUser {
+            user_id: "apikey:test".to_string(),
+            org_id: Some("org_1".to_string()),
+            org_slug: None,
+            org_role: None,
+            email: None,
+            display_name: Some("test key".to_string()),
+            scoped_org_ids: vec!["org_1".to_string()],
+            scoped_project_ids: vec![],
+            api_key_permissions: Some(perms(permissions)),
+        }
+    }
+
+    async fn post_issue(auth: AuthUser) -> StatusCode {
+        let app = Router::new()
+            .route("/issues", post(|| async { "created" }))
+            .layer(axum::middleware::from_fn(enforce_api_key_scopes))
+            .layer(axum::Extension(auth));
+        let req = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/issues")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap().status()
+    }
+
+    #[test]
+    fn test_required_scope_mapping() {
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/issues").as_deref(),
+            Some("issues:write")
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/projects/abc/issues").as_deref(),
+            Some("issues:read")
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/issues/abc").as_deref(),
+            Some("issues:delete")
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/projects/abc/purge").as_deref(),
+            Some("projects:delete")
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/issues/abc/comments").as_deref(),
+            Some("comments:write")
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/ai/chat").as_deref(),
+            Some("ai:chat")
+        );
+        assert_eq!(required_scope(&Method::GET, "/dashboard/summary"), None);
+    }
+
+    #[test]
+    fn test_read_only_key_cannot_write() {
+        let read_only = perms(&["issues:read", "projects:read"]);
+        assert!(check_request(&read_only, &Method::GET, "/issues").is_ok());
+        assert_eq!(
+            check_request(&read_only, &Method::POST, "/issues").unwrap_err(),
+            "issues:write"
+        );
+        assert!(check_request(&read_only, &Method::PATCH, "/views/abc").is_err());
+        assert!(check_request(&perms(&["admin:full"]), &Method::DELETE, "/projects/abc").is_ok());
+        assert!(check_request(&perms(&["issues:write"]), &Method::GET, "/issues").is_ok());
+    }
+
+    #[test]
+    fn test_write_key_limited_to_its_resources() {
+        let issues_write = perms(&["issues:write"]);
+        for path in [
+            "/github/mappings",
+            "/org/settings",
+            "/api-keys",
+            "/integrations/slack",
+        ] {
+            assert_eq!(
+                check_request(&issues_write, &Method::POST, path).unwrap_err(),
+                "admin:full"
+            );
+        }
+        assert_eq!(
+            check_request(&issues_write, &Method::POST, "/views").unwrap_err(),
+            "views:write"
+        );
+        assert_eq!(
+            check_request(&issues_write, &Method::DELETE, "/favorites").unwrap_err(),
+            "views:write"
+        );
+        assert_eq!(
+            check_request(&issues_write, &Method::POST, "/invites/bulk").unwrap_err(),
+            "members:invite"
+        );
+        assert_eq!(
+            check_request(&issues_write, &Method::POST, "/notifications/read-all").unwrap_err(),
+            "admin:full"
+        );
+        assert!(check_request(&issues_write, &Method::GET, "/dashboard/summary").is_ok());
+        assert!(check_request(&perms(&["views:write"]), &Method::POST, "/favorites").is_ok());
+        assert!(check_request(&perms(&["admin:full"]), &Method::POST, "/github/mappings").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_key_rejected_on_issue_create() {
+        assert_eq!(
+            post_issue(api_key_user(&["issues:read"])).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            post_issue(api_key_user(&["issues:write"])).await,
+            StatusCode::OK
+        );
+
+        let mut clerk_user = api_key_user(&[]);
+        clerk_user.user_id = "user_123".to_string();
+        clerk_user.api_key_permissions = None;
+        assert_eq!(post_issue(clerk_user).await, StatusCode::OK);
+    }
+}
@@ -1,4 +1,12 @@
-use axum::{extract::Request, middleware::Next, response::Response};
+use axum::{
+    extract::Request,
+    http::{header, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use super::request_id::REQUEST_ID_HEADER;
 
 /// Adds security headers to every response (similar to helmet.js).
 pub async fn security_headers(request: Request, next: Next) -> Response {
@@ -28,3 +36,82 @@ pub async fn security_headers(request: Request, next: Next) -> Response {
     );
     response
 }
+
+/// Browser origins allowed to call the API: `CORS_ORIGINS` if set, otherwise
+/// the Clerk authorized parties plus `APP_URL`.
+fn cors_allowed_origins() -> Vec<HeaderValue> {
+    let configured = std::env::var("CORS_ORIGINS").unwrap_or_default();
+    let mut origins: Vec<String> = if configured.trim().is_empty() {
+        let mut parties = super::clerk_authorized_parties();
+        if let Ok(app_url) = std::env::var("APP_URL") {
+            parties.push(app_url);
+        }
+        parties
+    } else {
+        configured.split(',').map(|s| s.to_string()).collect()
+    };
+    origins.sort();
+    origins.dedup();
+
+    origins
+        .iter()
+        .map(|o| o.trim().trim_end_matches('/'))
+        .filter(|o| {
+            if *o == "*" {
+                tracing::warn!(
+                    "Ignoring '*' in CORS_ORIGINS; set CORS_ALLOW_ALL=true for local dev"
+                );
+            }
+            !o.is_empty() && *o != "*"
+        })
+        .filter_map(|o| o.parse().ok())
+        .collect()
+}
+
+/// CORS for the API. Credentialed requests are only allowed from the origin
+/// allowlist, with the methods and headers the web app actually sends.
+/// `CORS_ALLOW_ALL=true` switches to any-origin (no credentials) for local dev.
+pub fn cors_layer() -> CorsLayer {
+    let request_id = HeaderName::from_static(REQUEST_ID_HEADER);
+    let exposed = [
+        request_id.clone(),
+        HeaderName::from_static("x-ratelimit-requests-limit"),
+        HeaderName::from_static("x-ratelimit-requests-remaining"),
+        HeaderName::from_static("x-ratelimit-requests-reset"),
+    ];
+
+    let allow_all = std::env::var("CORS_ALLOW_ALL")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    if allow_all {
+        tracing::warn!("CORS_ALLOW_ALL is set: accepting any origin (local dev only)");
+        return CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any)
+            .expose_headers(exposed);
+    }
+
+    let origins = cors_allowed_origins();
+    tracing::info!(count = origins.len(), "CORS origin allowlist loaded");
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            HeaderName::from_static("last-event-id"),
+            request_id,
+        ])
+        .expose_headers(exposed)
+        .allow_credentials(true)
+}
@@ -0,0 +1,475 @@
+//! Embedded schema migrations. Each version runs once at startup and is
+//! recorded in `_migrations` with a SHA-256 of its SQL; startup refuses to
+//! continue if an applied file has since been edited. Versions with a paired
+//! `NNN_name.down.sql` can be rolled back with `MIGRATE_DOWN=<version>` (or
+//! `baaton-api migrate-down <version>`).
+
+use anyhow::{bail, Context};
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, PgConnection, PgPool};
+
+/// Embedded schema migrations, applied in order at startup and recorded in
+/// `_migrations`.
+pub static MIGRATIONS: &[(i32, &str)] = &[
+    (1, include_str!("../migrations/001_init.sql")),
+    (2, include_str!("../migrations/002_sprints.sql")),
+    (3, include_str!("../migrations/003_project_tags.sql")),
+    (4, include_str!("../migrations/004_issue_category.sql")),
+    (5, include_str!("../migrations/005_org_upsert.sql")),
+    (6, include_str!("../migrations/006_github_integration.sql")),
+    (
+        7,
+        include_str!("../migrations/007_issue_creator_duedate.sql"),
+    ),
+    (8, include_str!("../migrations/008_activity_log.sql")),
+    (
+        9,
+        include_str!("../migrations/009_openclaw_integration.sql"),
+    ),
+    (
+        10,
+        include_str!("../migrations/010_milestone_enhancements.sql"),
+    ),
+    (11, include_str!("../migrations/011_saved_views.sql")),
+    (
+        12,
+        include_str!("../migrations/012_templates_estimates.sql"),
+    ),
+    (
+        13,
+        include_str!("../migrations/013_project_auto_assign.sql"),
+    ),
+    (15, include_str!("../migrations/015_public_submit.sql")),
+    (16, include_str!("../migrations/016_issue_timestamps.sql")),
+    (
+        17,
+        include_str!("../migrations/017_api_key_project_scope.sql"),
+    ),
+    (18, include_str!("../migrations/018_webhooks.sql")),
+    (
+        19,
+        include_str!("../migrations/019_project_github_metadata.sql"),
+    ),
+    (20, include_str!("../migrations/020_label_colors.sql")),
+    (21, include_str!("../migrations/021_search_vector.sql")),
+    (22, include_str!("../migrations/022_snooze.sql")),
+    (23, include_str!("../migrations/023_activity_log.sql")),
+    (24, include_str!("../migrations/024_auto_archive.sql")),
+    (25, include_str!("../migrations/025_issue_relations.sql")),
+    (26, include_str!("../migrations/026_recurring_issues.sql")),
+    (27, include_str!("../migrations/027_cycles.sql")),
+    (28, include_str!("../migrations/028_notifications.sql")),
+    (29, include_str!("../migrations/029_custom_views.sql")),
+    (30, include_str!("../migrations/030_sla.sql")),
+    (31, include_str!("../migrations/031_templates.sql")),
+    (32, include_str!("../migrations/032_automations.sql")),
+    (33, include_str!("../migrations/033_pricing.sql")),
+    (34, include_str!("../migrations/034_initiatives.sql")),
+    (35, include_str!("../migrations/035_attachments.sql")),
+    (36, include_str!("../migrations/036_slack.sql")),
+    (37, include_str!("../migrations/037_ai_usage.sql")),
+    (371, include_str!("../migrations/037_agent_config.sql")), // was duplicate v37, re-indexed as 371
+    (38, include_str!("../migrations/038_superadmin.sql")),
+    (39, include_str!("../migrations/039_admin_audit_log.sql")),
+    (40, include_str!("../migrations/040_user_plans.sql")),
+    (41, include_str!("../migrations/041_gamification.sql")),
+    (42, include_str!("../migrations/042_gamification_v2.sql")),
+    (
+        43,
+        include_str!("../migrations/043_backfill_gamification.sql"),
+    ),
+    (
+        44,
+        include_str!("../migrations/044_custom_fields_estimates.sql"),
+    ),
+    (45, include_str!("../migrations/045_plans_per_user.sql")),
+    (46, include_str!("../migrations/046_approval_workflow.sql")),
+    (47, include_str!("../migrations/047_advanced_api.sql")),
+    (48, include_str!("../migrations/048_agent_sessions.sql")),
+    (49, include_str!("../migrations/049_project_context.sql")),
+    (
+        50,
+        include_str!("../migrations/050_api_keys_created_by.sql"),
+    ),
+    (51, include_str!("../migrations/051_api_key_org_scopes.sql")),
+    (
+        52,
+        include_str!("../migrations/052_api_key_org_scope_mode.sql"),
+    ),
+    (53, include_str!("../migrations/053_source_ai.sql")),
+    (
+        54,
+        include_str!("../migrations/054_github_display_metadata.sql"),
+    ),
+    (55, include_str!("../migrations/055_user_profiles.sql")),
+    (56, include_str!("../migrations/056_sync_job_claims.sql")),
+    (57, include_str!("../migrations/057_pm_reviews.sql")),
+    (58, include_str!("../migrations/058_issue_trigram.sql")),
+    (59, include_str!("../migrations/059_issue_merge.sql")),
+    (
+        60,
+        include_str!("../migrations/060_public_submit_spam_filter.sql"),
+    ),
+    (61, include_str!("../migrations/061_custom_view_sort.sql")),
+    (
+        62,
+        include_str!("../migrations/062_custom_view_default.sql"),
+    ),
+    (63, include_str!("../migrations/063_project_archive.sql")),
+    (64, include_str!("../migrations/064_project_members.sql")),
+    (65, include_str!("../migrations/065_strict_assignees.sql")),
+    (
+        66,
+        include_str!("../migrations/066_notification_failures.sql"),
+    ),
+    (67, include_str!("../migrations/067_notify_reporters.sql")),
+    (
+        68,
+        include_str!("../migrations/068_notification_digests.sql"),
+    ),
+    (
+        69,
+        include_str!("../migrations/069_notification_preference_channels.sql"),
+    ),
+    (
+        70,
+        include_str!("../migrations/070_org_role_requirements.sql"),
+    ),
+    (71, include_str!("../migrations/071_audit_log.sql")),
+    (72, include_str!("../migrations/072_invite_short_links.sql")),
+    (
+        73,
+        include_str!("../migrations/073_normalize_tldr_tests_status.sql"),
+    ),
+    (74, include_str!("../migrations/074_tldr_vcs_links.sql")),
+    (75, include_str!("../migrations/075_project_categories.sql")),
+    (
+        76,
+        include_str!("../migrations/076_org_assignee_reports.sql"),
+    ),
+    (77, include_str!("../migrations/077_display_id_padding.sql")),
+    (
+        78,
+        include_str!("../migrations/078_project_issue_counters.sql"),
+    ),
+    (
+        79,
+        include_str!("../migrations/079_issue_snooze_until_timestamp.sql"),
+    ),
+    (80, include_str!("../migrations/080_issue_views.sql")),
+    (81, include_str!("../migrations/081_user_favorites.sql")),
+    (82, include_str!("../migrations/082_time_entries.sql")),
+    (
+        83,
+        include_str!("../migrations/083_project_working_days.sql"),
+    ),
+    (84, include_str!("../migrations/084_sla_policies.sql")),
+    (
+        85,
+        include_str!("../migrations/085_github_issue_defaults.sql"),
+    ),
+    (
+        86,
+        include_str!("../migrations/086_github_label_sync_job.sql"),
+    ),
+    (
+        87,
+        include_str!("../migrations/087_github_webhook_event_retention.sql"),
+    ),
+    (88, include_str!("../migrations/088_api_key_usage.sql")),
+    (
+        89,
+        include_str!("../migrations/089_notification_failures_claim.sql"),
+    ),
+];
+
+/// Rollback SQL for the versions that have a `.down.sql` file.
+pub static DOWN_MIGRATIONS: &[(i32, &str)] = &[
+    (59, include_str!("../migrations/059_issue_merge.down.sql")),
+    (
+        60,
+        include_str!("../migrations/060_public_submit_spam_filter.down.sql"),
+    ),
+    (
+        61,
+        include_str!("../migrations/061_custom_view_sort.down.sql"),
+    ),
+    (
+        62,
+        include_str!("../migrations/062_custom_view_default.down.sql"),
+    ),
+    (
+        63,
+        include_str!("../migrations/063_project_archive.down.sql"),
+    ),
+    (
+        64,
+        include_str!("../migrations/064_project_members.down.sql"),
+    ),
+    (
+        65,
+        include_str!("../migrations/065_strict_assignees.down.sql"),
+    ),
+    (
+        66,
+        include_str!("../migrations/066_notification_failures.down.sql"),
+    ),
+    (
+        67,
+        include_str!("../migrations/067_notify_reporters.down.sql"),
+    ),
+    (
+        68,
+        include_str!("../migrations/068_notification_digests.down.sql"),
+    ),
+    (
+        69,
+        include_str!("../migrations/069_notification_preference_channels.down.sql"),
+    ),
+    (
+        70,
+        include_str!("../migrations/070_org_role_requirements.down.sql"),
+    ),
+    (71, include_str!("../migrations/071_audit_log.down.sql")),
+    (
+        72,
+        include_str!("../migrations/072_invite_short_links.down.sql"),
+    ),
+    (
+        73,
+        include_str!("../migrations/073_normalize_tldr_tests_status.down.sql"),
+    ),
+    (
+        74,
+        include_str!("../migrations/074_tldr_vcs_links.down.sql"),
+    ),
+    (
+        75,
+        include_str!("../migrations/075_project_categories.down.sql"),
+    ),
+    (
+        76,
+        include_str!("../migrations/076_org_assignee_reports.down.sql"),
+    ),
+    (
+        77,
+        include_str!("../migrations/077_display_id_padding.down.sql"),
+    ),
+    (
+        78,
+        include_str!("../migrations/078_project_issue_counters.down.sql"),
+    ),
+    (
+        79,
+        include_str!("../migrations/079_issue_snooze_until_timestamp.down.sql"),
+    ),
+    (80, include_str!("../migrations/080_issue_views.down.sql")),
+    (
+        81,
+        include_str!("../migrations/081_user_favorites.down.sql"),
+    ),
+    (82, include_str!("../migrations/082_time_entries.down.sql")),
+    (
+        83,
+        include_str!("../migrations/083_project_working_days.down.sql"),
+    ),
+    (84, include_str!("../migrations/084_sla_policies.down.sql")),
+    (
+        85,
+        include_str!("../migrations/085_github_issue_defaults.down.sql"),
+    ),
+    (
+        86,
+        include_str!("../migrations/086_github_label_sync_job.down.sql"),
+    ),
+    (
+        87,
+        include_str!("../migrations/087_github_webhook_event_retention.down.sql"),
+    ),
+    (88, include_str!("../migrations/088_api_key_usage.down.sql")),
+    (
+        89,
+        include_str!("../migrations/089_notification_failures_claim.down.sql"),
+    ),
+];
+
+fn checksum(sql: &str) -> String {
+    hex::encode(Sha256::digest(sql.as_bytes()))
+}
+
+/// Rollback target from `MIGRATE_DOWN` or `migrate-down <version>` argv.
+pub fn down_target() -> Option<i32> {
+    let mut args = std::env::args().skip(1);
+    let from_args = match args.next().as_deref() {
+        Some("migrate-down") => args.next(),
+        _ => None,
+    };
+    from_args
+        .or_else(|| std::env::var("MIGRATE_DOWN").ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Migrations can legitimately outrun `DB_STATEMENT_TIMEOUT_MS` (index
+/// builds), so they run on their own connection with the timeout off. It is
+/// detached, so it closes instead of returning to the pool.
+async fn migration_connection(pool: &PgPool) -> anyhow::Result<PgConnection> {
+    let mut conn = pool.acquire().await?.detach();
+    sqlx::query("SET statement_timeout = 0")
+        .execute(&mut conn)
+        .await?;
+    Ok(conn)
+}
+
+async fn ensure_table(conn: &mut PgConnection) -> anyhow::Result<()> {
+    sqlx::raw_sql(
+        "CREATE TABLE IF NOT EXISTS _migrations (version INT PRIMARY KEY, applied_at TIMESTAMPTZ DEFAULT now());
+         ALTER TABLE _migrations ADD COLUMN IF NOT EXISTS checksum TEXT;",
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Verify applied migrations are unchanged, then apply pending ones. A
+/// failing migration is logged and retried on the next restart.
+pub async fn run(pool: &PgPool) -> anyhow::Result<()> {
+    let mut conn = migration_connection(pool).await?;
+    ensure_table(&mut conn).await?;
+
+    let applied: Vec<(i32, Option<String>)> =
+        sqlx::query_as("SELECT version, checksum FROM _migrations")
+            .fetch_all(&mut conn)
+            .await?;
+
+    let mut changed = Vec::new();
+    for &(version, sql) in MIGRATIONS {
+        match applied.iter().find(|(v, _)| *v == version) {
+            Some((_, Some(recorded))) if *recorded != checksum(sql) => {
+                tracing::error!(
+                    version,
+                    recorded = %&recorded[..12.min(recorded.len())],
+                    embedded = %&checksum(sql)[..12],
+                    "applied migration was edited after it ran"
+                );
+                changed.push(version)
+            }
+            // Applied before checksums were recorded: trust what's embedded now
+            Some((_, None)) => {
+                sqlx::query("UPDATE _migrations SET checksum = $2 WHERE version = $1")
+                    .bind(version)
+                    .bind(checksum(sql))
+                    .execute(&mut conn)
+                    .await?;
+            }
+            Some(_) => {}
+            None => match sqlx::raw_sql(sql).execute(&mut conn).await {
+                Ok(_) => {
+                    sqlx::query("INSERT INTO _migrations (version, checksum) VALUES ($1, $2)")
+                        .bind(version)
+                        .bind(checksum(sql))
+                        .execute(&mut conn)
+                        .await?;
+                    tracing::info!("Applied migration {}", version);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Migration {} failed (will retry on next restart): {}",
+                        version,
+                        e
+                    );
+                }
+            },
+        }
+    }
+
+    if !changed.is_empty() {
+        bail!(
+            "applied migrations {:?} no longer match their embedded SQL; add a new migration \
+             instead of editing an applied one (or clear the checksum in _migrations to accept)",
+            changed
+        );
+    }
+
+    tracing::info!("Migrations applied");
+    Ok(())
+}
+
+/// Roll back every applied migration listed after `target` in
+/// `MIGRATIONS`, newest first, each in its own transaction. Order follows the
+/// list, not the number (371 sits between 37 and 38). Stops before touching
+/// anything if one lacks a down file.
+pub async fn rollback(pool: &PgPool, target: i32) -> anyhow::Result<()> {
+    let mut conn = migration_connection(pool).await?;
+    ensure_table(&mut conn).await?;
+
+    let Some(position) = MIGRATIONS.iter().position(|(v, _)| *v == target) else {
+        bail!("unknown migration version {}", target);
+    };
+    let applied: Vec<i32> = sqlx::query_scalar("SELECT version FROM _migrations")
+        .fetch_all(&mut conn)
+        .await?;
+    let to_revert: Vec<i32> = MIGRATIONS[position + 1..]
+        .iter()
+        .rev()
+        .map(|(v, _)| *v)
+        .filter(|v| applied.contains(v))
+        .collect();
+
+    let missing: Vec<i32> = to_revert
+        .iter()
+        .copied()
+        .filter(|v| !DOWN_MIGRATIONS.iter().any(|(d, _)| d == v))
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "cannot roll back to {}: no down migration for versions {:?}",
+            target,
+            missing
+        );
+    }
+
+    for version in to_revert {
+        let (_, sql) = DOWN_MIGRATIONS
+            .iter()
+            .find(|(d, _)| *d == version)
+            .expect("checked above");
+        let mut tx = conn.begin().await?;
+        sqlx::raw_sql(sql)
+            .execute(tx.as_mut())
+            .await
+            .with_context(|| format!("down migration {} failed", version))?;
+        sqlx::query("DELETE FROM _migrations WHERE version = $1")
+            .bind(version)
+            .execute(tx.as_mut())
+            .await?;
+        tx.commit().await?;
+        tracing::info!("Rolled back migration {}", version);
+    }
+
+    tracing::info!("Database is at migration {}", target);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_down_migrations_pair_with_up() {
+        for (version, _) in DOWN_MIGRATIONS {
+            assert!(
+                MIGRATIONS.iter().any(|(v, _)| v == version),
+                "down migration {} has no up migration",
+                version
+            );
+        }
+        let mut versions: Vec<i32> = MIGRATIONS.iter().map(|(v, _)| *v).collect();
+        versions.sort_unstable();
+        versions.dedup();
+        assert_eq!(
+            versions.len(),
+            MIGRATIONS.len(),
+            "duplicate migration version"
+        );
+    }
+}
@@ -13,6 +13,7 @@ pub struct GitHubInstallation {
     pub github_account_id: i64,
     pub github_account_login: String,
     pub github_account_type: String,
+    pub github_account_avatar_url: Option<String>,
     pub permissions: serde_json::Value,
     pub status: String,
     pub installed_by: Option<String>,
@@ -32,6 +33,8 @@ pub struct GitHubRepository {
     pub full_name: String,
     pub default_branch: String,
     pub is_private: bool,
+    pub description: Option<String>,
+    pub html_url: Option<String>,
     pub last_synced_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -50,6 +53,16 @@ pub struct GitHubRepoMapping {
     pub sync_comments: bool,
     pub auto_create_issues: bool,
     pub status_mapping: serde_json::Value,
+    /// Defaults for issues auto-created from GitHub issues.
+    #[sqlx(default)]
+    pub default_issue_type: Option<String>,
+    #[sqlx(default)]
+    pub default_priority: Option<String>,
+    #[sqlx(default)]
+    pub default_tags: Vec<String>,
+    /// GitHub label → tag, or → `{ type, priority, tags }`.
+    #[sqlx(default)]
+    pub label_mapping: serde_json::Value,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -156,15 +169,72 @@ pub struct GitHubSyncJob {
 
 // ─── Composite Response Types ─────────────────────────
 
+/// A TLDR tied to a PR link and/or commit on the same issue
+#[derive(Debug, Serialize, FromRow)]
+pub struct TldrVcsLink {
+    pub tldr_id: Uuid,
+    pub agent_name: String,
+    pub github_pr_link_id: Option<Uuid>,
+    pub pr_number: Option<i32>,
+    pub commit_sha: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Data returned for an issue's GitHub sidebar
 #[derive(Debug, Serialize)]
 pub struct IssueGitHubData {
     pub github_issue: Option<GitHubIssueLink>,
     pub pull_requests: Vec<GitHubPrLink>,
     pub commits: Vec<GitHubCommitLink>,
+    pub tldrs: Vec<TldrVcsLink>,
     pub branch_name: String,
 }
 
+/// A Baaton issue a previewed PR or commit would link to
+#[derive(Debug, Serialize, FromRow)]
+pub struct PreviewLinkedIssue {
+    pub id: Uuid,
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// What the sync engine would do for one recent PR
+#[derive(Debug, Serialize)]
+pub struct PrSyncPreview {
+    pub pr_number: i32,
+    pub pr_title: String,
+    pub pr_url: String,
+    pub pr_state: String,
+    pub head_branch: String,
+    pub issue: Option<PreviewLinkedIssue>,
+    /// Status the issue would move to; None when unchanged
+    pub new_status: Option<String>,
+    /// "link_pr" and/or "change_status"
+    pub actions: Vec<&'static str>,
+}
+
+/// What the sync engine would do for one recent commit
+#[derive(Debug, Serialize)]
+pub struct CommitSyncPreview {
+    pub sha: String,
+    pub message: String,
+    pub url: String,
+    pub issue: Option<PreviewLinkedIssue>,
+    /// "link_commit" when the commit would be newly linked
+    pub actions: Vec<&'static str>,
+}
+
+/// Dry run of a repo mapping against the repo's recent activity
+#[derive(Debug, Serialize)]
+pub struct RepoMappingPreview {
+    pub mapping_id: Uuid,
+    pub repo_full_name: String,
+    pub status_mapping: serde_json::Value,
+    pub pull_requests: Vec<PrSyncPreview>,
+    pub commits: Vec<CommitSyncPreview>,
+}
+
 // ─── Request DTOs ─────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -177,6 +247,10 @@ pub struct CreateRepoMapping {
     pub sync_comments: Option<bool>,
     pub auto_create_issues: Option<bool>,
     pub status_mapping: Option<serde_json::Value>,
+    pub default_issue_type: Option<String>,
+    pub default_priority: Option<String>,
+    pub default_tags: Option<Vec<String>>,
+    pub label_mapping: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -187,5 +261,19 @@ pub struct UpdateRepoMapping {
     pub sync_comments: Option<bool>,
     pub auto_create_issues: Option<bool>,
     pub status_mapping: Option<serde_json::Value>,
+    /// Empty string clears.
+    pub default_issue_type: Option<String>,
+    /// Empty string clears.
+    pub default_priority: Option<String>,
+    pub default_tags: Option<Vec<String>>,
+    pub label_mapping: Option<serde_json::Value>,
     pub is_active: Option<bool>,
 }
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PreviewRepoMapping {
+    /// Preview this status mapping instead of the stored one.
+    pub status_mapping: Option<serde_json::Value>,
+    /// Recent PRs and commits to check (default 20, max 100).
+    pub limit: Option<u32>,
+}
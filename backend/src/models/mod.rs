@@ -34,6 +34,29 @@ pub struct Project {
     pub github_repo_url: Option<String>,
     pub github_metadata: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
+    /// Set by `DELETE /projects/{id}`; archived projects are hidden and take no new issues.
+    #[sqlx(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Reject issue assignees who aren't on the project roster.
+    #[sqlx(default)]
+    pub strict_assignees: bool,
+    /// Reject issue categories that aren't defined in `project_categories`.
+    #[sqlx(default)]
+    pub validate_categories: bool,
+    /// Zero-pad new issue numbers to this many digits (`BAA-0007`). 0 = off.
+    #[sqlx(default)]
+    pub display_id_padding: i32,
+    /// ISO weekdays (1 = Monday) counted for auto due dates; None = every day.
+    #[sqlx(default)]
+    pub working_days: Option<Vec<i32>>,
+    /// Dates skipped for auto due dates.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    /// Whether the caller starred this project; only set on `GET /projects/{id}`.
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorited: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -53,12 +76,33 @@ pub struct ProjectAutoAssignSettings {
     pub project_id: Uuid,
     pub auto_assign_mode: String,
     pub default_assignee_id: Option<String>,
+    pub strict_assignees: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateProjectAutoAssignSettings {
     pub auto_assign_mode: String,
     pub default_assignee_id: Option<String>,
+    pub strict_assignees: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ProjectMember {
+    pub user_id: String,
+    pub role: String,
+    pub added_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Resolved from the profile cache, not stored.
+    #[sqlx(skip)]
+    pub display_name: Option<String>,
+    #[sqlx(skip)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddProjectMember {
+    pub user_id: String,
+    pub role: Option<String>,
 }
 
 // ─── Milestone ────────────────────────────────────────
@@ -109,13 +153,30 @@ pub struct Issue {
     pub qualified_at: Option<DateTime<Utc>>,
     pub qualified_by: Option<String>,
     pub estimate: Option<i32>,
+    /// Sum of the direct children's estimates. Computed, never stored; only
+    /// set on single-issue reads and lists called with `rollup_estimate=true`.
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollup_estimate: Option<i64>,
     pub sprint_id: Option<Uuid>,
     pub status_changed_at: Option<DateTime<Utc>>,
     pub closed_at: Option<DateTime<Utc>>,
-    pub snoozed_until: Option<NaiveDate>,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// Who set the current snooze; told when it ends.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub snoozed_by: Option<String>,
     pub archived: bool,
     pub archived_at: Option<DateTime<Utc>>,
-    pub sla_deadline: Option<DateTime<Utc>>,
+    /// SLA targets from the project's policy for this priority.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub sla_response_due_at: Option<DateTime<Utc>>,
+    pub sla_resolution_due_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub sla_responded_at: Option<DateTime<Utc>>,
+    /// Set once a target is missed; stays set.
     pub sla_breached: Option<bool>,
     pub agent_status: Option<String>,
     pub agent_session_id: Option<Uuid>,
@@ -162,12 +223,55 @@ pub struct UpdateIssue {
     pub attachments: Option<serde_json::Value>,
     pub estimate: Option<Option<i32>>,
     pub sprint_id: Option<Option<Uuid>>,
-    pub snoozed_until: Option<Option<NaiveDate>>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD`; `null` unsnoozes.
+    #[serde(default, deserialize_with = "deserialize_snooze_patch")]
+    pub snoozed_until: Option<Option<DateTime<Utc>>>,
     /// When true, skip workflow transition warnings (agent confirmed the move).
     #[serde(default)]
     pub force: bool,
 }
 
+/// Parse a snooze time: an RFC 3339 timestamp, or a plain `YYYY-MM-DD` date
+/// meaning the start of that day (UTC).
+pub fn parse_snooze_until(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|t| t.and_utc())
+        })
+}
+
+/// `snoozed_until` for `POST /issues/{id}/snooze`.
+pub fn deserialize_snooze_until<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    parse_snooze_until(&raw).ok_or_else(|| {
+        serde::de::Error::custom("expected an RFC 3339 timestamp or YYYY-MM-DD date")
+    })
+}
+
+/// `snoozed_until` on PATCH: absent leaves it alone, `null` clears it.
+fn deserialize_snooze_patch<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<DateTime<Utc>>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        None => Ok(Some(None)),
+        Some(raw) => parse_snooze_until(&raw).map(|t| Some(Some(t))).ok_or_else(|| {
+            serde::de::Error::custom("expected an RFC 3339 timestamp or YYYY-MM-DD date")
+        }),
+    }
+}
+
 // ─── Agent Session ────────────────────────────────────
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -251,6 +355,24 @@ pub struct IssueRelation {
     pub created_at: DateTime<Utc>,
 }
 
+/// An issue on the other end of a `blocks` / `blocked_by` relation.
+#[derive(Debug, Serialize, FromRow)]
+pub struct DependencyRef {
+    pub id: Uuid,
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IssueDependencies {
+    /// Issues that must finish before this one
+    pub blocked_by: Vec<DependencyRef>,
+    /// Issues waiting on this one
+    pub blocks: Vec<DependencyRef>,
+}
+
 // ─── Recurrence Rule ──────────────────────────────────
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -288,6 +410,9 @@ pub struct Tldr {
     pub decisions_made: Vec<String>,
     pub edge_cases: Vec<String>,
     pub context_updates: Vec<String>,
+    /// PR link whose URL matches `pr_url` on the same issue, if GitHub knows it.
+    pub github_pr_link_id: Option<Uuid>,
+    pub commit_sha: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -301,6 +426,16 @@ pub struct CreateTldr {
     pub decisions_made: Option<Vec<String>>,
     pub edge_cases: Option<Vec<String>>,
     pub context_updates: Option<Vec<String>>,
+    pub commit_sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTldr {
+    pub summary: Option<String>,
+    pub files_changed: Option<Vec<String>>,
+    pub tests_status: Option<String>,
+    pub pr_url: Option<String>,
+    pub commit_sha: Option<String>,
 }
 
 // ─── Project Context ───────────────────────────────────
@@ -386,7 +521,12 @@ pub struct IssueDetail {
     pub issue: Issue,
     pub tldrs: Vec<Tldr>,
     pub comments: Vec<Comment>,
+    pub dependencies: IssueDependencies,
     pub agent_session: Option<AgentSession>,
+    /// Whether the caller starred this issue
+    pub favorited: bool,
+    /// Sum of `time_entries.minutes` logged on the issue
+    pub total_minutes: i64,
     /// Compact one-line summary for LLM context efficiency
     #[serde(rename = "_context")]
     pub context_summary: String,
@@ -413,6 +553,25 @@ pub struct CreateProjectTag {
     pub description: Option<String>,
 }
 
+// ─── Project Category ─────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ProjectCategory {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProjectCategory {
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
 // ─── API Key ──────────────────────────────────────────
 
 #[allow(dead_code)]
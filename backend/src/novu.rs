@@ -1,9 +1,31 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Replays per failed trigger before it is left as `failed`.
+const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+/// First retry after 1 min, doubling up to 6h.
+const RETRY_BASE_SECS: i64 = 60;
+const RETRY_MAX_SECS: i64 = 6 * 3600;
+/// How long a claimed retry may stay `sending` before another worker takes it.
+const CLAIM_LEASE_SECS: i64 = 600;
+/// Most users a group mention (`@project`, `@here`, `@everyone`) expands to.
+pub const MAX_GROUP_MENTION: usize = 50;
 
 lazy_static! {
     static ref MENTION_RE: Regex = Regex::new(r"@(user_[a-zA-Z0-9]+)").unwrap();
+    static ref GROUP_MENTION_RE: Regex =
+        Regex::new(r"(?:^|[^\w@.])@(project|here|everyone)\b").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupMention {
+    /// `@project` / `@here`: everyone on the project roster.
+    Project,
+    /// `@everyone`: every org member. Only org admins may use it.
+    Everyone,
 }
 
 #[derive(Clone)]
@@ -11,6 +33,8 @@ pub struct NovuClient {
     http: reqwest::Client,
     api_url: String,
     secret_key: String,
+    /// Where failed triggers are queued for retry (`notification_failures`).
+    failures: Option<PgPool>,
 }
 
 /// Subscriber info for trigger_many
@@ -20,6 +44,40 @@ pub struct Subscriber {
     pub name: Option<String>,
 }
 
+/// Build subscribers for `user_ids` with their email and display name filled
+/// in from the profile cache, so Novu can deliver to people it hasn't seen yet.
+pub async fn resolve_subscribers(pool: &PgPool, user_ids: Vec<String>) -> Vec<Subscriber> {
+    let mut profiles = crate::middleware::resolve_profiles_cached(pool, &user_ids).await;
+    let mut seen = std::collections::HashSet::new();
+    user_ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .map(|id| {
+            let (name, email) = profiles.remove(&id).unwrap_or_default();
+            Subscriber { id, email, name }
+        })
+        .collect()
+}
+
+/// `resolve_subscribers` for a workflow trigger, minus the users who muted
+/// that workflow in their notification preferences.
+pub async fn subscribers_for(
+    pool: &PgPool,
+    org_id: &str,
+    workflow_id: &str,
+    user_ids: Vec<String>,
+) -> Vec<Subscriber> {
+    let user_ids = crate::routes::notifications::filter_muted(
+        pool,
+        org_id,
+        workflow_id,
+        crate::routes::notifications::CHANNEL_EMAIL,
+        user_ids,
+    )
+    .await;
+    resolve_subscribers(pool, user_ids).await
+}
+
 impl NovuClient {
     /// Returns None if NOVU_SECRET_KEY is not set (graceful degradation).
     pub fn from_env() -> Option<Self> {
@@ -30,13 +88,21 @@ impl NovuClient {
         tracing::info!("Novu client initialized (api: {})", api_url);
 
         Some(Self {
-            http: reqwest::Client::new(),
+            http: crate::util::http::novu().clone(),
             api_url,
             secret_key,
+            failures: None,
         })
     }
 
-    /// Fire a single notification trigger.
+    /// Queue failed triggers in `notification_failures` instead of dropping them.
+    pub fn with_failure_queue(mut self, pool: PgPool) -> Self {
+        self.failures = Some(pool);
+        self
+    }
+
+    /// Fire a single notification trigger. Failures are logged and, when a
+    /// failure queue is configured, stored for the retry worker.
     pub async fn trigger(
         &self,
         workflow_id: &str,
@@ -45,6 +111,54 @@ impl NovuClient {
         name: Option<&str>,
         payload: serde_json::Value,
     ) {
+        match self
+            .send(workflow_id, subscriber_id, email, name, &payload)
+            .await
+        {
+            Ok(()) => {
+                tracing::debug!(
+                    workflow = workflow_id,
+                    subscriber = subscriber_id,
+                    "novu.trigger.ok"
+                );
+            }
+            Err(error) => {
+                tracing::warn!(
+                    workflow = workflow_id,
+                    subscriber = subscriber_id,
+                    error = %error,
+                    "novu.trigger.failed"
+                );
+                if let Some(ref pool) = self.failures {
+                    let queued = sqlx::query(
+                        r#"INSERT INTO notification_failures
+                           (workflow_id, subscriber_id, subscriber_email, subscriber_name, payload, error)
+                           VALUES ($1, $2, $3, $4, $5, $6)"#,
+                    )
+                    .bind(workflow_id)
+                    .bind(subscriber_id)
+                    .bind(email)
+                    .bind(name)
+                    .bind(&payload)
+                    .bind(&error)
+                    .execute(pool)
+                    .await;
+                    if let Err(e) = queued {
+                        tracing::error!(error = %e, "novu.trigger.queue_failed");
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        workflow_id: &str,
+        subscriber_id: &str,
+        email: Option<&str>,
+        name: Option<&str>,
+        payload: &serde_json::Value,
+    ) -> Result<(), String> {
         let mut subscriber = json!({ "subscriberId": subscriber_id });
         if let Some(e) = email {
             subscriber["email"] = json!(e);
@@ -60,41 +174,20 @@ impl NovuClient {
         });
 
         let url = format!("{}/v1/events/trigger", self.api_url);
-        match self
+        let resp = self
             .http
             .post(&url)
             .header("Authorization", format!("ApiKey {}", self.secret_key))
             .json(&body)
             .send()
             .await
-        {
-            Ok(resp) if resp.status().is_success() => {
-                tracing::debug!(
-                    workflow = workflow_id,
-                    subscriber = subscriber_id,
-                    "novu.trigger.ok"
-                );
-            }
-            Ok(resp) => {
-                let status = resp.status();
-                let text = resp.text().await.unwrap_or_default();
-                tracing::warn!(
-                    workflow = workflow_id,
-                    subscriber = subscriber_id,
-                    status = %status,
-                    body = %text,
-                    "novu.trigger.failed"
-                );
-            }
-            Err(e) => {
-                tracing::warn!(
-                    workflow = workflow_id,
-                    subscriber = subscriber_id,
-                    error = %e,
-                    "novu.trigger.error"
-                );
-            }
+            .map_err(|e| e.to_string())?;
+        if resp.status().is_success() {
+            return Ok(());
         }
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        Err(format!("{}: {}", status, text))
     }
 
     /// Trigger a workflow for multiple subscribers concurrently (fire-and-forget each).
@@ -117,6 +210,101 @@ impl NovuClient {
     }
 }
 
+type QueuedFailure = (
+    Uuid,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    serde_json::Value,
+    i32,
+);
+
+/// Background worker replaying queued Novu failures with exponential
+/// backoff. Due rows are claimed (`sending`) before the replay so each is
+/// sent by one replica only; a claim older than `CLAIM_LEASE_SECS` (the
+/// replica died mid-send) is taken over. Delivered rows are deleted;
+/// exhausted ones are marked `failed`.
+pub async fn retry_worker(client: NovuClient, pool: PgPool) {
+    tracing::info!("Novu retry worker started");
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+
+        let due = sqlx::query_as::<_, QueuedFailure>(
+            r#"UPDATE notification_failures SET status = 'sending', updated_at = now()
+               WHERE id IN (
+                 SELECT id FROM notification_failures
+                 WHERE (status = 'retrying' AND next_retry_at <= now())
+                    OR (status = 'sending' AND updated_at < now() - make_interval(secs => $1))
+                 ORDER BY next_retry_at ASC
+                 LIMIT 50
+                 FOR UPDATE SKIP LOCKED
+               )
+               RETURNING id, workflow_id, subscriber_id, subscriber_email, subscriber_name, payload, attempts"#,
+        )
+        .bind(CLAIM_LEASE_SECS as f64)
+        .fetch_all(&pool)
+        .await;
+
+        let due = match due {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::error!(error = %e, "novu_retry: query failed");
+                continue;
+            }
+        };
+
+        for (id, workflow, subscriber, email, name, payload, attempts) in due {
+            let result = client
+                .send(
+                    &workflow,
+                    &subscriber,
+                    email.as_deref(),
+                    name.as_deref(),
+                    &payload,
+                )
+                .await;
+
+            let update = match result {
+                Ok(()) => {
+                    tracing::info!(workflow = %workflow, subscriber = %subscriber, attempts, "novu_retry: delivered");
+                    sqlx::query("DELETE FROM notification_failures WHERE id = $1")
+                        .bind(id)
+                        .execute(&pool)
+                        .await
+                }
+                Err(error) => {
+                    let attempts = attempts + 1;
+                    let status = if attempts >= MAX_DELIVERY_ATTEMPTS {
+                        tracing::warn!(workflow = %workflow, subscriber = %subscriber, error = %error, "novu_retry: giving up");
+                        "failed"
+                    } else {
+                        "retrying"
+                    };
+                    let delay = (RETRY_BASE_SECS << attempts.min(20)).min(RETRY_MAX_SECS);
+                    sqlx::query(
+                        r#"UPDATE notification_failures
+                           SET attempts = $2, status = $3, error = $4,
+                               next_retry_at = now() + make_interval(secs => $5),
+                               updated_at = now()
+                           WHERE id = $1"#,
+                    )
+                    .bind(id)
+                    .bind(attempts)
+                    .bind(status)
+                    .bind(&error)
+                    .bind(delay as f64)
+                    .execute(&pool)
+                    .await
+                }
+            };
+            if let Err(e) = update {
+                tracing::error!(error = %e, "novu_retry: update failed");
+            }
+        }
+    }
+}
+
 /// Extract `@user_xxx` mentions from text.
 pub fn parse_mentions(text: &str) -> Vec<String> {
     MENTION_RE
@@ -125,6 +313,43 @@ pub fn parse_mentions(text: &str) -> Vec<String> {
         .collect()
 }
 
+/// Extract group mentions from text, each group once.
+pub fn parse_group_mentions(text: &str) -> Vec<GroupMention> {
+    let mut groups = Vec::new();
+    for c in GROUP_MENTION_RE.captures_iter(text) {
+        let group = match &c[1] {
+            "everyone" => GroupMention::Everyone,
+            _ => GroupMention::Project,
+        };
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+/// Explicit mentions first, then group members up to `MAX_GROUP_MENTION`.
+/// Duplicates and `exclude` (the author) are dropped.
+pub fn merge_mentions(explicit: Vec<String>, group: Vec<String>, exclude: &str) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for id in explicit {
+        if id != exclude && !merged.contains(&id) {
+            merged.push(id);
+        }
+    }
+    let mut expanded = 0;
+    for id in group {
+        if expanded == MAX_GROUP_MENTION {
+            break;
+        }
+        if id != exclude && !merged.contains(&id) {
+            merged.push(id);
+            expanded += 1;
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +372,29 @@ mod tests {
         let mentions = parse_mentions(text);
         assert_eq!(mentions.len(), 2); // returns all occurrences
     }
+
+    #[test]
+    fn test_parse_group_mentions() {
+        assert_eq!(
+            parse_group_mentions("@here and @project, also @everyone"),
+            vec![GroupMention::Project, GroupMention::Everyone]
+        );
+        assert!(parse_group_mentions("mail ops@here.com or @projects").is_empty());
+    }
+
+    #[test]
+    fn test_merge_mentions_dedupes_and_caps() {
+        let ids = |range: std::ops::Range<usize>| -> Vec<String> {
+            range.map(|i| format!("user_{}", i)).collect()
+        };
+        let merged = merge_mentions(
+            vec!["user_1".into(), "user_1".into(), "user_me".into()],
+            ids(0..200),
+            "user_me",
+        );
+        assert_eq!(merged[0], "user_1");
+        assert!(!merged.contains(&"user_me".to_string()));
+        assert_eq!(merged.iter().filter(|id| *id == "user_1").count(), 1);
+        assert_eq!(merged.len(), 1 + MAX_GROUP_MENTION);
+    }
 }
@@ -1,25 +1,69 @@
-use axum::{extract::{Path, Query, State}, Extension, Json};
-use serde::Deserialize;
+use axum::{extract::{Path, Query, State}, http::StatusCode, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{ActivityEntry, ApiResponse};
+use crate::routes::issues::require_user_org_scope;
 
 #[derive(Debug, Deserialize)]
 pub struct ActivityParams {
     pub limit: Option<i64>,
+    /// Only entries with this action (e.g. `status_changed`).
+    pub action: Option<String>,
+    /// Only entries by this actor.
+    pub user_id: Option<String>,
+    pub project_id: Option<Uuid>,
+    /// `next_cursor` from the previous page — returns strictly older entries.
+    pub before: Option<String>,
 }
 
-/// GET /api/v1/issues/:id/activity — activity log for a specific issue
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+/// Decode a `before` cursor into its (created_at, id) keyset position.
+fn parse_before(before: Option<&str>) -> Result<Option<(DateTime<Utc>, Uuid)>, ApiError> {
+    let Some(cursor) = before else {
+        return Ok(None);
+    };
+    crate::filter::decode_cursor(cursor)
+        .and_then(|(ts, id)| {
+            let ts = DateTime::parse_from_rfc3339(&ts).ok()?.with_timezone(&Utc);
+            Some((ts, id.parse().ok()?))
+        })
+        .map(Some)
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Invalid cursor"}))))
+}
+
+/// Trim the look-ahead row and build the `{ data, next_cursor }` body.
+fn page(mut entries: Vec<ActivityEntry>, limit: i64) -> Json<serde_json::Value> {
+    let next_cursor = if entries.len() > limit as usize {
+        entries.truncate(limit as usize);
+        entries
+            .last()
+            .map(|e| crate::filter::encode_cursor(&e.created_at.to_rfc3339(), &e.id.to_string()))
+    } else {
+        None
+    };
+    Json(json!({
+        "data": entries,
+        "next_cursor": next_cursor,
+    }))
+}
+
+/// GET /api/v1/issues/:id/activity — activity log for a specific issue,
+/// newest first, paginated with `before`
 pub async fn list_by_issue(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
     Path(issue_id): Path<Uuid>,
     Query(params): Query<ActivityParams>,
-) -> Json<ApiResponse<Vec<ActivityEntry>>> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let org_id = auth.org_id.unwrap_or_default();
-    let limit = params.limit.unwrap_or(50);
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let before = parse_before(params.before.as_deref())?;
 
     let entries = sqlx::query_as::<_, ActivityEntry>(
         r#"
@@ -29,13 +73,16 @@ pub async fn list_by_issue(
         FROM activity_log al
         LEFT JOIN issues i ON i.id = al.issue_id
         WHERE al.issue_id = $1 AND al.org_id = $2
-        ORDER BY al.created_at DESC
+          AND ($4::timestamptz IS NULL OR (al.created_at, al.id) < ($4, $5))
+        ORDER BY al.created_at DESC, al.id DESC
         LIMIT $3
         "#,
     )
     .bind(issue_id)
     .bind(&org_id)
-    .bind(limit)
+    .bind(limit + 1)
+    .bind(before.map(|(ts, _)| ts))
+    .bind(before.map(|(_, id)| id))
     .fetch_all(&pool)
     .await
     .unwrap_or_else(|e| {
@@ -43,17 +90,20 @@ pub async fn list_by_issue(
         vec![]
     });
 
-    Json(ApiResponse::new(entries))
+    Ok(page(entries, limit))
 }
 
-/// GET /api/v1/activity — recent activity across the org (for dashboard)
+/// GET /api/v1/activity — recent activity across the org (for dashboard),
+/// optionally filtered by `action`, `user_id` and `project_id`, paginated
+/// with `before`
 pub async fn list_recent(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
     Query(params): Query<ActivityParams>,
-) -> Json<ApiResponse<Vec<ActivityEntry>>> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let org_id = auth.org_id.unwrap_or_default();
-    let limit = params.limit.unwrap_or(30);
+    let limit = params.limit.unwrap_or(30).clamp(1, 200);
+    let before = parse_before(params.before.as_deref())?;
 
     let entries = sqlx::query_as::<_, ActivityEntry>(
         r#"
@@ -63,12 +113,21 @@ pub async fn list_recent(
         FROM activity_log al
         LEFT JOIN issues i ON i.id = al.issue_id
         WHERE al.org_id = $1
-        ORDER BY al.created_at DESC
+          AND ($3::text IS NULL OR al.action = $3)
+          AND ($4::text IS NULL OR al.user_id = $4)
+          AND ($5::uuid IS NULL OR al.project_id = $5)
+          AND ($6::timestamptz IS NULL OR (al.created_at, al.id) < ($6, $7))
+        ORDER BY al.created_at DESC, al.id DESC
         LIMIT $2
         "#,
     )
     .bind(&org_id)
-    .bind(limit)
+    .bind(limit + 1)
+    .bind(&params.action)
+    .bind(&params.user_id)
+    .bind(params.project_id)
+    .bind(before.map(|(ts, _)| ts))
+    .bind(before.map(|(_, id)| id))
     .fetch_all(&pool)
     .await
     .unwrap_or_else(|e| {
@@ -76,7 +135,112 @@ pub async fn list_recent(
         vec![]
     });
 
-    Json(ApiResponse::new(entries))
+    Ok(page(entries, limit))
+}
+
+// ─── Issue history ────────────────────────────────────
+
+/// Consecutive edits by the same user this close together collapse into one
+/// timeline entry.
+const HISTORY_GROUP_WINDOW_SECS: i64 = 120;
+
+#[derive(Debug, Serialize)]
+pub struct HistoryChange {
+    pub action: String,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Ready-to-render line, e.g. "priority: low → high".
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub changes: Vec<HistoryChange>,
+}
+
+fn change_summary(entry: &ActivityEntry) -> String {
+    match entry.field.as_deref() {
+        Some(field) => format!(
+            "{}: {} → {}",
+            field,
+            entry.old_value.as_deref().unwrap_or("none"),
+            entry.new_value.as_deref().unwrap_or("none"),
+        ),
+        None => entry.action.replace('_', " "),
+    }
+}
+
+/// Fold oldest-first activity rows into timeline entries.
+fn group_history(entries: Vec<ActivityEntry>) -> Vec<HistoryEntry> {
+    let mut grouped: Vec<HistoryEntry> = Vec::new();
+    for entry in entries {
+        let change = HistoryChange {
+            summary: change_summary(&entry),
+            action: entry.action,
+            field: entry.field,
+            old_value: entry.old_value,
+            new_value: entry.new_value,
+        };
+        match grouped.last_mut() {
+            Some(last)
+                if last.user_id == entry.user_id
+                    && (entry.created_at - last.ended_at).num_seconds() <= HISTORY_GROUP_WINDOW_SECS =>
+            {
+                last.ended_at = entry.created_at;
+                last.changes.push(change);
+            }
+            _ => grouped.push(HistoryEntry {
+                user_id: entry.user_id,
+                user_name: entry.user_name,
+                started_at: entry.created_at,
+                ended_at: entry.created_at,
+                changes: vec![change],
+            }),
+        }
+    }
+    grouped
+}
+
+/// GET /api/v1/issues/:id/history — the issue's activity as a timeline,
+/// oldest first, with rapid edits by the same user grouped together.
+pub async fn issue_history(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<HistoryEntry>>>, (StatusCode, Json<serde_json::Value>)> {
+    let (_current_org_id, org_ids) = require_user_org_scope(&pool, &auth).await?;
+
+    let org_id: String = sqlx::query_scalar(
+        "SELECT p.org_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = ANY($2)",
+    )
+    .bind(issue_id)
+    .bind(&org_ids)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))))?;
+
+    let entries = sqlx::query_as::<_, ActivityEntry>(
+        r#"
+        SELECT al.*
+        FROM activity_log al
+        WHERE al.issue_id = $1 AND al.org_id = $2
+        ORDER BY al.created_at ASC, al.id ASC
+        LIMIT 1000
+        "#,
+    )
+    .bind(issue_id)
+    .bind(&org_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(group_history(entries))))
 }
 
 /// Helper: log an activity entry AND fire gamification counters.
@@ -126,3 +290,42 @@ pub async fn log_activity(
         crate::routes::gamification::record_activity(pool, user_id, org_id, action).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user: &str, secs: i64, field: &str, old: &str, new: &str) -> ActivityEntry {
+        ActivityEntry {
+            id: Uuid::new_v4(),
+            org_id: "org_1".to_string(),
+            project_id: None,
+            issue_id: None,
+            user_id: user.to_string(),
+            user_name: Some(user.to_string()),
+            action: "issue_updated".to_string(),
+            field: Some(field.to_string()),
+            old_value: Some(old.to_string()),
+            new_value: Some(new.to_string()),
+            metadata: serde_json::json!({}),
+            created_at: DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap(),
+            issue_title: None,
+            issue_display_id: None,
+        }
+    }
+
+    #[test]
+    fn test_group_history_merges_rapid_edits_by_same_user() {
+        let history = group_history(vec![
+            entry("alice", 0, "priority", "low", "high"),
+            entry("alice", 30, "status", "todo", "in_progress"),
+            entry("bob", 40, "status", "in_progress", "done"),
+            entry("alice", 1000, "priority", "high", "low"),
+        ]);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].changes.len(), 2);
+        assert_eq!(history[0].changes[0].summary, "priority: low → high");
+        assert_eq!(history[1].user_id, "bob");
+        assert_eq!(history[2].changes.len(), 1);
+    }
+}
@@ -132,14 +132,15 @@ pub async fn set_plan(
     }
 
     // Write to organizations (legacy compat) AND user_plans for all members
-    sqlx::query(
-        "INSERT INTO organizations (id, name, slug, plan) VALUES ($1, $1, $1, $2) ON CONFLICT (id) DO UPDATE SET plan = $2"
-    )
-    .bind(&org_id)
-    .bind(&body.plan)
-    .execute(&pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+    ensure_org(&pool, &org_id, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+    sqlx::query("UPDATE organizations SET plan = $2 WHERE id = $1")
+        .bind(&org_id)
+        .bind(&body.plan)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
     // Also set user_plans for all org members (via Clerk)
     let members = fetch_org_members(&org_id).await;
@@ -493,8 +494,7 @@ pub async fn fetch_org_members(org_id: &str) -> Vec<Value> {
     let clerk_key = std::env::var("CLERK_SECRET_KEY").unwrap_or_default();
     if clerk_key.is_empty() { return vec![]; }
 
-    let client = reqwest::Client::new();
-    let resp = client
+    let resp = crate::util::http::clerk()
         .get(format!("https://api.clerk.com/v1/organizations/{org_id}/memberships?limit=100"))
         .header("Authorization", format!("Bearer {clerk_key}"))
         .send()
@@ -634,7 +634,7 @@ pub async fn get_billing(
         let mut map = std::collections::HashMap::new();
         let clerk_key = std::env::var("CLERK_SECRET_KEY").unwrap_or_default();
         if !clerk_key.is_empty() {
-            let client = reqwest::Client::new();
+            let client = crate::util::http::clerk();
             for oid in &org_ids {
                 if let Ok(resp) = client
                     .get(format!("https://api.clerk.com/v1/organizations/{oid}"))
@@ -851,57 +851,58 @@ pub async fn get_audit_log(
 
 // ─── Org name resolution from Clerk ─────────────────────────────────────
 
-/// Fetch org name from Clerk API and update DB if name is currently the org_id
+/// Fetch org name from Clerk API and update DB while the name is still a placeholder
+/// (the org id or its slug)
 pub async fn ensure_org_name(pool: &PgPool, org_id: &str) {
     // Skip if name is already resolved
-    let current_name: Option<String> = sqlx::query_scalar(
-        "SELECT name FROM organizations WHERE id = $1"
+    let current: Option<(String, String)> = sqlx::query_as(
+        "SELECT name, slug FROM organizations WHERE id = $1"
     ).bind(org_id).fetch_optional(pool).await.ok().flatten();
 
-    match current_name {
-        Some(ref name) if name != org_id && !name.is_empty() => return, // Already resolved
+    match current {
+        Some((ref name, ref slug)) if name != org_id && name != slug && !name.is_empty() => return, // Already resolved
         _ => {}
     }
 
-    let clerk_key = std::env::var("CLERK_SECRET_KEY").unwrap_or_default();
-    if clerk_key.is_empty() { return; }
-
-    let resp = reqwest::Client::new()
-        .get(format!("https://api.clerk.com/v1/organizations/{org_id}"))
-        .header("Authorization", format!("Bearer {clerk_key}"))
-        .send()
-        .await;
-
-    if let Ok(r) = resp {
-        if let Ok(body) = r.json::<serde_json::Value>().await {
-            let name = body.get("name").and_then(|n| n.as_str()).unwrap_or("");
-            let slug = body.get("slug").and_then(|s| s.as_str()).unwrap_or("");
-            let logo = body.get("image_url").and_then(|u| u.as_str()).unwrap_or("");
-            if !name.is_empty() {
-                let _ = sqlx::query(
-                    "UPDATE organizations SET name = $2, slug = CASE WHEN slug = id THEN $3 ELSE slug END WHERE id = $1"
-                )
-                .bind(org_id)
-                .bind(name)
-                .bind(if slug.is_empty() { name } else { slug })
-                .execute(pool)
-                .await;
-                tracing::info!(org_id = %org_id, name = %name, "Resolved org name from Clerk");
-            }
-            // Also store logo URL if we have a column (future)
-            let _ = logo;
-        }
+    let Some((name, slug, _image_url)) = crate::routes::dashboard::fetch_org_metadata(org_id).await else {
+        return;
+    };
+    if name.is_empty() {
+        return;
     }
+    let _ = sqlx::query(
+        "UPDATE organizations SET name = $2, slug = CASE WHEN slug = id THEN $3 ELSE slug END WHERE id = $1"
+    )
+    .bind(org_id)
+    .bind(&name)
+    .bind(if slug.is_empty() { &name } else { &slug })
+    .execute(pool)
+    .await;
+    tracing::info!(org_id = %org_id, name = %name, "Resolved org name from Clerk");
 }
 
-/// Ensure org exists in DB with proper name. Fire-and-forget name resolution.
-pub fn upsert_org_background(pool: PgPool, org_id: String) {
+/// Ensure the org row exists before anything references it. New rows take the
+/// slug from the caller's token when there is one (else the org id), and a row
+/// still carrying its id as slug is upgraded once a real slug shows up. The
+/// display name is resolved from Clerk in the background.
+pub async fn ensure_org(pool: &PgPool, org_id: &str, slug_from_token: Option<&str>) -> Result<(), sqlx::Error> {
+    let slug = slug_from_token.map(str::trim).filter(|s| !s.is_empty()).unwrap_or(org_id);
+    sqlx::query(
+        r#"INSERT INTO organizations (id, name, slug)
+           SELECT $1, $2, CASE WHEN EXISTS (SELECT 1 FROM organizations WHERE slug = $2 AND id <> $1)
+                               THEN $1 ELSE $2 END
+           ON CONFLICT (id) DO UPDATE SET slug = EXCLUDED.slug
+           WHERE organizations.slug = organizations.id AND EXCLUDED.slug <> organizations.id"#
+    )
+    .bind(org_id)
+    .bind(slug)
+    .execute(pool)
+    .await?;
+
+    let pool = pool.clone();
+    let org_id = org_id.to_string();
     tokio::spawn(async move {
-        // Upsert first
-        let _ = sqlx::query(
-            "INSERT INTO organizations (id, name, slug) VALUES ($1, $1, $1) ON CONFLICT (id) DO NOTHING"
-        ).bind(&org_id).execute(&pool).await;
-        // Then resolve name
         ensure_org_name(&pool, &org_id).await;
     });
+    Ok(())
 }
@@ -17,7 +17,7 @@ use crate::models::{
     CreateAgentSession, CreateAgentStep, UpdateAgentSession,
 };
 use crate::routes::activity::log_activity;
-use crate::routes::sse::{EventSender, broadcast_event};
+use crate::routes::sse::{EventSender, BaatonEvent, broadcast_event};
 
 const VALID_STATUSES: &[&str] = &["pending", "active", "awaiting_input", "completed", "error"];
 const VALID_STEP_TYPES: &[&str] = &["info", "action", "thought", "error", "tool_call", "tool_result"];
@@ -114,7 +114,10 @@ pub async fn create(
     ).await;
 
     // SSE broadcast
-    broadcast_event(&sse_tx, org_id, "agent_session.started", &serde_json::to_string(&session).unwrap_or_default());
+    broadcast_event(&sse_tx, org_id, &BaatonEvent::Custom {
+        event_type: "agent_session.started".to_string(),
+        payload: serde_json::to_value(&session).unwrap_or_default(),
+    });
 
     let hints = vec![
         ActionHint::recommended(
@@ -371,7 +374,10 @@ pub async fn update(
     } else {
         "agent_session.updated"
     };
-    broadcast_event(&sse_tx, org_id, sse_event, &serde_json::to_string(&session).unwrap_or_default());
+    broadcast_event(&sse_tx, org_id, &BaatonEvent::Custom {
+        event_type: sse_event.to_string(),
+        payload: serde_json::to_value(&session).unwrap_or_default(),
+    });
 
     let mut hints = vec![];
     if new_status == "completed" {
@@ -466,7 +472,10 @@ pub async fn create_step(
         .await;
 
     // SSE broadcast — enables live stream without DB polling
-    broadcast_event(&sse_tx, org_id, "agent_session.step", &serde_json::to_string(&step).unwrap_or_default());
+    broadcast_event(&sse_tx, org_id, &BaatonEvent::Custom {
+        event_type: "agent_session.step".to_string(),
+        payload: serde_json::to_value(&step).unwrap_or_default(),
+    });
 
     Ok(Json(ApiResponse::new(step)))
 }
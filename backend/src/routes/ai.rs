@@ -1,38 +1,23 @@
 use std::collections::{HashMap, HashSet};
 
 use axum::{
-    extract::{Extension, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
-    response::{IntoResponse, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
-use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use sqlx::PgPool;
+use std::convert::Infallible;
 use uuid::Uuid;
 
 use crate::{middleware::AuthUser, models::ApiResponse};
 
-// ─── Key Endpoint (returns Gemini API key to authenticated users) ──
-
-pub async fn get_key(
-    Extension(_auth): Extension<AuthUser>,
-) -> Response {
-    match std::env::var("GEMINI_API_KEY") {
-        Ok(k) if !k.is_empty() => {
-            Json(serde_json::json!({"key": k})).into_response()
-        }
-        _ => {
-            (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(serde_json::json!({"error": "AI service not configured"})),
-            )
-                .into_response()
-        }
-    }
-}
-
 // ─── Request Types (from frontend) ───────────────────
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +30,14 @@ pub struct ChatRequest {
     pub system_instruction: Option<String>,
     #[serde(default)]
     pub model: Option<String>,
+    /// Stream Gemini chunks as `text/event-stream` (default). `false` returns
+    /// the whole Gemini response as JSON, as before.
+    #[serde(default)]
+    pub stream: Option<bool>,
+    /// Run whitelisted Baaton function calls server-side (see `server_tool`)
+    /// and return Gemini's final answer. Implies `stream: false`.
+    #[serde(default, alias = "execute_tools")]
+    pub execute_tools: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -77,6 +70,45 @@ struct GeminiPart {
     text: String,
 }
 
+// ─── Model Selection ──────────────────────────────────
+
+const DEFAULT_CHAT_MODEL: &str = "gemini-3-flash-preview";
+
+/// Model used when the client doesn't pick one, from `GEMINI_DEFAULT_MODEL`.
+fn default_chat_model() -> String {
+    std::env::var("GEMINI_DEFAULT_MODEL")
+        .ok()
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| DEFAULT_CHAT_MODEL.to_string())
+}
+
+/// Models a client may request, from `GEMINI_ALLOWED_MODELS` (comma-separated).
+/// The default model is always allowed; unset means only the default.
+fn allowed_chat_models(default: &str) -> Vec<String> {
+    let mut models: Vec<String> = std::env::var("GEMINI_ALLOWED_MODELS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+    if !models.iter().any(|m| m == default) {
+        models.push(default.to_string());
+    }
+    models
+}
+
+/// Pick the Gemini model for a chat request. Errors with the requested name
+/// when it isn't on the allowlist.
+fn resolve_chat_model(requested: Option<&str>) -> Result<String, String> {
+    let default = default_chat_model();
+    match requested.map(str::trim).filter(|m| !m.is_empty()) {
+        None => Ok(default),
+        Some(m) if allowed_chat_models(&default).iter().any(|a| a == m) => Ok(m.to_string()),
+        Some(m) => Err(m.to_string()),
+    }
+}
+
 // ─── Handler ──────────────────────────────────────────
 
 pub async fn chat(
@@ -111,6 +143,10 @@ pub async fn chat(
         ).into_response();
     }
 
+    if let Err(err) = check_org_token_quota(&pool, org_id).await {
+        return err.into_response();
+    }
+
     // Validate input
     if body.messages.is_empty() {
         return (
@@ -148,7 +184,22 @@ pub async fn chat(
         }
     };
 
-    let model = body.model.as_deref().unwrap_or("gemini-3-flash-preview");
+    let model = match resolve_chat_model(body.model.as_deref()) {
+        Ok(m) => m,
+        Err(requested) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": format!("model '{}' is not allowed", requested),
+                    "code": "model_not_allowed",
+                    "field": "model",
+                })),
+            )
+                .into_response();
+        }
+    };
+    let streaming = body.stream.unwrap_or(true) && !body.execute_tools;
+    let execute_tools = body.execute_tools;
 
     // Convert messages to Gemini format
     let contents: Vec<GeminiContent> = body
@@ -177,12 +228,23 @@ pub async fn chat(
         system_instruction,
     };
 
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, api_key
-    );
+    if execute_tools {
+        return chat_with_server_tools(&pool, &auth, org_id, &model, &api_key, &gemini_body).await;
+    }
 
-    let client = reqwest::Client::new();
+    let url = if streaming {
+        format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+            model, api_key
+        )
+    } else {
+        format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            model, api_key
+        )
+    };
+
+    let client = crate::util::http::gemini();
     let resp = match client.post(&url).json(&gemini_body).send().await {
         Ok(r) => r,
         Err(e) => {
@@ -196,6 +258,15 @@ pub async fn chat(
     };
 
     let status = resp.status();
+    if streaming && status.is_success() {
+        let stream = proxy_gemini_stream(resp, pool, org_id.to_string(), auth.user_id.clone(), model);
+        let mut response = Sse::new(stream)
+            .keep_alive(KeepAlive::new().interval(std::time::Duration::from_secs(15)).text("ping"))
+            .into_response();
+        response.headers_mut().insert("cache-control", "no-cache".parse().unwrap());
+        return response;
+    }
+
     let resp_bytes = match resp.bytes().await {
         Ok(b) => b,
         Err(e) => {
@@ -235,31 +306,263 @@ pub async fn chat(
     };
 
     // ── Record AI usage with token tracking (Marathon-inspired metering) ──
-    let (tokens_in, tokens_out) = {
-        let usage = gemini_json.pointer("/usageMetadata");
-        (
-            usage.and_then(|u| u.get("promptTokenCount")).and_then(|v| v.as_i64()).unwrap_or(0) as i32,
-            usage.and_then(|u| u.get("candidatesTokenCount")).and_then(|v| v.as_i64()).unwrap_or(0) as i32,
-        )
-    };
+    let (tokens_in, tokens_out) = usage_tokens(&gemini_json);
+    record_chat_usage(&pool, org_id, &auth.user_id, tokens_in, tokens_out, &model).await;
 
+    Json(gemini_json).into_response()
+}
+
+/// (prompt, candidates) token counts from a Gemini response or stream chunk.
+fn usage_tokens(gemini_json: &Value) -> (i32, i32) {
+    let usage = gemini_json.pointer("/usageMetadata");
+    (
+        usage.and_then(|u| u.get("promptTokenCount")).and_then(|v| v.as_i64()).unwrap_or(0) as i32,
+        usage.and_then(|u| u.get("candidatesTokenCount")).and_then(|v| v.as_i64()).unwrap_or(0) as i32,
+    )
+}
+
+async fn record_chat_usage(
+    pool: &PgPool,
+    org_id: &str,
+    user_id: &str,
+    tokens_in: i32,
+    tokens_out: i32,
+    model: &str,
+) {
     let _ = sqlx::query(
         "INSERT INTO ai_usage (org_id, user_id, event_type, tokens_in, tokens_out, model) VALUES ($1, $2, 'chat_message', $3, $4, $5)"
     )
     .bind(org_id)
-    .bind(&auth.user_id)
+    .bind(user_id)
     .bind(tokens_in)
     .bind(tokens_out)
-    .bind(body.model.as_deref().unwrap_or("gemini-3-flash-preview"))
-    .execute(&pool)
+    .bind(model)
+    .execute(pool)
     .await;
+}
 
-    Json(gemini_json).into_response()
+/// Max Gemini round-trips when executing tools server-side.
+const MAX_TOOL_ROUNDS: usize = 3;
+
+/// Baaton actions the chat proxy may execute itself, mapped to the
+/// `ai_tools` implementation that backs them.
+fn server_tool(name: &str) -> Option<&'static str> {
+    match name {
+        "create_issue" => Some("create_issue"),
+        "update_status" => Some("update_issue"),
+        "list_issues" => Some("search_issues"),
+        _ => None,
+    }
+}
+
+async fn generate_content(client: &reqwest::Client, url: &str, body: &Value) -> Result<Value, Response> {
+    let bad_gateway = |error: String| {
+        (StatusCode::BAD_GATEWAY, Json(json!({"error": error}))).into_response()
+    };
+    let resp = client.post(url).json(body).send().await.map_err(|e| {
+        tracing::error!("Gemini API request failed: {}", e);
+        bad_gateway("Failed to reach AI service".to_string())
+    })?;
+    let status = resp.status();
+    if !status.is_success() {
+        tracing::error!("Gemini API error {}: {}", status.as_u16(), resp.text().await.unwrap_or_default());
+        return Err(bad_gateway(format!("AI service returned status {}", status.as_u16())));
+    }
+    resp.json::<Value>().await.map_err(|e| {
+        tracing::error!("Failed to parse Gemini response as JSON: {}", e);
+        bad_gateway("Invalid AI service response".to_string())
+    })
+}
+
+/// `execute_tools` mode: while Gemini answers only with whitelisted function
+/// calls, run them against the caller's org, feed the results back and ask
+/// again. Any other function call is returned verbatim for the client to
+/// handle. Every executed action is written to the activity log, and the
+/// final response carries an `executedTools` list.
+async fn chat_with_server_tools(
+    pool: &PgPool,
+    auth: &AuthUser,
+    org_id: &str,
+    model: &str,
+    api_key: &str,
+    gemini_body: &GeminiRequest,
+) -> Response {
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        model, api_key
+    );
+    let client = crate::util::http::gemini();
+    let org_ids = vec![org_id.to_string()];
+    let mut request = serde_json::to_value(gemini_body).unwrap_or_default();
+    let mut executed: Vec<Value> = Vec::new();
+    let (mut tokens_in, mut tokens_out) = (0, 0);
+    let mut round = 0;
+
+    loop {
+        let mut gemini_json = match generate_content(client, &url, &request).await {
+            Ok(v) => v,
+            Err(resp) => return resp,
+        };
+        let (round_in, round_out) = usage_tokens(&gemini_json);
+        tokens_in += round_in;
+        tokens_out += round_out;
+
+        let parts: Vec<Value> = gemini_json
+            .pointer("/candidates/0/content/parts")
+            .and_then(|p| p.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let calls: Vec<(String, Value)> = parts
+            .iter()
+            .filter_map(|p| p.get("functionCall"))
+            .map(|call| {
+                let name = call.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                (name.to_string(), call.get("args").cloned().unwrap_or(json!({})))
+            })
+            .collect();
+
+        let done = calls.is_empty()
+            || round >= MAX_TOOL_ROUNDS
+            || calls.iter().any(|(name, _)| server_tool(name).is_none());
+        if done {
+            record_chat_usage(pool, org_id, &auth.user_id, tokens_in, tokens_out, model).await;
+            gemini_json["executedTools"] = json!(executed);
+            return Json(gemini_json).into_response();
+        }
+
+        let mut function_responses: Vec<Value> = Vec::with_capacity(calls.len());
+        for (name, args) in calls {
+            let args = if name == "update_status" {
+                json!({"issue_id": args.get("issue_id"), "status": args.get("status")})
+            } else {
+                args
+            };
+            let Some(tool_name) = server_tool(&name) else { continue };
+            let result = crate::routes::ai_tools::execute_tool(
+                pool,
+                &org_ids,
+                &auth.user_id,
+                auth.display_name.as_deref(),
+                tool_name,
+                args.clone(),
+            )
+            .await;
+            let (ok, summary, response) = match &result {
+                Ok(r) => (true, r.summary.clone(), json!({"result": r.for_model})),
+                Err(e) => (false, e.clone(), json!({"error": e})),
+            };
+
+            crate::routes::activity::log_activity(
+                pool,
+                org_id,
+                None,
+                None,
+                &auth.user_id,
+                auth.display_name.as_deref(),
+                "ai_tool_executed",
+                None,
+                None,
+                None,
+                Some(json!({"tool": name, "args": args, "ok": ok, "summary": summary})),
+            )
+            .await;
+
+            executed.push(json!({"name": name, "args": args, "ok": ok, "summary": summary}));
+            function_responses.push(json!({"functionResponse": {"name": name, "response": response}}));
+        }
+
+        if let Some(contents) = request.get_mut("contents").and_then(|c| c.as_array_mut()) {
+            contents.push(json!({"role": "model", "parts": parts}));
+            contents.push(json!({"role": "user", "parts": function_responses}));
+        }
+        round += 1;
+    }
+}
+
+/// Forward Gemini's `alt=sse` stream as it arrives: each upstream chunk is
+/// re-emitted verbatim as a `data:` event, followed by `event: done`. An
+/// upstream failure mid-stream ends with `event: error` (`{ "error": ... }`).
+/// Usage is recorded from the last chunk's `usageMetadata`. The upstream is
+/// read in its own task, which runs to the end even if the client
+/// disconnects, so usage is always recorded.
+fn proxy_gemini_stream(
+    mut resp: reqwest::Response,
+    pool: PgPool,
+    org_id: String,
+    user_id: String,
+    model: String,
+) -> impl futures::Stream<Item = Result<Event, Infallible>> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Event>(32);
+
+    tokio::spawn(async move {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut usage = (0, 0);
+        let mut failed = false;
+
+        // Sends fail once the client is gone; keep reading for the usage anyway
+        'read: loop {
+            let chunk = match resp.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::error!("Gemini stream interrupted: {}", e);
+                    let _ = tx
+                        .send(
+                            Event::default()
+                                .event("error")
+                                .data(json!({"error": "AI service stream interrupted"}).to_string()),
+                        )
+                        .await;
+                    failed = true;
+                    break;
+                }
+            };
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line);
+                let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                    continue;
+                };
+                let Ok(parsed) = serde_json::from_str::<Value>(data) else {
+                    continue;
+                };
+                if let Some(err) = parsed.get("error") {
+                    tracing::error!("Gemini stream error: {}", err);
+                    let message = err
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("AI service error");
+                    let _ = tx
+                        .send(Event::default().event("error").data(json!({"error": message}).to_string()))
+                        .await;
+                    failed = true;
+                    break 'read;
+                }
+                if parsed.get("usageMetadata").is_some() {
+                    usage = usage_tokens(&parsed);
+                }
+                let _ = tx.send(Event::default().data(data)).await;
+            }
+        }
+
+        record_chat_usage(&pool, &org_id, &user_id, usage.0, usage.1, &model).await;
+        if !failed {
+            let _ = tx.send(Event::default().event("done").data("{}")).await;
+        }
+    });
+
+    async_stream::stream! {
+        while let Some(event) = rx.recv().await {
+            yield Ok(event);
+        }
+    }
 }
 
 // ─── Deterministic PM Full Review (no Gemini tools) ─────────────
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct PmFullReviewRequest {
     #[serde(default)]
     pub project_ids: Option<Vec<Uuid>>,
@@ -267,8 +570,17 @@ pub struct PmFullReviewRequest {
     pub horizon_days: Option<i64>,
     #[serde(default)]
     pub sprint_length_days: Option<i64>,
+    /// Bucket issues into the project's real milestones and sprints instead
+    /// of the synthetic A/B/C milestones and rolling sprint windows.
+    #[serde(default)]
+    pub use_existing: bool,
+    /// Open-issue count above which an assignee is flagged as overloaded.
+    #[serde(default)]
+    pub overload_threshold: Option<usize>,
 }
 
+const DEFAULT_OVERLOAD_THRESHOLD: usize = 10;
+
 #[derive(Debug, sqlx::FromRow, Clone)]
 struct PmProjectRow {
     id: Uuid,
@@ -291,11 +603,39 @@ struct PmIssueRow {
     assignee_ids: Vec<String>,
     category: Vec<String>,
     tags: Vec<String>,
+    milestone_id: Option<Uuid>,
+    sprint_id: Option<Uuid>,
+    estimate: Option<i32>,
+    /// Display IDs of unfinished issues that block this one
+    open_blockers: Vec<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PmMilestoneRow {
+    id: Uuid,
+    project_id: Uuid,
+    name: String,
+    target_date: Option<NaiveDate>,
+    status: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PmSprintRow {
+    id: Uuid,
+    project_id: Uuid,
+    name: String,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    status: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PmFullReviewResponse {
+    /// `pm_reviews` row this run was saved as
+    id: Uuid,
     generated_at: DateTime<Utc>,
+    /// "heuristic" (synthetic buckets) or "existing" (real milestones/sprints)
+    mode: &'static str,
     horizon_days: i64,
     sprint_length_days: i64,
     period: ReviewPeriod,
@@ -303,6 +643,22 @@ pub struct PmFullReviewResponse {
     summary: PmReviewSummary,
     projects: Vec<ProjectPlan>,
     priority_suggestions: Vec<PrioritySuggestion>,
+    workload: PmWorkload,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PmWorkload {
+    overload_threshold: usize,
+    /// Busiest first
+    assignees: Vec<AssigneeWorkload>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssigneeWorkload {
+    assignee_id: String,
+    open_issue_count: usize,
+    estimate_total: i64,
+    overloaded: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -346,6 +702,10 @@ pub struct ProjectPlan {
 pub struct MilestoneBucket {
     key: String,
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    milestone_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_date: Option<NaiveDate>,
     issue_ids: Vec<String>,
     issues: Vec<PmIssuePlanItem>,
 }
@@ -354,8 +714,10 @@ pub struct MilestoneBucket {
 pub struct SprintBucket {
     key: String,
     name: String,
-    start_date: NaiveDate,
-    end_date: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sprint_id: Option<Uuid>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
     issue_ids: Vec<String>,
     issues: Vec<PmIssuePlanItem>,
 }
@@ -375,6 +737,8 @@ pub struct PmIssuePlanItem {
     assignee_ids: Vec<String>,
     category: Vec<String>,
     tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    blocked_by: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -413,6 +777,10 @@ fn is_backlog_status(status: &str) -> bool {
 }
 
 fn has_blocker_signal(issue: &PmIssueRow) -> bool {
+    if !issue.open_blockers.is_empty() {
+        return true;
+    }
+
     let status = normalize(&issue.status);
     if status == "blocked" || status == "blocker" {
         return true;
@@ -458,7 +826,40 @@ fn milestone_key(issue: &PmIssueRow) -> (&'static str, &'static str) {
     ("milestone_c", "Milestone C — Backlog Acceleration")
 }
 
-fn score_issue_for_priority(issue: &PmIssueRow, today: NaiveDate) -> (i32, String) {
+/// Open issues and summed estimates per assignee, busiest first.
+fn compute_workload(issues: &[PmIssueRow], overload_threshold: usize) -> Vec<AssigneeWorkload> {
+    let mut by_assignee: HashMap<&str, (usize, i64)> = HashMap::new();
+    for issue in issues {
+        for assignee in &issue.assignee_ids {
+            let entry = by_assignee.entry(assignee.as_str()).or_default();
+            entry.0 += 1;
+            entry.1 += issue.estimate.unwrap_or(0) as i64;
+        }
+    }
+
+    let mut workload: Vec<AssigneeWorkload> = by_assignee
+        .into_iter()
+        .map(|(assignee_id, (open_issue_count, estimate_total))| AssigneeWorkload {
+            assignee_id: assignee_id.to_string(),
+            open_issue_count,
+            estimate_total,
+            overloaded: open_issue_count > overload_threshold,
+        })
+        .collect();
+    workload.sort_by(|a, b| {
+        b.open_issue_count
+            .cmp(&a.open_issue_count)
+            .then_with(|| b.estimate_total.cmp(&a.estimate_total))
+            .then_with(|| a.assignee_id.cmp(&b.assignee_id))
+    });
+    workload
+}
+
+fn score_issue_for_priority(
+    issue: &PmIssueRow,
+    today: NaiveDate,
+    overloaded: &HashMap<&str, usize>,
+) -> (i32, String) {
     let mut score = 0;
     let mut reasons: Vec<String> = Vec::new();
 
@@ -509,7 +910,16 @@ fn score_issue_for_priority(issue: &PmIssueRow, today: NaiveDate) -> (i32, Strin
 
     if has_blocker_signal(issue) {
         score += 35;
-        reasons.push("blocker/hotfix signal detected".to_string());
+        if issue.open_blockers.is_empty() {
+            reasons.push("blocker/hotfix signal detected".to_string());
+        } else if priority_rank(issue.priority.as_deref()) <= 1 {
+            reasons.push(format!(
+                "high priority but blocked by unfinished {}",
+                issue.open_blockers.join(", ")
+            ));
+        } else {
+            reasons.push(format!("blocked by {}", issue.open_blockers.join(", ")));
+        }
     }
 
     let age_days = (today - issue.created_at.date_naive()).num_days().max(0);
@@ -523,6 +933,18 @@ fn score_issue_for_priority(issue: &PmIssueRow, today: NaiveDate) -> (i32, Strin
         reasons.push("no assignee".to_string());
     }
 
+    // Nudge rebalancing: work held by an overloaded assignee is at risk
+    let mut flagged = false;
+    for assignee in &issue.assignee_ids {
+        if let Some(count) = overloaded.get(assignee.as_str()) {
+            flagged = true;
+            reasons.push(format!("assignee {} has {} open issues", assignee, count));
+        }
+    }
+    if flagged {
+        score += 8;
+    }
+
     if reasons.is_empty() {
         reasons.push("open issue requiring planning".to_string());
     }
@@ -545,6 +967,7 @@ fn to_plan_item(issue: &PmIssueRow) -> PmIssuePlanItem {
         assignee_ids: issue.assignee_ids.clone(),
         category: issue.category.clone(),
         tags: issue.tags.clone(),
+        blocked_by: issue.open_blockers.clone(),
     }
 }
 
@@ -562,6 +985,100 @@ fn sort_oldest_first(a: &&PmIssueRow, b: &&PmIssueRow) -> std::cmp::Ordering {
         .then_with(|| a.display_id.cmp(&b.display_id))
 }
 
+/// Buckets for `use_existing`: one per real milestone (completed/cancelled
+/// ones only when they still hold open issues), plus "No milestone".
+fn existing_milestone_buckets(
+    project_id: Uuid,
+    issues: &[&PmIssueRow],
+    milestones: &[PmMilestoneRow],
+) -> Vec<MilestoneBucket> {
+    let mut buckets = Vec::new();
+    for milestone in milestones.iter().filter(|m| m.project_id == project_id) {
+        let mut members: Vec<&PmIssueRow> = issues
+            .iter()
+            .copied()
+            .filter(|i| i.milestone_id == Some(milestone.id))
+            .collect();
+        if members.is_empty() && milestone.status != "active" {
+            continue;
+        }
+        members.sort_by(sort_by_priority_then_oldest);
+        buckets.push(MilestoneBucket {
+            key: milestone.id.to_string(),
+            name: milestone.name.clone(),
+            milestone_id: Some(milestone.id),
+            target_date: milestone.target_date,
+            issue_ids: members.iter().map(|i| i.display_id.clone()).collect(),
+            issues: members.iter().map(|i| to_plan_item(i)).collect(),
+        });
+    }
+
+    let known: HashSet<Uuid> = buckets.iter().filter_map(|b| b.milestone_id).collect();
+    let mut unplanned: Vec<&PmIssueRow> = issues
+        .iter()
+        .copied()
+        .filter(|i| i.milestone_id.is_none_or(|id| !known.contains(&id)))
+        .collect();
+    unplanned.sort_by(sort_by_priority_then_oldest);
+    buckets.push(MilestoneBucket {
+        key: "no_milestone".to_string(),
+        name: "No milestone".to_string(),
+        milestone_id: None,
+        target_date: None,
+        issue_ids: unplanned.iter().map(|i| i.display_id.clone()).collect(),
+        issues: unplanned.iter().map(|i| to_plan_item(i)).collect(),
+    });
+    buckets
+}
+
+/// Buckets for `use_existing`: one per real sprint (completed ones only when
+/// they still hold open issues), plus "No sprint".
+fn existing_sprint_buckets(
+    project_id: Uuid,
+    issues: &[&PmIssueRow],
+    sprints: &[PmSprintRow],
+) -> Vec<SprintBucket> {
+    let mut buckets = Vec::new();
+    for sprint in sprints.iter().filter(|s| s.project_id == project_id) {
+        let mut members: Vec<&PmIssueRow> = issues
+            .iter()
+            .copied()
+            .filter(|i| i.sprint_id == Some(sprint.id))
+            .collect();
+        if members.is_empty() && sprint.status == "completed" {
+            continue;
+        }
+        members.sort_by(sort_by_priority_then_oldest);
+        buckets.push(SprintBucket {
+            key: sprint.id.to_string(),
+            name: sprint.name.clone(),
+            sprint_id: Some(sprint.id),
+            start_date: sprint.start_date,
+            end_date: sprint.end_date,
+            issue_ids: members.iter().map(|i| i.display_id.clone()).collect(),
+            issues: members.iter().map(|i| to_plan_item(i)).collect(),
+        });
+    }
+
+    let known: HashSet<Uuid> = buckets.iter().filter_map(|b| b.sprint_id).collect();
+    let mut unscheduled: Vec<&PmIssueRow> = issues
+        .iter()
+        .copied()
+        .filter(|i| i.sprint_id.is_none_or(|id| !known.contains(&id)))
+        .collect();
+    unscheduled.sort_by(sort_by_priority_then_oldest);
+    buckets.push(SprintBucket {
+        key: "no_sprint".to_string(),
+        name: "No sprint".to_string(),
+        sprint_id: None,
+        start_date: None,
+        end_date: None,
+        issue_ids: unscheduled.iter().map(|i| i.display_id.clone()).collect(),
+        issues: unscheduled.iter().map(|i| to_plan_item(i)).collect(),
+    });
+    buckets
+}
+
 pub async fn pm_full_review(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -572,6 +1089,7 @@ pub async fn pm_full_review(
         .as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
+    let request_json = serde_json::to_value(&body).unwrap_or_default();
     let horizon_days = body.horizon_days.unwrap_or(42).clamp(7, 180);
     let sprint_length_days = body.sprint_length_days.unwrap_or(14).clamp(7, 42);
     let selected_project_ids = body.project_ids.and_then(|ids| if ids.is_empty() { None } else { Some(ids) });
@@ -590,10 +1108,10 @@ pub async fn pm_full_review(
     .fetch_all(&pool)
     .await
     .map_err(|e| {
-        (
+        crate::routes::query_timeout_response(&e).unwrap_or_else(|| (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": format!("Failed to list projects: {}", e)})),
-        )
+        ))
     })?;
 
     let issues = sqlx::query_as::<_, PmIssueRow>(
@@ -611,7 +1129,19 @@ pub async fn pm_full_review(
             i.updated_at,
             COALESCE(i.assignee_ids, '{}'::text[]) AS assignee_ids,
             COALESCE(i.category, '{}'::text[]) AS category,
-            COALESCE(i.tags, '{}'::text[]) AS tags
+            COALESCE(i.tags, '{}'::text[]) AS tags,
+            i.milestone_id,
+            i.sprint_id,
+            i.estimate,
+            ARRAY(
+                SELECT b.display_id
+                FROM issue_relations r
+                JOIN issues b ON b.id = r.source_issue_id
+                WHERE r.target_issue_id = i.id
+                  AND r.relation_type = 'blocks'
+                  AND LOWER(b.status) NOT IN ('done', 'cancelled')
+                ORDER BY b.display_id
+            ) AS open_blockers
         FROM issues i
         JOIN projects p ON p.id = i.project_id
         WHERE p.org_id = $1
@@ -625,12 +1155,53 @@ pub async fn pm_full_review(
     .fetch_all(&pool)
     .await
     .map_err(|e| {
-        (
+        crate::routes::query_timeout_response(&e).unwrap_or_else(|| (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": format!("Failed to list open issues: {}", e)})),
-        )
+        ))
     })?;
 
+    let (live_milestones, live_sprints) = if body.use_existing {
+        let project_ids: Vec<Uuid> = projects.iter().map(|p| p.id).collect();
+        let milestones = sqlx::query_as::<_, PmMilestoneRow>(
+            r#"
+            SELECT id, project_id, name, target_date, status
+            FROM milestones
+            WHERE project_id = ANY($1)
+            ORDER BY target_date ASC NULLS LAST, name ASC
+            "#,
+        )
+        .bind(&project_ids)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| {
+            crate::routes::query_timeout_response(&e).unwrap_or_else(|| (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to list milestones: {}", e)})),
+            ))
+        })?;
+        let sprints = sqlx::query_as::<_, PmSprintRow>(
+            r#"
+            SELECT id, project_id, name, start_date, end_date, status
+            FROM sprints
+            WHERE project_id = ANY($1)
+            ORDER BY start_date ASC NULLS LAST, name ASC
+            "#,
+        )
+        .bind(&project_ids)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| {
+            crate::routes::query_timeout_response(&e).unwrap_or_else(|| (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Failed to list sprints: {}", e)})),
+            ))
+        })?;
+        (milestones, sprints)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
     let today = Utc::now().date_naive();
     let horizon_end = today + Duration::days(horizon_days - 1);
 
@@ -672,6 +1243,18 @@ pub async fn pm_full_review(
         let project_issues = issues_by_project.remove(&project.id).unwrap_or_default();
         let issue_refs: Vec<&PmIssueRow> = project_issues.iter().collect();
 
+        if body.use_existing {
+            project_plans.push(ProjectPlan {
+                project_id: project.id,
+                project_name: project.name,
+                project_prefix: project.prefix,
+                open_issue_count: issue_refs.len(),
+                milestones: existing_milestone_buckets(project.id, &issue_refs, &live_milestones),
+                sprints: existing_sprint_buckets(project.id, &issue_refs, &live_sprints),
+            });
+            continue;
+        }
+
         // Milestone buckets
         let mut milestone_a: Vec<&PmIssueRow> = Vec::new();
         let mut milestone_b: Vec<&PmIssueRow> = Vec::new();
@@ -761,18 +1344,24 @@ pub async fn pm_full_review(
             MilestoneBucket {
                 key: "milestone_a".to_string(),
                 name: "Milestone A — Stabilization".to_string(),
+                milestone_id: None,
+                target_date: None,
                 issue_ids: milestone_a.iter().map(|i| i.display_id.clone()).collect(),
                 issues: milestone_a.iter().map(|i| to_plan_item(i)).collect(),
             },
             MilestoneBucket {
                 key: "milestone_b".to_string(),
                 name: "Milestone B — Active Delivery".to_string(),
+                milestone_id: None,
+                target_date: None,
                 issue_ids: milestone_b.iter().map(|i| i.display_id.clone()).collect(),
                 issues: milestone_b.iter().map(|i| to_plan_item(i)).collect(),
             },
             MilestoneBucket {
                 key: "milestone_c".to_string(),
                 name: "Milestone C — Backlog Acceleration".to_string(),
+                milestone_id: None,
+                target_date: None,
                 issue_ids: milestone_c.iter().map(|i| i.display_id.clone()).collect(),
                 issues: milestone_c.iter().map(|i| to_plan_item(i)).collect(),
             },
@@ -786,24 +1375,27 @@ pub async fn pm_full_review(
             SprintBucket {
                 key: s1.key.clone(),
                 name: s1.name.clone(),
-                start_date: s1.start_date,
-                end_date: s1.end_date,
+                sprint_id: None,
+                start_date: Some(s1.start_date),
+                end_date: Some(s1.end_date),
                 issue_ids: sprint1.iter().map(|i| i.display_id.clone()).collect(),
                 issues: sprint1.iter().map(|i| to_plan_item(i)).collect(),
             },
             SprintBucket {
                 key: s2.key.clone(),
                 name: s2.name.clone(),
-                start_date: s2.start_date,
-                end_date: s2.end_date,
+                sprint_id: None,
+                start_date: Some(s2.start_date),
+                end_date: Some(s2.end_date),
                 issue_ids: sprint2.iter().map(|i| i.display_id.clone()).collect(),
                 issues: sprint2.iter().map(|i| to_plan_item(i)).collect(),
             },
             SprintBucket {
                 key: s3.key.clone(),
                 name: s3.name.clone(),
-                start_date: s3.start_date,
-                end_date: s3.end_date,
+                sprint_id: None,
+                start_date: Some(s3.start_date),
+                end_date: Some(s3.end_date),
                 issue_ids: sprint3.iter().map(|i| i.display_id.clone()).collect(),
                 issues: sprint3.iter().map(|i| to_plan_item(i)).collect(),
             },
@@ -819,10 +1411,21 @@ pub async fn pm_full_review(
         });
     }
 
+    let overload_threshold = body
+        .overload_threshold
+        .unwrap_or(DEFAULT_OVERLOAD_THRESHOLD)
+        .clamp(1, 500);
+    let workload = compute_workload(&all_open_issues, overload_threshold);
+    let overloaded: HashMap<&str, usize> = workload
+        .iter()
+        .filter(|w| w.overloaded)
+        .map(|w| (w.assignee_id.as_str(), w.open_issue_count))
+        .collect();
+
     let mut ranked: Vec<RankedSuggestion<'_>> = all_open_issues
         .iter()
         .map(|issue| {
-            let (score, reason) = score_issue_for_priority(issue, today);
+            let (score, reason) = score_issue_for_priority(issue, today, &overloaded);
             RankedSuggestion { score, reason, issue }
         })
         .collect();
@@ -846,17 +1449,19 @@ pub async fn pm_full_review(
         .collect();
 
     let response = PmFullReviewResponse {
+        id: Uuid::new_v4(),
         generated_at: Utc::now(),
+        mode: if body.use_existing { "existing" } else { "heuristic" },
         horizon_days,
         sprint_length_days,
         period: ReviewPeriod {
             start_date: today,
             end_date: horizon_end,
         },
-        sprint_windows,
+        sprint_windows: if body.use_existing { Vec::new() } else { sprint_windows },
         summary: PmReviewSummary {
             project_count: project_plans.len(),
-            open_issue_count: total_milestone_a + total_milestone_b + total_milestone_c,
+            open_issue_count: all_open_issues.len(),
             milestone_a_count: total_milestone_a,
             milestone_b_count: total_milestone_b,
             milestone_c_count: total_milestone_c,
@@ -867,7 +1472,229 @@ pub async fn pm_full_review(
         },
         projects: project_plans,
         priority_suggestions,
+        workload: PmWorkload {
+            overload_threshold,
+            assignees: workload,
+        },
     };
 
+    // Keep the run for historical comparison; a failed insert doesn't fail the review
+    if let Err(e) = sqlx::query(
+        "INSERT INTO pm_reviews (id, org_id, generated_at, request, response) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(response.id)
+    .bind(org_id)
+    .bind(response.generated_at)
+    .bind(&request_json)
+    .bind(serde_json::to_value(&response).unwrap_or_default())
+    .execute(&pool)
+    .await
+    {
+        tracing::warn!(org_id = %org_id, error = %e, "Failed to persist PM review");
+    }
+
     Ok(Json(ApiResponse::new(response)))
 }
+
+// ─── PM Review History ─────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PmReviewListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PmReviewListItem {
+    id: Uuid,
+    generated_at: DateTime<Utc>,
+    request: Value,
+    /// The run's `summary` block (full response via GET /ai/pm-reviews/{id})
+    summary: Option<Value>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PmReviewRecord {
+    id: Uuid,
+    generated_at: DateTime<Utc>,
+    request: Value,
+    response: Value,
+}
+
+/// GET /ai/pm-reviews — past PM review runs for the org, newest first.
+pub async fn list_pm_reviews(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Query(params): Query<PmReviewListParams>,
+) -> Result<Json<ApiResponse<Vec<PmReviewListItem>>>, (StatusCode, Json<Value>)> {
+    let org_id = auth
+        .org_id
+        .as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let reviews = sqlx::query_as::<_, PmReviewListItem>(
+        r#"
+        SELECT id, generated_at, request, response->'summary' AS summary
+        FROM pm_reviews
+        WHERE org_id = $1
+        ORDER BY generated_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(org_id)
+    .bind(params.limit.unwrap_or(20).clamp(1, 100))
+    .bind(params.offset.unwrap_or(0).max(0))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to list PM reviews: {}", e)})),
+        )
+    })?;
+
+    Ok(Json(ApiResponse::new(reviews)))
+}
+
+/// GET /ai/pm-reviews/{id} — one stored run, including the full response.
+pub async fn get_pm_review(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PmReviewRecord>>, (StatusCode, Json<Value>)> {
+    let org_id = auth
+        .org_id
+        .as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let review = sqlx::query_as::<_, PmReviewRecord>(
+        "SELECT id, generated_at, request, response FROM pm_reviews WHERE id = $1 AND org_id = $2",
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to load PM review: {}", e)})),
+        )
+    })?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "PM review not found"}))))?;
+
+    Ok(Json(ApiResponse::new(review)))
+}
+
+// ─── Org AI Usage & Quota ──────────────────────────────
+
+/// Monthly Gemini token budget per org (input + output), from
+/// `AI_ORG_MONTHLY_TOKEN_CAP`. Unset or 0 means no cap.
+fn org_monthly_token_cap() -> Option<i64> {
+    std::env::var("AI_ORG_MONTHLY_TOKEN_CAP")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|cap| *cap > 0)
+}
+
+/// 429 once the org has spent its monthly token budget. Checked on top of
+/// the per-user message quota; fails open if usage can't be read.
+pub(crate) async fn check_org_token_quota(pool: &PgPool, org_id: &str) -> Result<(), (StatusCode, Json<Value>)> {
+    let Some(cap) = org_monthly_token_cap() else {
+        return Ok(());
+    };
+
+    let used: i64 = match sqlx::query_scalar(
+        "SELECT COALESCE(SUM(COALESCE(tokens_in, 0) + COALESCE(tokens_out, 0)), 0)::BIGINT \
+         FROM ai_usage WHERE org_id = $1 AND created_at >= date_trunc('month', now())",
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await
+    {
+        Ok(used) => used,
+        Err(e) => {
+            tracing::warn!(org_id = %org_id, error = %e, "AI org quota check failed (allowing)");
+            return Ok(());
+        }
+    };
+
+    if used >= cap {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "error": "Organization AI token quota exceeded for this month",
+                "code": "ai_org_quota_exceeded",
+                "limit": cap,
+                "current": used,
+            })),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AiUsageDay {
+    day: NaiveDate,
+    request_count: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AiUsageSummary {
+    org_id: String,
+    period_start: NaiveDate,
+    request_count: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+    /// Monthly token cap (input + output); null when uncapped
+    token_cap: Option<i64>,
+    days: Vec<AiUsageDay>,
+}
+
+/// GET /ai/usage — the current org's AI consumption this month, per day.
+pub async fn org_ai_usage(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<AiUsageSummary>>, (StatusCode, Json<Value>)> {
+    let org_id = auth
+        .org_id
+        .as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let days = sqlx::query_as::<_, AiUsageDay>(
+        r#"
+        SELECT
+            created_at::date AS day,
+            COUNT(*) AS request_count,
+            COALESCE(SUM(tokens_in), 0)::BIGINT AS input_tokens,
+            COALESCE(SUM(tokens_out), 0)::BIGINT AS output_tokens
+        FROM ai_usage
+        WHERE org_id = $1 AND created_at >= date_trunc('month', now())
+        GROUP BY day
+        ORDER BY day ASC
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Failed to load AI usage: {}", e)})),
+        )
+    })?;
+
+    let today = Utc::now().date_naive();
+    let summary = AiUsageSummary {
+        org_id: org_id.to_string(),
+        period_start: today.with_day(1).unwrap_or(today),
+        request_count: days.iter().map(|d| d.request_count).sum(),
+        input_tokens: days.iter().map(|d| d.input_tokens).sum(),
+        output_tokens: days.iter().map(|d| d.output_tokens).sum(),
+        token_cap: org_monthly_token_cap(),
+        days,
+    };
+
+    Ok(Json(ApiResponse::new(summary)))
+}
@@ -404,6 +404,8 @@ pub async fn agent_chat(
         ));
     }
 
+    crate::routes::ai::check_org_token_quota(&pool, &org_id).await?;
+
     // Validate request
     if body.message.is_empty() {
         return Err((
@@ -509,7 +511,7 @@ pub async fn agent_chat(
             "parts": [{"text": message}]
         }));
 
-        let client = reqwest::Client::new();
+        let client = crate::util::http::gemini();
         let model = "gemini-2.0-flash";
         let mut total_tokens_in = 0i32;
         let mut total_tokens_out = 0i32;
@@ -76,6 +76,8 @@ pub async fn chat_handler(
         ));
     }
 
+    crate::routes::ai::check_org_token_quota(&pool, &org_id).await?;
+
     // ── Validate messages ──
     if body.messages.is_empty() {
         return Err((
@@ -206,7 +206,7 @@ pub fn build_stream(
         yield sse_chunk(&UIMessageChunk::Start { message_id });
         yield sse_chunk(&UIMessageChunk::StartStep);
 
-        let client = reqwest::Client::new();
+        let client = crate::util::http::gemini();
         // Aligné avec `ai.rs` : preview Gemini 3 Flash ; surcharge via GEMINI_CHAT_MODEL (ex. gemini-2.5-flash).
         let model = std::env::var("GEMINI_CHAT_MODEL")
             .unwrap_or_else(|_| "gemini-3-flash-preview".to_string());
@@ -499,7 +499,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         // ── 21. manage_sla ───────────────────────────────────────────────
         tool(
             "manage_sla",
-            "Manage SLA (Service Level Agreement) rules and monitor compliance for a project. Use action='list_rules' to see current SLA definitions, 'stats' to get compliance metrics, 'create_rule' to set a deadline per priority, 'delete_rule' to remove one.\n\nUse when the user says 'set SLA for urgent bugs to 4 hours', 'show SLA compliance', 'what's our breach rate?', 'list SLA rules for HLM'.\n\nNot for: Automation triggers when SLA is breached (use manage_automations). Updating individual issue fields (use update_issue). Project-wide metrics beyond SLA (use get_project_metrics).\n\nReturns: For 'list_rules': array of { id, priority, deadline_hours }. For 'stats': { total_open, breached_count, compliance_rate }. For mutations: { id, confirmation }.",
+            "Manage SLA (Service Level Agreement) rules and monitor compliance for a project. Use action='list_rules' to see current SLA definitions, 'stats' to get compliance metrics, 'create_rule' to set a deadline per priority, 'delete_rule' to remove one.\n\nUse when the user says 'set SLA for urgent bugs to 4 hours', 'show SLA compliance', 'what's our breach rate?', 'list SLA rules for HLM'.\n\nNot for: Automation triggers when SLA is breached (use manage_automations). Updating individual issue fields (use update_issue). Project-wide metrics beyond SLA (use get_project_metrics).\n\nReturns: For 'list_rules': array of { id, priority, response_minutes, resolution_minutes }. For 'stats': { total_open, breached_count, compliance_rate }. For mutations: { id, confirmation }.",
             json!({
                 "type": "OBJECT",
                 "properties": {
@@ -515,7 +515,8 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                         "enum": ["urgent", "high", "medium", "low"],
                         "description": "Priority tier for the SLA rule. Required for create_rule. One rule per priority per project."
                     },
-                    "deadline_hours": {"type": "NUMBER", "description": "Maximum resolution time in hours. Required for create_rule. Example: 4 for urgent, 24 for high."}
+                    "deadline_hours": {"type": "NUMBER", "description": "Maximum resolution time in hours. Required for create_rule. Example: 4 for urgent, 24 for high."},
+                    "response_hours": {"type": "NUMBER", "description": "Optional time to first response in hours for create_rule. Example: 1 for urgent."}
                 },
                 "required": ["action", "project_id"]
             }),
@@ -2965,30 +2966,30 @@ async fn create_issue_real(
         .unwrap_or_default();
 
     // Verify project belongs to org + get prefix
-    let row: Option<(String,)> = sqlx::query_as(
-        "SELECT prefix FROM projects WHERE id = $1 AND org_id = ANY($2::text[])",
+    let row: Option<(String, bool)> = sqlx::query_as(
+        "SELECT prefix, archived_at IS NOT NULL FROM projects WHERE id = $1 AND org_id = ANY($2::text[])",
     )
     .bind(project_id)
     .bind(org_ids)
     .fetch_optional(pool)
     .await
     .map_err(|e| format!("DB error: {}", e))?;
-    let (prefix,) = row.ok_or_else(|| format!(
+    let (prefix, archived) = row.ok_or_else(|| format!(
         "Project '{}' not found or you don't have access. Double-check the project UUID or prefix.",
         project_id_str
     ))?;
+    if archived {
+        return Err(format!(
+            "Project '{}' is archived and does not accept new issues. Unarchive it first or pick another project.",
+            project_id_str
+        ));
+    }
 
-    // Generate display_id: MAX existing number + 1
-    let (next_num,): (i64,) = sqlx::query_as(
-        r#"SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
-           FROM issues WHERE project_id = $1 AND display_id ~ ('^' || $2 || '-[0-9]+$')"#,
-    )
-    .bind(project_id)
-    .bind(&prefix)
-    .fetch_one(pool)
-    .await
-    .unwrap_or((1i64,));
-    let display_id = format!("{}-{}", prefix, next_num);
+    // Allocate the display_id from the project counter, in the insert's transaction
+    let mut tx = pool.begin().await.map_err(|e| format!("DB error: {}", e))?;
+    let display_id = crate::routes::issues::next_display_id(&mut *tx, project_id, &prefix)
+        .await
+        .map_err(|(_, body)| format!("Failed to allocate issue number: {}", body.0))?;
 
     // Max position in backlog lane
     let max_pos: Option<f64> = sqlx::query_scalar::<_, Option<f64>>(
@@ -3019,9 +3020,10 @@ async fn create_issue_real(
         .bind(position)
         .bind(user_id)
         .bind(user_display_name)
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| format!("Failed to create issue: {}", e))?;
+    tx.commit().await.map_err(|e| format!("Failed to create issue: {}", e))?;
 
     let priority_str = pri.as_deref().unwrap_or("none");
     let category_str = if category.is_empty() { "none".to_string() } else { category.join(", ") };
@@ -3046,6 +3048,31 @@ async fn create_issue_real(
     })
 }
 
+/// Reject a status that isn't one of the issue's project status keys, so tool
+/// calls can't create phantom board columns. Missing issues pass through.
+async fn check_project_status(pool: &PgPool, issue_id: Uuid, status: &str) -> Result<(), String> {
+    let statuses: Option<Value> = sqlx::query_scalar(
+        "SELECT p.statuses FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1",
+    )
+    .bind(issue_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("DB error: {}", e))?;
+    let Some(statuses) = statuses else { return Ok(()) };
+
+    let keys: Vec<&str> = statuses.as_array().map(|a| {
+        a.iter().filter_map(|s| s.get("key").and_then(|k| k.as_str())).collect()
+    }).unwrap_or_default();
+    if keys.contains(&status) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid status '{}' for this project. Accepted values: {}",
+            status, keys.join(", ")
+        ))
+    }
+}
+
 async fn update_issue_real(
     pool: &PgPool,
     org_ids: &[String],
@@ -3080,6 +3107,9 @@ async fn update_issue_real(
     let new_title    = args.get("title").and_then(|v| v.as_str());
     let new_desc     = args.get("description").and_then(|v| v.as_str());
     let new_status   = args.get("status").and_then(|v| v.as_str());
+    if let Some(status) = new_status {
+        check_project_status(pool, issue_id, status).await?;
+    }
     let new_priority = args.get("priority").and_then(|v| v.as_str());
     let new_tags: Option<Vec<String>> = args.get("tags").and_then(|v| v.as_array())
         .map(|a| a.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect());
@@ -3177,6 +3207,7 @@ async fn bulk_update_issues_real(
 
     let mut updated_count: usize = 0;
     let mut result_issues: Vec<Value> = Vec::new();
+    let mut rejected: Vec<Value> = Vec::new();
 
     for item in updates {
         let issue_id: Uuid = match item.get("issue_id").and_then(|v| v.as_str()) {
@@ -3194,6 +3225,13 @@ async fn bulk_update_issues_real(
         let new_category: Option<Vec<String>> = item.get("category").and_then(|v| v.as_array())
             .map(|a| a.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect());
 
+        if let Some(status) = new_status {
+            if let Err(e) = check_project_status(pool, issue_id, status).await {
+                rejected.push(json!({ "issue_id": issue_id.to_string(), "error": e }));
+                continue;
+            }
+        }
+
         // Embedded org check in UPDATE — skip if issue doesn't belong to org
         let result: Option<(String, String, Option<String>)> = sqlx::query_as(
             r#"UPDATE issues SET
@@ -3234,8 +3272,18 @@ async fn bulk_update_issues_real(
         data: json!({
             "updated_count": updated_count,
             "issues": result_issues,
+            "rejected": rejected,
         }),
-        for_model: format!("✅ Bulk updated {} issues.", updated_count),
+        for_model: if rejected.is_empty() {
+            format!("✅ Bulk updated {} issues.", updated_count)
+        } else {
+            format!(
+                "✅ Bulk updated {} issues. {} rejected: {}",
+                updated_count,
+                rejected.len(),
+                rejected.iter().filter_map(|r| r["error"].as_str()).collect::<Vec<_>>().join("; ")
+            )
+        },
         component_hint: None,
         summary: format!("Bulk updated {} issues", updated_count),
     })
@@ -4131,8 +4179,8 @@ async fn ai_manage_sla(pool: &PgPool, org_ids: &[String], args: &Value) -> Resul
     match action {
         "list_rules" => {
             let rows = sqlx::query(
-                "SELECT id::text, priority, deadline_hours
-                 FROM sla_rules
+                "SELECT id::text, priority, response_minutes, resolution_minutes
+                 FROM sla_policies
                  WHERE project_id = $1 AND org_id = ANY($2::text[])
                  ORDER BY CASE priority
                    WHEN 'urgent' THEN 1 WHEN 'high' THEN 2
@@ -4147,7 +4195,8 @@ async fn ai_manage_sla(pool: &PgPool, org_ids: &[String], args: &Value) -> Resul
             let rules: Vec<Value> = rows.iter().map(|r| json!({
                 "id":             r.get::<String, _>("id"),
                 "priority":       r.get::<String, _>("priority"),
-                "deadline_hours": r.get::<i32, _>("deadline_hours"),
+                "response_minutes":   r.get::<Option<i32>, _>("response_minutes"),
+                "resolution_minutes": r.get::<Option<i32>, _>("resolution_minutes"),
             })).collect();
 
             Ok(ToolResult {
@@ -4202,17 +4251,20 @@ async fn ai_manage_sla(pool: &PgPool, org_ids: &[String], args: &Value) -> Resul
                 .ok_or_else(|| "priority is required".to_string())?;
             let deadline_hours = args.get("deadline_hours").and_then(|v| v.as_i64())
                 .ok_or_else(|| "deadline_hours is required".to_string())? as i32;
+            let response_hours = args.get("response_hours").and_then(|v| v.as_i64()).map(|h| h as i32);
 
             let new_id: String = sqlx::query(
-                "INSERT INTO sla_rules (project_id, org_id, priority, deadline_hours)
-                 VALUES ($1, (SELECT org_id FROM projects WHERE id = $1), $2, $3)
+                "INSERT INTO sla_policies (project_id, org_id, priority, response_minutes, resolution_minutes)
+                 VALUES ($1, (SELECT org_id FROM projects WHERE id = $1), $2, $3, $4)
                  ON CONFLICT (project_id, priority)
-                 DO UPDATE SET deadline_hours = EXCLUDED.deadline_hours
+                 DO UPDATE SET response_minutes = EXCLUDED.response_minutes,
+                               resolution_minutes = EXCLUDED.resolution_minutes
                  RETURNING id::text"
             )
             .bind(project_id)
             .bind(priority)
-            .bind(deadline_hours)
+            .bind(response_hours.map(|h| h * 60))
+            .bind(deadline_hours * 60)
             .fetch_one(pool)
             .await
             .map_err(|e| e.to_string())?
@@ -4233,7 +4285,7 @@ async fn ai_manage_sla(pool: &PgPool, org_ids: &[String], args: &Value) -> Resul
                 .map_err(|_| "Invalid rule_id".to_string())?;
 
             sqlx::query(
-                "DELETE FROM sla_rules WHERE id = $1 AND project_id = $2 AND org_id = ANY($3::text[])"
+                "DELETE FROM sla_policies WHERE id = $1 AND project_id = $2 AND org_id = ANY($3::text[])"
             )
             .bind(rule_uuid)
             .bind(project_id)
@@ -1,4 +1,4 @@
-use axum::{extract::{Extension, Path, State}, http::StatusCode, Json};
+use axum::{extract::{Extension, Multipart, Path, State}, http::StatusCode, Json};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::{FromRow, PgPool};
@@ -6,6 +6,8 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::ApiResponse;
+use crate::routes::issues::require_user_org_scope;
+use crate::storage::StorageClient;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Attachment {
@@ -21,12 +23,59 @@ pub struct Attachment {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct CreateAttachment {
-    pub filename: String,
-    pub content_type: Option<String>,
-    pub size_bytes: Option<i64>,
-    pub storage_url: Option<String>,
+/// Largest accepted upload. The route's body limit is set slightly above this
+/// to leave room for the multipart framing.
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+    "text/plain",
+    "text/markdown",
+    "text/csv",
+    "application/json",
+    "application/zip",
+    "video/mp4",
+];
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+fn bad_request(msg: &str) -> ApiError {
+    (StatusCode::BAD_REQUEST, Json(json!({"error": msg})))
+}
+
+/// Keep object keys URL-safe so they can be signed without re-encoding.
+fn sanitize_filename(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    let cleaned = cleaned.trim_matches('.');
+    if cleaned.is_empty() { "file".to_string() } else { cleaned.chars().take(100).collect() }
+}
+
+/// (project_id, org_id) of an issue the caller can see, or 404.
+async fn scoped_issue(pool: &PgPool, auth: &AuthUser, issue_id: Uuid) -> Result<(Uuid, String), ApiError> {
+    let (_current_org_id, org_ids) = require_user_org_scope(pool, auth).await?;
+    sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT i.project_id, p.org_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = ANY($2)"
+    )
+    .bind(issue_id)
+    .bind(&org_ids)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))))
+}
+
+fn storage_or_503(storage: Option<StorageClient>) -> Result<StorageClient, ApiError> {
+    storage.ok_or_else(|| (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({"error": "Attachment storage is not configured", "code": "storage_not_configured"})),
+    ))
 }
 
 /// GET /issues/{id}/attachments — list attachments
@@ -50,66 +99,157 @@ pub async fn list(
     Ok(Json(ApiResponse::new(attachments)))
 }
 
-/// POST /issues/{id}/attachments — register attachment metadata
+/// POST /issues/{id}/attachments — multipart upload (`file` field). Stores the
+/// object in S3-compatible storage, records it in `attachments` and appends
+/// `{ id, filename, url, size, content_type }` to the issue's `attachments`.
 pub async fn create(
     Extension(auth): Extension<AuthUser>,
+    Extension(storage): Extension<Option<StorageClient>>,
     State(pool): State<PgPool>,
     Path(issue_id): Path<Uuid>,
-    Json(body): Json<CreateAttachment>,
-) -> Result<Json<ApiResponse<Attachment>>, (StatusCode, Json<serde_json::Value>)> {
-    let org_id = auth.org_id.as_deref()
-        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<Attachment>>, ApiError> {
+    let (project_id, org_id) = scoped_issue(&pool, &auth, issue_id).await?;
+    let storage = storage_or_503(storage)?;
 
-    // Get project_id from issue
-    let project_id: (Uuid,) = sqlx::query_as(
-        "SELECT project_id FROM issues WHERE id = $1 AND org_id = $2"
-    )
-    .bind(issue_id)
-    .bind(org_id)
-    .fetch_optional(&pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
-    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))))?;
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| bad_request(&e.body_text()))? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let filename = field.file_name().unwrap_or("file").to_string();
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_ascii_lowercase();
+        let bytes = field.bytes().await.map_err(|e| {
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({"error": format!("Upload failed: {}", e.body_text()), "max_bytes": MAX_UPLOAD_BYTES})),
+            )
+        })?;
+        file = Some((filename, content_type, bytes));
+        break;
+    }
+    let (filename, content_type, bytes) = file.ok_or_else(|| bad_request("Missing multipart field 'file'"))?;
+
+    if bytes.is_empty() {
+        return Err(bad_request("File is empty"));
+    }
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({"error": "File is too large", "max_bytes": MAX_UPLOAD_BYTES})),
+        ));
+    }
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({"error": format!("Content type '{}' is not allowed", content_type), "allowed": ALLOWED_CONTENT_TYPES})),
+        ));
+    }
+
+    let id = Uuid::new_v4();
+    let size = bytes.len() as i64;
+    let key = format!("{}/{}/{}-{}", org_id, issue_id, id, sanitize_filename(&filename));
+    let url = storage.put(&key, &content_type, bytes.to_vec()).await.map_err(|e| {
+        tracing::error!(error = %e, "attachments.upload_failed");
+        (StatusCode::BAD_GATEWAY, Json(json!({"error": "Failed to store attachment"})))
+    })?;
+
+    let mut tx = pool.begin().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
     let attachment = sqlx::query_as::<_, Attachment>(
-        r#"INSERT INTO attachments (issue_id, project_id, org_id, filename, content_type, size_bytes, storage_url, uploaded_by)
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        r#"INSERT INTO attachments (id, issue_id, project_id, org_id, filename, content_type, size_bytes, storage_url, uploaded_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
            RETURNING *"#,
     )
+    .bind(id)
     .bind(issue_id)
-    .bind(project_id.0)
-    .bind(org_id)
-    .bind(&body.filename)
-    .bind(&body.content_type)
-    .bind(body.size_bytes)
-    .bind(&body.storage_url)
+    .bind(project_id)
+    .bind(&org_id)
+    .bind(&filename)
+    .bind(&content_type)
+    .bind(size)
+    .bind(&url)
     .bind(&auth.user_id)
-    .fetch_one(&pool)
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let entry = json!({
+        "id": id,
+        "filename": filename,
+        "url": url,
+        "size": size,
+        "content_type": content_type,
+    });
+    sqlx::query(
+        "UPDATE issues SET attachments = COALESCE(attachments, '[]'::jsonb) || jsonb_build_array($1::jsonb), updated_at = now() WHERE id = $2"
+    )
+    .bind(&entry)
+    .bind(issue_id)
+    .execute(tx.as_mut())
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
+    tx.commit().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
     Ok(Json(ApiResponse::new(attachment)))
 }
 
-/// DELETE /issues/{id}/attachments/{att_id}
+/// DELETE /issues/{id}/attachments/{att_id} — drops the record, the entry in
+/// the issue's `attachments` and (best effort) the stored object.
 pub async fn remove(
     Extension(auth): Extension<AuthUser>,
+    Extension(storage): Extension<Option<StorageClient>>,
     State(pool): State<PgPool>,
     Path((issue_id, att_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    let org_id = auth.org_id.as_deref()
-        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (_project_id, org_id) = scoped_issue(&pool, &auth, issue_id).await?;
 
-    let result = sqlx::query("DELETE FROM attachments WHERE id = $1 AND issue_id = $2 AND org_id = $3")
-        .bind(att_id)
-        .bind(issue_id)
-        .bind(org_id)
-        .execute(&pool)
-        .await
+    let mut tx = pool.begin().await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
-    if result.rows_affected() == 0 {
+    let storage_url: Option<Option<String>> = sqlx::query_scalar(
+        "DELETE FROM attachments WHERE id = $1 AND issue_id = $2 AND org_id = $3 RETURNING storage_url"
+    )
+    .bind(att_id)
+    .bind(issue_id)
+    .bind(&org_id)
+    .fetch_optional(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let Some(storage_url) = storage_url else {
         return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Attachment not found"}))));
+    };
+
+    sqlx::query(
+        r#"UPDATE issues SET attachments = COALESCE(
+               (SELECT jsonb_agg(a) FROM jsonb_array_elements(attachments) a WHERE a->>'id' IS DISTINCT FROM $1),
+               '[]'::jsonb
+           ), updated_at = now()
+           WHERE id = $2"#,
+    )
+    .bind(att_id.to_string())
+    .bind(issue_id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    tx.commit().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    // Orphaned objects are harmless; don't fail the request over them
+    if let (Some(storage), Some(url)) = (storage, storage_url) {
+        if let Some(key) = storage.key_for_url(&url) {
+            if let Err(e) = storage.delete(key).await {
+                tracing::warn!(error = %e, attachment_id = %att_id, "attachments.object_delete_failed");
+            }
+        }
     }
 
     Ok(Json(json!({"deleted": true})))
@@ -0,0 +1,116 @@
+use axum::{extract::{Query, State}, http::{HeaderMap, StatusCode}, Extension, Json};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::middleware::rate_limit::client_ip;
+use crate::middleware::roles::{require_role, ROLE_ADMIN};
+use crate::middleware::scopes::has_scope;
+use crate::middleware::AuthUser;
+use crate::models::ApiResponse;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub org_id: String,
+    pub actor_id: String,
+    pub actor_name: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub details: serde_json::Value,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditParams {
+    pub action: Option<String>,
+    /// Inclusive start date (YYYY-MM-DD)
+    pub from: Option<NaiveDate>,
+    /// Inclusive end date (YYYY-MM-DD)
+    pub to: Option<NaiveDate>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Record a sensitive action for the caller's org. Failures are logged, never
+/// surfaced: the action itself already happened.
+pub async fn record(
+    pool: &PgPool,
+    auth: &AuthUser,
+    headers: &HeaderMap,
+    action: &str,
+    target_type: &str,
+    target_id: &str,
+    details: serde_json::Value,
+) {
+    let Some(org_id) = auth.org_id.as_deref() else { return };
+    let actor_name = auth.display_name.as_deref().or(auth.email.as_deref());
+
+    let result = sqlx::query(
+        r#"INSERT INTO audit_log (org_id, actor_id, actor_name, action, target_type, target_id, details, ip_address)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#
+    )
+    .bind(org_id)
+    .bind(&auth.user_id)
+    .bind(actor_name)
+    .bind(action)
+    .bind(target_type)
+    .bind(target_id)
+    .bind(&details)
+    .bind(client_ip(headers))
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!(error = %e, action, target_id, "audit_log insert failed");
+    }
+}
+
+/// GET /audit?action=api_key.created&from=2026-01-01&to=2026-01-31 — org admins only.
+pub async fn list(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Query(params): Query<AuditParams>,
+) -> Result<Json<ApiResponse<Vec<AuditEntry>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    match auth.api_key_permissions {
+        Some(ref permissions) if !has_scope(permissions, "admin:full") => {
+            return Err((StatusCode::FORBIDDEN, Json(json!({
+                "error": "API key is missing the 'admin:full' permission",
+                "code": "insufficient_scope",
+                "required": "admin:full"
+            }))));
+        }
+        Some(_) => {}
+        None => require_role(&auth, ROLE_ADMIN)?,
+    }
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let entries = sqlx::query_as::<_, AuditEntry>(
+        r#"SELECT * FROM audit_log
+           WHERE org_id = $1
+             AND ($2::text IS NULL OR action = $2)
+             AND ($3::date IS NULL OR created_at >= $3::date)
+             AND ($4::date IS NULL OR created_at < $4::date + 1)
+           ORDER BY created_at DESC
+           LIMIT $5 OFFSET $6"#
+    )
+    .bind(org_id)
+    .bind(&params.action)
+    .bind(params.from)
+    .bind(params.to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(entries)))
+}
@@ -0,0 +1,183 @@
+use axum::{extract::{Path, State}, http::StatusCode, Extension, Json};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{ApiResponse, CreateProjectCategory, ProjectCategory};
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct CategoryUsage {
+    pub name: String,
+    pub color: Option<String>,
+    /// False for values used on issues but missing from `project_categories`
+    pub defined: bool,
+    /// Non-archived issues carrying the category
+    pub issue_count: i64,
+}
+
+async fn project_in_org(pool: &PgPool, project_id: Uuid, org_id: &str) -> bool {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)")
+        .bind(project_id)
+        .bind(org_id)
+        .fetch_one(pool)
+        .await
+        .unwrap_or(false)
+}
+
+/// Category names from `categories` that the project doesn't define, when the
+/// project has `validate_categories` on. Empty when validation is off.
+pub async fn undefined_categories(
+    pool: &PgPool,
+    project_id: Uuid,
+    org_id: &str,
+    categories: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    if categories.is_empty() {
+        return Ok(vec![]);
+    }
+    sqlx::query_scalar(
+        r#"
+        SELECT c FROM unnest($3::text[]) AS c
+        WHERE EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2 AND validate_categories)
+          AND NOT EXISTS(SELECT 1 FROM project_categories pc WHERE pc.project_id = $1 AND pc.name = c)
+        "#,
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .bind(categories)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn list_by_project(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ProjectCategory>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    if !project_in_org(&pool, project_id, org_id).await {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let categories = sqlx::query_as::<_, ProjectCategory>(
+        "SELECT * FROM project_categories WHERE project_id = $1 ORDER BY name ASC",
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::error!(error = %e, "categories.list query failed");
+        vec![]
+    });
+
+    Ok(Json(ApiResponse::new(categories)))
+}
+
+pub async fn create(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<CreateProjectCategory>,
+) -> Result<Json<ApiResponse<ProjectCategory>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({"error": "Category name is required", "field": "name"}))));
+    }
+
+    if !project_in_org(&pool, project_id, org_id).await {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let color = body.color.as_deref().unwrap_or("#6b7280");
+
+    let category = sqlx::query_as::<_, ProjectCategory>(
+        r#"
+        INSERT INTO project_categories (project_id, name, color, description)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (project_id, name) DO UPDATE
+            SET color = EXCLUDED.color,
+                description = EXCLUDED.description
+        RETURNING *
+        "#,
+    )
+    .bind(project_id)
+    .bind(name)
+    .bind(color)
+    .bind(&body.description)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(category)))
+}
+
+pub async fn remove(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(category_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    // Existing issues keep the value; only the definition goes away.
+    let result = sqlx::query(
+        "DELETE FROM project_categories WHERE id = $1 AND project_id IN (SELECT id FROM projects WHERE org_id = $2)"
+    )
+    .bind(category_id)
+    .bind(org_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    if result.rows_affected() > 0 {
+        Ok(Json(ApiResponse::new(())))
+    } else {
+        Err((StatusCode::NOT_FOUND, Json(json!({"error": "Category not found"}))))
+    }
+}
+
+/// GET /projects/{id}/categories/usage — every defined or in-use category with
+/// its issue count, most used first.
+pub async fn usage(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<CategoryUsage>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    if !project_in_org(&pool, project_id, org_id).await {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let usage = sqlx::query_as::<_, CategoryUsage>(
+        r#"
+        WITH used AS (
+            SELECT c AS name, COUNT(*) AS issue_count
+            FROM issues i, unnest(i.category) AS c
+            WHERE i.project_id = $1 AND i.archived = false
+            GROUP BY c
+        )
+        SELECT COALESCE(pc.name, u.name) AS name,
+               pc.color,
+               pc.id IS NOT NULL AS defined,
+               COALESCE(u.issue_count, 0) AS issue_count
+        FROM (SELECT * FROM project_categories WHERE project_id = $1) pc
+        FULL OUTER JOIN used u ON u.name = pc.name
+        ORDER BY issue_count DESC, name ASC
+        "#,
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(usage)))
+}
@@ -6,9 +6,10 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{ApiResponse, Comment};
+use crate::novu::GroupMention;
 use crate::routes::activity::log_activity;
 use crate::routes::notifications::create_notification;
-use crate::routes::sse::{EventSender, broadcast_event};
+use crate::routes::sse::{EventSender, BaatonEvent, CommentEnvelope, broadcast_event};
 use crate::routes::webhooks::dispatch_event;
 
 #[derive(Debug, Deserialize)]
@@ -106,6 +107,15 @@ pub async fn create(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
+    // A comment from anyone but the reporter counts as the SLA response
+    let _ = sqlx::query(
+        "UPDATE issues SET sla_responded_at = now() WHERE id = $1 AND sla_responded_at IS NULL AND created_by_id IS DISTINCT FROM $2"
+    )
+    .bind(issue_id)
+    .bind(&author_id)
+    .execute(&pool)
+    .await;
+
     // ── Gamification: award XP for comment (fire-and-forget) ──
     {
         let pool2 = pool.clone();
@@ -120,19 +130,23 @@ pub async fn create(
     if let Some(ref novu) = novu {
         let novu = novu.clone();
         let pool = pool.clone();
+        let org_id = org_id.to_string();
         let commenter_id = author_id.clone();
         let commenter_name = author_name.clone();
         let comment_body = body.body.clone();
+        let is_org_admin = auth.org_role.as_deref()
+            .map(|r| r.contains("admin"))
+            .unwrap_or(false);
 
         tokio::spawn(async move {
-            let issue = sqlx::query_as::<_, (String, String, Vec<String>)>(
-                "SELECT display_id, title, assignee_ids FROM issues WHERE id = $1",
+            let issue = sqlx::query_as::<_, (String, String, Vec<String>, Uuid)>(
+                "SELECT display_id, title, assignee_ids, project_id FROM issues WHERE id = $1",
             )
             .bind(issue_id)
             .fetch_optional(&pool)
             .await;
 
-            let (display_id, title, assignee_ids) = match issue {
+            let (display_id, title, assignee_ids, project_id) = match issue {
                 Ok(Some(row)) => row,
                 _ => return,
             };
@@ -151,10 +165,7 @@ pub async fn create(
                 .collect();
 
             if !assignees.is_empty() {
-                let subs: Vec<crate::novu::Subscriber> = assignees
-                    .into_iter()
-                    .map(|id| crate::novu::Subscriber { id, email: None, name: None })
-                    .collect();
+                let subs = crate::novu::subscribers_for(&pool, &org_id, "comment-on-assigned-issue", assignees).await;
                 novu.trigger_many(
                     "comment-on-assigned-issue",
                     subs,
@@ -167,18 +178,36 @@ pub async fn create(
                 );
             }
 
-            // Notify @mentioned users (exclude commenter)
-            let mentioned = crate::novu::parse_mentions(&comment_body);
-            let mentioned: Vec<String> = mentioned
-                .into_iter()
-                .filter(|id| *id != commenter_id)
-                .collect();
+            // Notify @mentioned users, expanding @project/@here/@everyone (exclude commenter)
+            let mut group_members: Vec<String> = Vec::new();
+            for group in crate::novu::parse_group_mentions(&comment_body) {
+                match group {
+                    GroupMention::Project => {
+                        match crate::routes::project_members::roster(&pool, &org_id, project_id).await {
+                            Ok(roster) => group_members.extend(roster),
+                            Err(_) => tracing::warn!(issue_id = %issue_id, "comment.group_mention: roster lookup failed"),
+                        }
+                    }
+                    GroupMention::Everyone if is_org_admin => {
+                        group_members.extend(
+                            crate::routes::admin::fetch_org_members(&org_id).await
+                                .iter()
+                                .filter_map(|m| m.get("user_id").and_then(|u| u.as_str()).map(String::from)),
+                        );
+                    }
+                    GroupMention::Everyone => {
+                        tracing::info!(issue_id = %issue_id, user_id = %commenter_id, "comment.group_mention: @everyone needs an org admin, ignored");
+                    }
+                }
+            }
+            let mentioned = crate::novu::merge_mentions(
+                crate::novu::parse_mentions(&comment_body),
+                group_members,
+                &commenter_id,
+            );
 
             if !mentioned.is_empty() {
-                let subs: Vec<crate::novu::Subscriber> = mentioned
-                    .into_iter()
-                    .map(|id| crate::novu::Subscriber { id, email: None, name: None })
-                    .collect();
+                let subs = crate::novu::subscribers_for(&pool, &org_id, "mentioned-in-comment", mentioned).await;
                 novu.trigger_many(
                     "mentioned-in-comment",
                     subs,
@@ -271,7 +300,11 @@ pub async fn create(
     dispatch_event(pool.clone(), org_id.to_string(), "comment.created", serde_json::to_value(&comment).unwrap_or_default()).await;
 
     // ── SSE broadcast ────────────────────────────────
-    broadcast_event(&sse_tx, org_id, "comment.created", &serde_json::to_string(&comment).unwrap_or_default());
+    broadcast_event(&sse_tx, org_id, &BaatonEvent::CommentAdded(CommentEnvelope {
+        issue_id: comment.issue_id,
+        comment_id: comment.id,
+        author_name: Some(comment.author_name.clone()),
+    }));
 
     // AI-first: action hints
     let hints = vec![
@@ -326,7 +359,11 @@ pub async fn remove(
     dispatch_event(pool.clone(), org_id.to_string(), "comment.deleted", serde_json::json!({"id": comment_id.to_string(), "issue_id": issue_id.to_string()})).await;
 
     // ── SSE broadcast ────────────────────────────────
-    broadcast_event(&sse_tx, org_id, "comment.deleted", &format!(r#"{{"id":"{}","issue_id":"{}"}}"#, comment_id, issue_id));
+    broadcast_event(&sse_tx, org_id, &BaatonEvent::CommentDeleted(CommentEnvelope {
+        issue_id,
+        comment_id,
+        author_name: None,
+    }));
 
     Ok(Json(ApiResponse::new(())))
 }
@@ -41,7 +41,9 @@ struct ClerkOrgResponse {
     image_url: Option<String>,
 }
 
-async fn fetch_org_metadata(org_id: &str) -> Option<(String, String, Option<String>)> {
+/// Org `(name, slug, image_url)` from the Clerk Backend API, cached for
+/// `ORG_CACHE_TTL`. None when `CLERK_SECRET_KEY` is unset or Clerk fails.
+pub(crate) async fn fetch_org_metadata(org_id: &str) -> Option<(String, String, Option<String>)> {
     // Check cache first
     {
         let cache = org_cache().read().await;
@@ -59,7 +61,7 @@ async fn fetch_org_metadata(org_id: &str) -> Option<(String, String, Option<Stri
     let secret = std::env::var("CLERK_SECRET_KEY").ok()?;
     let url = format!("https://api.clerk.com/v1/organizations/{}", org_id);
 
-    let resp = reqwest::Client::new()
+    let resp = crate::util::http::clerk()
         .get(&url)
         .bearer_auth(&secret)
         .send()
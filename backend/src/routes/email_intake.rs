@@ -1,11 +1,22 @@
-use axum::{extract::{Path, State}, http::StatusCode, Json};
+use axum::{
+    extract::{FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    Extension, Json,
+};
 use serde::Deserialize;
 use serde_json::json;
 use sqlx::PgPool;
-use uuid::Uuid;
 
-/// POST /api/v1/public/{slug}/email-intake — Create issue from email
-/// Public endpoint but requires matching project with email_intake_enabled
+use crate::models::{ApiResponse, Issue};
+use crate::routes::issues::{submit_public_issue, PublicSource, PublicSubmission, PublicSubmitQuery};
+use crate::routes::sse::EventSender;
+
+/// Longest title taken from a subject line or first body line.
+const EMAIL_TITLE_MAX_CHARS: usize = 200;
+/// Matches the public form's description limit.
+const EMAIL_BODY_MAX_BYTES: usize = 10_000;
+
+/// Legacy JSON payload of `/public/{slug}/email-intake`.
 #[derive(Debug, Deserialize)]
 pub struct EmailPayload {
     pub from_name: Option<String>,
@@ -16,70 +27,228 @@ pub struct EmailPayload {
     pub html: Option<String>,
 }
 
+/// POST /api/v1/public/{slug}/email-intake — legacy email intake, kept for
+/// existing webhooks. Goes through the same public submit path as
+/// `/submit/email` and keeps the old `{ issue_id, display_id, title }` reply.
 pub async fn intake(
+    Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
     Path(slug): Path<String>,
+    Query(query): Query<PublicSubmitQuery>,
+    headers: HeaderMap,
     Json(body): Json<EmailPayload>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    // Find project by slug
-    let project = sqlx::query_as::<_, (Uuid, String, String, i64)>(
-        r#"SELECT id, org_id, prefix, 
-           (SELECT COALESCE(MAX(issue_number), 0) FROM issues WHERE project_id = projects.id)
-           FROM projects WHERE slug = $1"#,
-    )
-    .bind(&slug)
-    .fetch_optional(&pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
-    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))))?;
-
-    let (project_id, org_id, prefix, max_num) = project;
-
-    // Generate title from subject or body
-    let title = body.subject
-        .as_deref()
-        .filter(|s| !s.is_empty() && !s.starts_with("Re:") && !s.starts_with("Fwd:"))
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| {
-            let text = body.body.as_deref().unwrap_or("No content");
-            let first_line = text.lines().next().unwrap_or("Email issue");
-            if first_line.len() > 100 {
-                format!("{}...", &first_line[..97])
-            } else {
-                first_line.to_string()
-            }
-        });
-
-    // Use body text for description, append sender info
-    let mut desc = body.body.clone().unwrap_or_default();
-    if let Some(name) = &body.from_name {
-        desc = format!("**From:** {} {}\n\n{}", name, body.from_email.as_deref().unwrap_or(""), desc);
-    }
+    let email = InboundEmail {
+        from: body.from_email,
+        from_name: body.from_name,
+        subject: body.subject,
+        text: body.body,
+    };
+    let submission = email_submission(&email).ok_or_else(|| {
+        (StatusCode::BAD_REQUEST, Json(json!({"error": "Email has no subject or body", "field": "subject"})))
+    })?;
 
-    let issue_number = max_num + 1;
-    let display_id = format!("{}-{}", prefix, issue_number);
-
-    // Create issue
-    let issue_id: (Uuid,) = sqlx::query_as(
-        r#"INSERT INTO issues (project_id, org_id, title, description, status, priority, issue_type, issue_number, display_id, source)
-           VALUES ($1, $2, $3, $4, 'backlog', 'medium', 'question', $5, $6, 'email')
-           RETURNING id"#,
-    )
-    .bind(project_id)
-    .bind(&org_id)
-    .bind(&title)
-    .bind(&desc)
-    .bind(issue_number as i32)
-    .bind(&display_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+    let issue = submit_public_issue(
+        &pool, &sse_tx, &slug, query.token.as_deref(), &headers, &submission, PublicSource::Email,
+    ).await?;
 
     Ok(Json(json!({
         "data": {
-            "issue_id": issue_id.0.to_string(),
-            "display_id": display_id,
-            "title": title,
+            "issue_id": issue.id.to_string(),
+            "display_id": issue.display_id,
+            "title": issue.title,
         }
     })))
 }
+
+// ─── Public submit by email ───────────────────────────
+
+/// Inbound-email webhook payload. Covers SendGrid Inbound Parse (multipart
+/// `from` / `subject` / `text`) and Postmark (JSON `From` / `FromName` /
+/// `Subject` / `TextBody`).
+#[derive(Debug, Default, Deserialize)]
+pub struct InboundEmail {
+    #[serde(alias = "From")]
+    pub from: Option<String>,
+    #[serde(alias = "FromName")]
+    pub from_name: Option<String>,
+    #[serde(alias = "Subject")]
+    pub subject: Option<String>,
+    #[serde(alias = "TextBody")]
+    pub text: Option<String>,
+}
+
+/// POST /public/{slug}/submit/email — creates an issue through the public
+/// submit path (`source = 'email'`), so the project must allow public
+/// submissions and `?token=` is required when the project has one.
+pub async fn public_submit_email(
+    Extension(sse_tx): Extension<EventSender>,
+    State(pool): State<PgPool>,
+    Path(slug): Path<String>,
+    Query(query): Query<PublicSubmitQuery>,
+    request: Request,
+) -> Result<Json<ApiResponse<Issue>>, (StatusCode, Json<serde_json::Value>)> {
+    let headers = request.headers().clone();
+    let email = read_inbound_email(request).await?;
+    let submission = email_submission(&email).ok_or_else(|| {
+        (StatusCode::BAD_REQUEST, Json(json!({"error": "Email has no subject or text", "field": "subject"})))
+    })?;
+
+    let issue = submit_public_issue(
+        &pool, &sse_tx, &slug, query.token.as_deref(), &headers, &submission, PublicSource::Email,
+    ).await?;
+
+    Ok(Json(ApiResponse::new(issue)))
+}
+
+async fn read_inbound_email(request: Request) -> Result<InboundEmail, (StatusCode, Json<serde_json::Value>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(json!({"error": e})));
+    let is_multipart = request.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("multipart/form-data"));
+
+    if !is_multipart {
+        let Json(email) = Json::<InboundEmail>::from_request(request, &()).await
+            .map_err(|e| bad_request(e.body_text()))?;
+        return Ok(email);
+    }
+
+    let mut multipart = Multipart::from_request(request, &()).await
+        .map_err(|e| bad_request(e.body_text()))?;
+    let mut email = InboundEmail::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| bad_request(e.body_text()))? {
+        let slot = match field.name() {
+            Some("from") => &mut email.from,
+            Some("subject") => &mut email.subject,
+            Some("text") => &mut email.text,
+            _ => continue,
+        };
+        *slot = Some(field.text().await.map_err(|e| bad_request(e.body_text()))?);
+    }
+    Ok(email)
+}
+
+/// Map an inbound email onto a public submission: the subject (minus
+/// `Re:` / `Fwd:`) is the title, the reply text without its quoted thread is
+/// the description, and the sender becomes the reporter. `None` when there is
+/// nothing to title the issue with.
+fn email_submission(email: &InboundEmail) -> Option<PublicSubmission> {
+    let (sender_name, sender_email) = email.from.as_deref().map(parse_email_sender).unwrap_or_default();
+    let reporter_name = email.from_name.clone().filter(|n| !n.trim().is_empty()).or(sender_name);
+    let body = email.text.as_deref().map(strip_quoted_reply).unwrap_or_default();
+
+    let subject = email.subject.as_deref().map(clean_subject).unwrap_or_default();
+    let title = if !subject.is_empty() {
+        subject
+    } else {
+        body.lines().map(str::trim).find(|l| !l.is_empty())?.to_string()
+    };
+
+    Some(PublicSubmission {
+        title: title.chars().take(EMAIL_TITLE_MAX_CHARS).collect(),
+        description: (!body.is_empty()).then(|| truncate_bytes(&body, EMAIL_BODY_MAX_BYTES).to_string()),
+        r#type: None,
+        priority: None,
+        category: None,
+        reporter_name,
+        reporter_email: sender_email,
+        token: None,
+        attachments: None,
+        website: None,
+    })
+}
+
+/// `"Jane Doe" <jane@example.com>` → (Some("Jane Doe"), Some("jane@example.com")).
+/// A bare address has no name.
+fn parse_email_sender(from: &str) -> (Option<String>, Option<String>) {
+    let from = from.trim();
+    match (from.rfind('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => {
+            let name = from[..start].trim().trim_matches('"').trim();
+            let address = from[start + 1..end].trim();
+            (
+                (!name.is_empty()).then(|| name.to_string()),
+                (!address.is_empty()).then(|| address.to_string()),
+            )
+        }
+        _ => (None, (!from.is_empty()).then(|| from.to_string())),
+    }
+}
+
+/// Drop any number of leading `Re:` / `Fwd:` / `Fw:` prefixes.
+fn clean_subject(subject: &str) -> String {
+    let mut rest = subject.trim();
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let Some(prefix) = ["re:", "fwd:", "fw:"].iter().find(|p| lower.starts_with(*p)) else {
+            return rest.to_string();
+        };
+        rest = rest[prefix.len()..].trim_start();
+    }
+}
+
+/// The new part of a reply: everything before the quoted thread ("On … wrote:",
+/// Outlook's "-----Original Message-----" / underscore rule) or the `-- `
+/// signature, without `>`-quoted lines.
+fn strip_quoted_reply(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut kept = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        // Gmail wraps long attributions: "On Mon, … Jane <jane@" / "example.com> wrote:"
+        let wrapped = lines.get(i + 1).map(|next| format!("{} {}", trimmed, next.trim()));
+        let attribution = |l: &str| l.starts_with("On ") && l.ends_with("wrote:");
+        if attribution(trimmed)
+            || wrapped.as_deref().is_some_and(attribution)
+            || trimmed.contains("-----Original Message-----")
+            || trimmed.starts_with("________________")
+            || line.trim_end() == "--"
+        {
+            break;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        kept.push(*line);
+    }
+    kept.join("\n").trim().to_string()
+}
+
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_email_sender() {
+        assert_eq!(
+            parse_email_sender("\"Jane Doe\" <jane@example.com>"),
+            (Some("Jane Doe".to_string()), Some("jane@example.com".to_string()))
+        );
+        assert_eq!(parse_email_sender("jane@example.com"), (None, Some("jane@example.com".to_string())));
+        assert_eq!(clean_subject("RE: Fwd: re:  Login broken"), "Login broken");
+    }
+
+    #[test]
+    fn test_strip_quoted_reply() {
+        let text = "The export still fails.\n\nThanks\n\nOn Mon, Oct 12, 2026 at 9:00 AM Support <help@\nbaaton.dev> wrote:\n> Can you retry?\n";
+        assert_eq!(strip_quoted_reply(text), "The export still fails.\n\nThanks");
+
+        let outlook = "Still broken\n-----Original Message-----\nFrom: Support";
+        assert_eq!(strip_quoted_reply(outlook), "Still broken");
+
+        let inline = "See below\n> old text\nmy answer\n-- \nJane";
+        assert_eq!(strip_quoted_reply(inline), "See below\nmy answer");
+    }
+}
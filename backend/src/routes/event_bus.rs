@@ -16,7 +16,7 @@ use serde::Serialize;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::routes::sse::{EventSender, broadcast_event};
+use crate::routes::sse::{EventSender, BaatonEvent, broadcast_event};
 use crate::routes::webhooks::dispatch_event;
 use crate::routes::activity::log_activity;
 
@@ -55,8 +55,10 @@ pub struct AppEvent {
 /// All channels are processed in a spawned task.
 pub fn emit(pool: PgPool, sse_tx: EventSender, event: AppEvent) {
     // 1. SSE broadcast (synchronous, in-memory — instant)
-    let payload_str = serde_json::to_string(&event.payload).unwrap_or_default();
-    broadcast_event(&sse_tx, &event.org_id, &event.event_type, &payload_str);
+    broadcast_event(&sse_tx, &event.org_id, &BaatonEvent::Custom {
+        event_type: event.event_type.clone(),
+        payload: event.payload.clone(),
+    });
 
     // 2. Everything else in a background task (async, fire-and-forget)
     tokio::spawn(async move {
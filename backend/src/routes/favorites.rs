@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{ApiResponse, Issue, Project};
+use crate::routes::issues::internal_err;
+
+const ENTITY_TYPES: &[&str] = &["issue", "project"];
+
+#[derive(Debug, Deserialize)]
+pub struct FavoriteRequest {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FavoriteState {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub favorited: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Favorites {
+    pub projects: Vec<Project>,
+    pub issues: Vec<Issue>,
+}
+
+fn validate_entity_type(entity_type: &str) -> Result<(), (StatusCode, Json<Value>)> {
+    if ENTITY_TYPES.contains(&entity_type) {
+        return Ok(());
+    }
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": format!("Invalid entity_type: '{}'", entity_type),
+            "code": "validation_error",
+            "field": "entity_type",
+            "accepted_values": ENTITY_TYPES,
+        })),
+    ))
+}
+
+/// Whether `user_id` has starred this issue or project.
+pub async fn is_favorited(
+    pool: &PgPool,
+    user_id: &str,
+    entity_type: &str,
+    entity_id: Uuid,
+) -> bool {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM user_favorites WHERE user_id = $1 AND entity_type = $2 AND entity_id = $3)",
+    )
+    .bind(user_id)
+    .bind(entity_type)
+    .bind(entity_id)
+    .fetch_one(pool)
+    .await
+    .unwrap_or(false)
+}
+
+/// POST /favorites — star an issue or project in the caller's org. Idempotent.
+pub async fn add(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Json(body): Json<FavoriteRequest>,
+) -> Result<Json<ApiResponse<FavoriteState>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    validate_entity_type(&body.entity_type)?;
+
+    let in_org: bool = match body.entity_type.as_str() {
+        "project" => sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)",
+        ),
+        _ => sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)",
+        ),
+    }
+    .bind(body.entity_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(internal_err)?;
+    if !in_org {
+        let what = if body.entity_type == "project" {
+            "Project"
+        } else {
+            "Issue"
+        };
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("{} not found", what)})),
+        ));
+    }
+
+    sqlx::query(
+        r#"INSERT INTO user_favorites (user_id, entity_type, entity_id) VALUES ($1, $2, $3)
+           ON CONFLICT DO NOTHING"#,
+    )
+    .bind(&auth.user_id)
+    .bind(&body.entity_type)
+    .bind(body.entity_id)
+    .execute(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    Ok(Json(ApiResponse::new(FavoriteState {
+        entity_type: body.entity_type,
+        entity_id: body.entity_id,
+        favorited: true,
+    })))
+}
+
+/// DELETE /favorites?entity_type=issue&entity_id=… — unstar. Idempotent.
+pub async fn remove(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Query(params): Query<FavoriteRequest>,
+) -> Result<Json<ApiResponse<FavoriteState>>, (StatusCode, Json<Value>)> {
+    validate_entity_type(&params.entity_type)?;
+
+    sqlx::query(
+        "DELETE FROM user_favorites WHERE user_id = $1 AND entity_type = $2 AND entity_id = $3",
+    )
+    .bind(&auth.user_id)
+    .bind(&params.entity_type)
+    .bind(params.entity_id)
+    .execute(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    Ok(Json(ApiResponse::new(FavoriteState {
+        entity_type: params.entity_type,
+        entity_id: params.entity_id,
+        favorited: false,
+    })))
+}
+
+/// GET /me/favorites — the caller's starred projects and issues in the
+/// current org, most recently starred first.
+pub async fn list_mine(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<Favorites>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+
+    let projects = sqlx::query_as::<_, Project>(
+        r#"SELECT p.* FROM user_favorites f
+           JOIN projects p ON p.id = f.entity_id
+           WHERE f.user_id = $1 AND f.entity_type = 'project'
+             AND p.org_id = $2 AND p.archived_at IS NULL
+           ORDER BY f.created_at DESC"#,
+    )
+    .bind(&auth.user_id)
+    .bind(org_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    let issues = sqlx::query_as::<_, Issue>(
+        r#"SELECT i.*, p.org_id FROM user_favorites f
+           JOIN issues i ON i.id = f.entity_id
+           JOIN projects p ON p.id = i.project_id
+           WHERE f.user_id = $1 AND f.entity_type = 'issue' AND p.org_id = $2
+           ORDER BY f.created_at DESC"#,
+    )
+    .bind(&auth.user_id)
+    .bind(org_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    Ok(Json(ApiResponse::new(Favorites { projects, issues })))
+}
@@ -1,12 +1,13 @@
 use axum::{
     extract::{Extension, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Redirect, Response},
     Json,
 };
 use serde::Deserialize;
 use sqlx::PgPool;
 
+use crate::middleware::roles::{require_action_role, RoleAction};
 use crate::middleware::AuthUser;
 use crate::models::github::GitHubInstallation;
 use crate::models::ApiResponse;
@@ -47,6 +48,7 @@ pub struct CallbackParams {
 pub async fn callback(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
+    headers: HeaderMap,
     Query(params): Query<CallbackParams>,
 ) -> Result<Response, StatusCode> {
     let org_id = auth
@@ -91,20 +93,22 @@ pub async fn callback(
         .as_str()
         .unwrap_or("Organization")
         .to_string();
+    let github_account_avatar_url = account["avatar_url"].as_str().map(|s| s.to_string());
     let permissions = install_info["permissions"].clone();
 
     // Upsert installation
     sqlx::query(
         r#"INSERT INTO github_installations
            (org_id, installation_id, github_account_id, github_account_login,
-            github_account_type, permissions, status, installed_by)
-           VALUES ($1, $2, $3, $4, $5, $6, 'active', $7)
+            github_account_type, permissions, status, installed_by, github_account_avatar_url)
+           VALUES ($1, $2, $3, $4, $5, $6, 'active', $7, $8)
            ON CONFLICT (org_id) DO UPDATE SET
             installation_id = $2,
             github_account_id = $3,
             github_account_login = $4,
             github_account_type = $5,
             permissions = $6,
+            github_account_avatar_url = $8,
             status = 'active',
             updated_at = now()"#,
     )
@@ -115,6 +119,7 @@ pub async fn callback(
     .bind(&github_account_type)
     .bind(&permissions)
     .bind(&auth.user_id)
+    .bind(&github_account_avatar_url)
     .execute(&pool)
     .await
     .map_err(|e| {
@@ -122,6 +127,17 @@ pub async fn callback(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    crate::routes::audit::record(
+        &pool,
+        &auth,
+        &headers,
+        "github.connected",
+        "github_installation",
+        &installation_id.to_string(),
+        serde_json::json!({ "account": github_account_login, "setup_action": params.setup_action }),
+    )
+    .await;
+
     // Sync available repos in the background
     let pool_bg = pool.clone();
     tokio::spawn(async move {
@@ -175,8 +191,15 @@ pub async fn get_installation(
 pub async fn disconnect(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
-    let org_id = auth.org_id.as_deref().ok_or(StatusCode::BAD_REQUEST)?;
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<()>>, Response> {
+    let org_id = auth
+        .org_id
+        .as_deref()
+        .ok_or_else(|| StatusCode::BAD_REQUEST.into_response())?;
+    require_action_role(&pool, &auth, RoleAction::GithubDisconnect)
+        .await
+        .map_err(IntoResponse::into_response)?;
 
     sqlx::query(
         "UPDATE github_installations SET status = 'removed', updated_at = now() WHERE org_id = $1",
@@ -186,7 +209,7 @@ pub async fn disconnect(
     .await
     .map_err(|e| {
         tracing::error!("Failed to disconnect GitHub: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     // Deactivate all mappings
@@ -199,9 +222,20 @@ pub async fn disconnect(
     .await
     .map_err(|e| {
         tracing::error!("Failed to deactivate mappings: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
+    crate::routes::audit::record(
+        &pool,
+        &auth,
+        &headers,
+        "github.disconnected",
+        "organization",
+        org_id,
+        serde_json::json!({}),
+    )
+    .await;
+
     Ok(Json(ApiResponse::new(())))
 }
 
@@ -244,14 +278,18 @@ async fn sync_installation_repos(
                 .unwrap_or("main")
                 .to_string();
             let is_private = repo["private"].as_bool().unwrap_or(false);
+            let description = repo["description"].as_str().map(|s| s.to_string());
+            let html_url = repo["html_url"].as_str().map(|s| s.to_string());
 
             sqlx::query(
                 r#"INSERT INTO github_repositories
-                   (installation_id, github_repo_id, owner, name, full_name, default_branch, is_private)
-                   VALUES ($1, $2, $3, $4, $5, $6, $7)
+                   (installation_id, github_repo_id, owner, name, full_name, default_branch, is_private,
+                    description, html_url)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
                    ON CONFLICT (github_repo_id) DO UPDATE SET
                     owner = $3, name = $4, full_name = $5,
-                    default_branch = $6, is_private = $7, updated_at = now()"#,
+                    default_branch = $6, is_private = $7,
+                    description = $8, html_url = $9, updated_at = now()"#,
             )
             .bind(installation_id)
             .bind(github_repo_id)
@@ -260,6 +298,8 @@ async fn sync_installation_repos(
             .bind(&full_name)
             .bind(&default_branch)
             .bind(is_private)
+            .bind(&description)
+            .bind(&html_url)
             .execute(pool)
             .await?;
         }
@@ -6,10 +6,14 @@ use axum::{
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::github::issue_linker::find_linked_issue;
+use crate::github::label_mapper::validate_issue_defaults;
+use crate::github::status_mapper::{mapped_status, pr_mapping_key};
 use crate::middleware::AuthUser;
 use crate::models::github::{
     CreateRepoMapping, GitHubRepoMapping, GitHubRepository, IssueGitHubData, UpdateRepoMapping,
-    GitHubIssueLink, GitHubPrLink, GitHubCommitLink,
+    GitHubIssueLink, GitHubPrLink, GitHubCommitLink, TldrVcsLink,
+    CommitSyncPreview, PrSyncPreview, PreviewLinkedIssue, PreviewRepoMapping, RepoMappingPreview,
 };
 use crate::models::ApiResponse;
 
@@ -83,6 +87,16 @@ pub async fn create_mapping(
 ) -> Result<Json<ApiResponse<GitHubRepoMapping>>, StatusCode> {
     let org_id = auth.org_id.as_deref().ok_or(StatusCode::BAD_REQUEST)?;
 
+    validate_issue_defaults(
+        body.default_issue_type.as_deref(),
+        body.default_priority.as_deref(),
+        body.label_mapping.as_ref(),
+    )
+    .map_err(|e| {
+        tracing::debug!("Rejected mapping issue defaults: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+
     // Verify project belongs to org
     let project_exists: Option<(Uuid,)> = sqlx::query_as(
         "SELECT id FROM projects WHERE id = $1 AND org_id = $2",
@@ -127,8 +141,9 @@ pub async fn create_mapping(
     let mapping = sqlx::query_as::<_, GitHubRepoMapping>(
         r#"INSERT INTO github_repo_mappings
            (project_id, github_repo_id, sync_direction,
-            sync_issues, sync_prs, sync_comments, auto_create_issues, status_mapping)
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            sync_issues, sync_prs, sync_comments, auto_create_issues, status_mapping,
+            default_issue_type, default_priority, default_tags, label_mapping)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NULLIF($9, ''), NULLIF($10, ''), $11, $12)
            RETURNING *"#,
     )
     .bind(body.project_id)
@@ -139,6 +154,10 @@ pub async fn create_mapping(
     .bind(body.sync_comments.unwrap_or(true))
     .bind(body.auto_create_issues.unwrap_or(false))
     .bind(&status_mapping)
+    .bind(&body.default_issue_type)
+    .bind(&body.default_priority)
+    .bind(body.default_tags.clone().unwrap_or_default())
+    .bind(body.label_mapping.clone().unwrap_or_else(|| serde_json::json!({})))
     .fetch_one(&pool)
     .await
     .map_err(|e| {
@@ -176,6 +195,16 @@ pub async fn update_mapping(
         return Err(StatusCode::NOT_FOUND);
     }
 
+    validate_issue_defaults(
+        body.default_issue_type.as_deref(),
+        body.default_priority.as_deref(),
+        body.label_mapping.as_ref(),
+    )
+    .map_err(|e| {
+        tracing::debug!("Rejected mapping issue defaults: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+
     let updated = sqlx::query_as::<_, GitHubRepoMapping>(
         r#"UPDATE github_repo_mappings SET
             sync_direction = COALESCE($2, sync_direction),
@@ -185,6 +214,10 @@ pub async fn update_mapping(
             auto_create_issues = COALESCE($6, auto_create_issues),
             status_mapping = COALESCE($7, status_mapping),
             is_active = COALESCE($8, is_active),
+            default_issue_type = CASE WHEN $9::text IS NULL THEN default_issue_type ELSE NULLIF($9, '') END,
+            default_priority = CASE WHEN $10::text IS NULL THEN default_priority ELSE NULLIF($10, '') END,
+            default_tags = COALESCE($11, default_tags),
+            label_mapping = COALESCE($12, label_mapping),
             updated_at = now()
            WHERE id = $1
            RETURNING *"#,
@@ -197,6 +230,10 @@ pub async fn update_mapping(
     .bind(body.auto_create_issues)
     .bind(&body.status_mapping)
     .bind(body.is_active)
+    .bind(&body.default_issue_type)
+    .bind(&body.default_priority)
+    .bind(&body.default_tags)
+    .bind(&body.label_mapping)
     .fetch_one(&pool)
     .await
     .map_err(|e| {
@@ -237,6 +274,205 @@ pub async fn delete_mapping(
     Ok(Json(ApiResponse::new(())))
 }
 
+// ─── Preview Mapping ──────────────────────────────────
+
+/// POST /github/mappings/{id}/preview
+///
+/// Dry run of the sync engine: fetches the repo's most recent PRs and
+/// default-branch commits from GitHub and reports which Baaton issue each
+/// would link to and which status change the mapping would apply. Nothing
+/// is written. An optional `status_mapping` in the body is previewed in
+/// place of the stored one, so a mapping can be tried before it is saved.
+/// The mapping's `is_active` / `sync_prs` flags and sync locks are ignored.
+pub async fn preview_mapping(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    body: Option<Json<PreviewRepoMapping>>,
+) -> Result<Json<ApiResponse<RepoMappingPreview>>, StatusCode> {
+    let org_id = auth.org_id.as_deref().ok_or(StatusCode::BAD_REQUEST)?;
+    let body = body.map(|Json(b)| b).unwrap_or_default();
+
+    let mut mapping = sqlx::query_as::<_, GitHubRepoMapping>(
+        r#"SELECT grm.* FROM github_repo_mappings grm
+           JOIN projects p ON p.id = grm.project_id
+           WHERE grm.id = $1 AND p.org_id = $2"#,
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Some(status_mapping) = body.status_mapping {
+        if !status_mapping.is_object() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        mapping.status_mapping = status_mapping;
+    }
+    let limit = body.limit.unwrap_or(20).clamp(1, 100);
+
+    let (installation_id, full_name): (i64, String) = sqlx::query_as(
+        "SELECT installation_id, full_name FROM github_repositories WHERE github_repo_id = $1",
+    )
+    .bind(mapping.github_repo_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let gh_client = crate::github::client::GitHubClient::from_env().map_err(|e| {
+        tracing::error!("Failed to create GitHub client: {}", e);
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
+    let crab = gh_client
+        .for_installation(installation_id as u64)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to authenticate installation {}: {}", installation_id, e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let pulls: Vec<serde_json::Value> = crab
+        .get(
+            format!("/repos/{}/pulls?state=all&sort=updated&direction=desc&per_page={}", full_name, limit),
+            None::<&()>,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list PRs for {}: {}", full_name, e);
+            StatusCode::BAD_GATEWAY
+        })?;
+    let commits: Vec<serde_json::Value> = crab
+        .get(format!("/repos/{}/commits?per_page={}", full_name, limit), None::<&()>)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list commits for {}: {}", full_name, e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let mut pr_previews = Vec::with_capacity(pulls.len());
+    for pr in &pulls {
+        let pr_number = pr["number"].as_i64().unwrap_or(0) as i32;
+        let pr_title = pr["title"].as_str().unwrap_or("");
+        let head_branch = pr["head"]["ref"].as_str().unwrap_or("");
+        // Same states as the webhook processor; the list API has no `merged` flag
+        let pr_state = match (pr["merged_at"].is_string(), pr["state"].as_str()) {
+            (true, _) => "merged",
+            (_, Some("closed")) => "closed",
+            _ if pr["draft"].as_bool().unwrap_or(false) => "draft",
+            _ => "open",
+        };
+
+        let pr_body = pr["body"].as_str().unwrap_or("");
+        let issue = preview_linked_issue(&pool, &mapping, head_branch, pr_title, pr_body)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to resolve PR #{} link: {}", pr_number, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        let mut actions = Vec::new();
+        let mut new_status = None;
+        if let Some(issue) = &issue {
+            let already_linked: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM github_pr_links WHERE github_repo_id = $1 AND pr_number = $2)",
+            )
+            .bind(mapping.github_repo_id)
+            .bind(pr_number)
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            if !already_linked {
+                actions.push("link_pr");
+            }
+            new_status = pr_mapping_key(pr_state)
+                .and_then(|key| mapped_status(&mapping.status_mapping, key))
+                .filter(|s| *s != issue.status)
+                .map(str::to_string);
+            if new_status.is_some() {
+                actions.push("change_status");
+            }
+        }
+
+        pr_previews.push(PrSyncPreview {
+            pr_number,
+            pr_title: pr_title.to_string(),
+            pr_url: pr["html_url"].as_str().unwrap_or("").to_string(),
+            pr_state: pr_state.to_string(),
+            head_branch: head_branch.to_string(),
+            issue,
+            new_status,
+            actions,
+        });
+    }
+
+    let mut commit_previews = Vec::with_capacity(commits.len());
+    for commit in &commits {
+        let sha = commit["sha"].as_str().unwrap_or("");
+        let message = commit["commit"]["message"].as_str().unwrap_or("");
+
+        // Listed from the default branch, so only the message can link
+        let issue = preview_linked_issue(&pool, &mapping, "", message, "").await.map_err(|e| {
+            tracing::error!("Failed to resolve commit {} link: {}", sha, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        let mut actions = Vec::new();
+        if issue.is_some() {
+            let already_linked: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM github_commit_links WHERE github_repo_id = $1 AND sha = $2)",
+            )
+            .bind(mapping.github_repo_id)
+            .bind(sha)
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            if !already_linked {
+                actions.push("link_commit");
+            }
+        }
+
+        commit_previews.push(CommitSyncPreview {
+            sha: sha.to_string(),
+            message: message.lines().next().unwrap_or("").to_string(),
+            url: commit["html_url"].as_str().unwrap_or("").to_string(),
+            issue,
+            actions,
+        });
+    }
+
+    Ok(Json(ApiResponse::new(RepoMappingPreview {
+        mapping_id: mapping.id,
+        repo_full_name: full_name,
+        status_mapping: mapping.status_mapping,
+        pull_requests: pr_previews,
+        commits: commit_previews,
+    })))
+}
+
+/// The issue `find_linked_issue` resolves for a PR or commit, with the
+/// fields a preview shows.
+async fn preview_linked_issue(
+    pool: &PgPool,
+    mapping: &GitHubRepoMapping,
+    branch: &str,
+    title: &str,
+    body: &str,
+) -> Result<Option<PreviewLinkedIssue>, anyhow::Error> {
+    let Some(issue_id) = find_linked_issue(pool, mapping, branch, title, body).await? else {
+        return Ok(None);
+    };
+    let issue = sqlx::query_as::<_, PreviewLinkedIssue>(
+        "SELECT id, display_id, title, status FROM issues WHERE id = $1",
+    )
+    .bind(issue_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(issue)
+}
+
 // ─── Get Issue GitHub Data ────────────────────────────
 
 /// GET /issues/{id}/github
@@ -292,12 +528,28 @@ pub async fn get_issue_github_data(
         vec![]
     });
 
+    let tldrs = sqlx::query_as::<_, TldrVcsLink>(
+        r#"SELECT t.id AS tldr_id, t.agent_name, t.github_pr_link_id, l.pr_number, t.commit_sha, t.created_at
+           FROM tldrs t
+           LEFT JOIN github_pr_links l ON l.id = t.github_pr_link_id
+           WHERE t.issue_id = $1 AND (t.github_pr_link_id IS NOT NULL OR t.commit_sha IS NOT NULL)
+           ORDER BY t.created_at DESC"#,
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::error!(error = %e, "github.issue_data tldr links query failed");
+        vec![]
+    });
+
     let branch_name = crate::github::issue_linker::generate_branch_name(&display_id, &title);
 
     Ok(Json(ApiResponse::new(IssueGitHubData {
         github_issue,
         pull_requests,
         commits,
+        tldrs,
         branch_name,
     })))
 }
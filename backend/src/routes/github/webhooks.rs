@@ -3,11 +3,10 @@ use axum::{
     extract::State,
     http::{HeaderMap, StatusCode},
 };
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
 use sqlx::PgPool;
 
-type HmacSha256 = Hmac<Sha256>;
+use crate::github::webhook_processor::HandledEvent;
+use crate::util::hmac::verify_hmac_sha256;
 
 /// POST /webhooks/github
 ///
@@ -17,10 +16,11 @@ type HmacSha256 = Hmac<Sha256>;
 ///
 /// Flow:
 /// 1. Verify X-Hub-Signature-256 header
-/// 2. Check X-GitHub-Delivery for idempotency
-/// 3. Store raw event in github_webhook_events
-/// 4. Respond 200 immediately
-/// 5. Spawn background task for async processing
+/// 2. Drop event types we don't handle (200, not stored)
+/// 3. Check X-GitHub-Delivery for idempotency
+/// 4. Store raw event in github_webhook_events
+/// 5. Respond 200 immediately
+/// 6. Spawn background task for async processing
 pub async fn handle(
     State(pool): State<PgPool>,
     headers: HeaderMap,
@@ -54,6 +54,16 @@ pub async fn handle(
     verify_signature(&body, &webhook_secret, signature)
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
+    // Ignored event types (star, fork, ping, …) are acknowledged but not stored
+    if HandledEvent::parse(&event_type).is_none() {
+        tracing::debug!(
+            delivery_id = %delivery_id,
+            event_type = %event_type,
+            "Dropping unhandled GitHub webhook event"
+        );
+        return Ok(StatusCode::OK);
+    }
+
     // 3. Idempotency check
     let exists: Option<(bool,)> = sqlx::query_as(
         "SELECT true FROM github_webhook_events WHERE delivery_id = $1",
@@ -148,15 +158,12 @@ pub async fn handle(
     Ok(StatusCode::OK)
 }
 
-/// Verify the HMAC-SHA256 signature from GitHub.
-/// Uses constant-time comparison to prevent timing attacks.
+/// Verify GitHub's `X-Hub-Signature-256` (`sha256=` + hex HMAC of the body).
 fn verify_signature(body: &[u8], secret: &str, signature: &str) -> Result<(), ()> {
     let hex_sig = signature.strip_prefix("sha256=").ok_or(())?;
-    let sig_bytes = hex::decode(hex_sig).map_err(|_| ())?;
-
-    let mut mac =
-        HmacSha256::new_from_slice(secret.as_bytes()).map_err(|_| ())?;
-    mac.update(body);
-
-    mac.verify_slice(&sig_bytes).map_err(|_| ())
+    if verify_hmac_sha256(body, secret, hex_sig) {
+        Ok(())
+    } else {
+        Err(())
+    }
 }
@@ -0,0 +1,148 @@
+//! Operator endpoints served outside `/api/v1`.
+//!
+//! `GET /health/ready` is the readiness probe for the load balancer. `/health`
+//! stays a static liveness check; this one fails with 503 when Postgres
+//! doesn't answer, and reports JWKS/migration state.
+//!
+//! `GET /metrics` reports process-level stats (connection pool usage). It is
+//! only served when `METRICS_TOKEN` is set, and requires
+//! `Authorization: Bearer <token>`.
+
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    Extension, Json,
+};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+
+use crate::middleware::JwksKeys;
+
+const DB_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub async fn ready(
+    Extension(pool): Extension<PgPool>,
+    Extension(jwks): Extension<JwksKeys>,
+) -> (StatusCode, Json<Value>) {
+    let started = Instant::now();
+    let db_check = tokio::time::timeout(
+        DB_CHECK_TIMEOUT,
+        sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(&pool),
+    )
+    .await;
+    let database = match db_check {
+        Ok(Ok(_)) => json!({"ok": true, "latency_ms": started.elapsed().as_millis() as u64}),
+        Ok(Err(e)) => {
+            tracing::error!(error = %e, "Readiness database check failed");
+            json!({"ok": false, "error": "database unavailable"})
+        }
+        Err(_) => json!({
+            "ok": false,
+            "error": format!("timed out after {}ms", DB_CHECK_TIMEOUT.as_millis())
+        }),
+    };
+    let db_ok = database["ok"].as_bool().unwrap_or(false);
+
+    // Migrations that failed at startup are retried on the next restart
+    let pending_migrations = if db_ok {
+        sqlx::query_scalar::<_, i32>("SELECT version FROM _migrations")
+            .fetch_all(&pool)
+            .await
+            .ok()
+            .map(|applied| {
+                crate::migrate::MIGRATIONS
+                    .iter()
+                    .filter(|(version, _)| !applied.contains(version))
+                    .count()
+            })
+    } else {
+        None
+    };
+
+    let jwks_keys = jwks.read().await.len();
+
+    let status = if db_ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(json!({
+            "status": if db_ok { "ready" } else { "unavailable" },
+            "database": database,
+            "jwks": {"loaded": jwks_keys > 0, "keys": jwks_keys},
+            "migrations": {
+                "embedded": crate::migrate::MIGRATIONS.len(),
+                "pending": pending_migrations,
+            },
+        })),
+    )
+}
+
+pub async fn metrics(
+    Extension(pool): Extension<PgPool>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    let Some(token) = std::env::var("METRICS_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({"error": "Not found"})));
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !bool::from(provided.as_bytes().ct_eq(token.as_bytes())) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "Invalid metrics token"})),
+        );
+    }
+
+    // Novu triggers waiting for a retry / given up on
+    let notification_failures: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE status IN ('retrying', 'sending')), COUNT(*) FILTER (WHERE status = 'failed') \
+         FROM notification_failures",
+    )
+    .fetch_one(&pool)
+    .await
+    .ok();
+
+    // Webhook deliveries still stored (completed/failed ones age out, see
+    // `github::jobs::prune_webhook_events`)
+    let webhook_events: Option<(i64, i64, i64, i64)> = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE status IN ('pending', 'processing')), \
+                COUNT(*) FILTER (WHERE status IN ('completed', 'skipped')), \
+                COUNT(*) FILTER (WHERE status = 'failed'), \
+                COUNT(*) \
+         FROM github_webhook_events",
+    )
+    .fetch_one(&pool)
+    .await
+    .ok();
+
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    (
+        StatusCode::OK,
+        Json(json!({
+            "db_pool": {
+                "size": size,
+                "idle": idle,
+                "in_use": size.saturating_sub(idle),
+                "max": pool.options().get_max_connections(),
+                "min": pool.options().get_min_connections(),
+            },
+            "notification_failures": notification_failures.map(|(retrying, failed)| {
+                json!({"retrying": retrying, "failed": failed})
+            }),
+            "github_webhook_events": webhook_events.map(|(pending, completed, failed, total)| {
+                json!({"pending": pending, "completed": completed, "failed": failed, "total": total})
+            }),
+        })),
+    )
+}
@@ -11,6 +11,7 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::Issue;
+use crate::routes::issues::next_display_id;
 
 const MAX_IMPORT: usize = 500;
 
@@ -162,10 +163,11 @@ pub async fn import(
     struct ProjectMeta {
         prefix: String,
         statuses: serde_json::Value,
+        archived: bool,
     }
 
     let project = sqlx::query_as::<_, ProjectMeta>(
-        "SELECT prefix, statuses FROM projects WHERE id = $1 AND org_id = $2"
+        "SELECT prefix, statuses, archived_at IS NOT NULL AS archived FROM projects WHERE id = $1 AND org_id = $2"
     )
     .bind(project_id)
     .bind(org_id)
@@ -173,6 +175,9 @@ pub async fn import(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
     .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))))?;
+    if project.archived {
+        return Err((StatusCode::CONFLICT, Json(json!({"error": "Project is archived", "code": "project_archived"}))));
+    }
 
     let valid_statuses: Vec<String> = project.statuses
         .as_array()
@@ -215,22 +220,23 @@ pub async fn import(
             }
         }
 
-        // Generate display_id
-        let next_number: (i64,) = sqlx::query_as(
-            r#"
-            SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
-            FROM issues
-            WHERE project_id = $1
-              AND display_id ~ ('^' || $2 || '-[0-9]+$')
-            "#,
-        )
-        .bind(project_id)
-        .bind(&project.prefix)
-        .fetch_one(&pool)
-        .await
-        .unwrap_or((1i64,));
-
-        let display_id = format!("{}-{}", project.prefix, next_number.0);
+        // Claim the display_id and insert in one transaction so a failed row
+        // gives its number back
+        let mut tx = match pool.begin().await {
+            Ok(tx) => tx,
+            Err(e) => {
+                errors.push(ImportError { index: i, error: e.to_string() });
+                continue;
+            }
+        };
+        let display_id = match next_display_id(tx.as_mut(), project_id, &project.prefix).await {
+            Ok(id) => id,
+            Err((_, Json(e))) => {
+                let error = e["error"].as_str().unwrap_or("display_id allocation failed");
+                errors.push(ImportError { index: i, error: error.to_string() });
+                continue;
+            }
+        };
 
         // Max position for this status
         let max_pos: Option<f64> = sqlx::query_scalar(
@@ -269,11 +275,11 @@ pub async fn import(
         .bind(position)
         .bind(&created_by_id)
         .bind(&created_by_name)
-        .execute(&pool)
+        .execute(tx.as_mut())
         .await;
 
-        match result {
-            Ok(_) => imported += 1,
+        match result.and(tx.commit().await.map(|_| ())) {
+            Ok(()) => imported += 1,
             Err(e) => {
                 errors.push(ImportError { index: i, error: e.to_string() });
             }
@@ -1,31 +1,103 @@
-use axum::{extract::{Extension, Path}, http::StatusCode, Json, response::Redirect};
+use axum::{body::Bytes, extract::{Extension, Path, State}, http::{header, HeaderMap, StatusCode}, Json, response::Redirect};
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::LazyLock;
-use tokio::sync::RwLock;
+use sqlx::PgPool;
 
+use crate::middleware::roles::{require_role, ROLE_ADMIN, ROLE_MEMBER};
 use crate::middleware::AuthUser;
 use crate::models::ApiResponse;
 
-/// In-memory short code → Clerk URL mapping.
-/// Short codes are derived from the invite ID (first 8 chars).
-static SHORT_LINKS: LazyLock<RwLock<HashMap<String, String>>> =
-    LazyLock::new(|| RwLock::new(HashMap::new()));
+/// Matches Clerk's invitation lifetime; after that the short link 404s instead
+/// of landing on a dead invite.
+const SHORT_LINK_TTL_DAYS: i32 = 30;
+/// Most rows accepted by `POST /invites/bulk`.
+const MAX_BULK_INVITES: usize = 100;
+/// Concurrent Clerk calls during a bulk invite, to stay under Clerk's rate limit.
+const BULK_CONCURRENCY: usize = 3;
 
-/// Generate a short code from a Clerk invite ID like "orginv_39Qp33M0vO0Z..."
-fn make_short_code(invite_id: &str) -> String {
-    // Take last 8 chars of the invite ID (unique enough)
-    let id = invite_id.strip_prefix("orginv_").unwrap_or(invite_id);
-    id.chars().take(8).collect()
+/// Roles an invitation may grant.
+const INVITE_ROLES: &[&str] = &[ROLE_MEMBER, ROLE_ADMIN];
+
+/// Attempts at finding an unused random code before giving up.
+const SHORT_CODE_ATTEMPTS: usize = 5;
+
+/// Random 8-character alphanumeric code. Clerk invite IDs are time-ordered,
+/// so any slice of them collides between invites created close together.
+fn make_short_code() -> String {
+    use rand::{distr::Alphanumeric, Rng};
+    rand::rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect()
+}
+
+fn short_url(code: &str) -> String {
+    format!("https://api.baaton.dev/api/v1/invite/{}", code)
+}
+
+/// Persist `code → url` in `invite_short_links` and return the public short URL.
+/// An invitation that already has a live link keeps its code. Returns None if
+/// the link couldn't be stored, so callers fall back to the Clerk URL.
+async fn store_short_link(pool: &PgPool, org_id: &str, invite_id: &str, url: &str) -> Option<String> {
+    let existing: Result<Option<String>, sqlx::Error> = sqlx::query_scalar(
+        "UPDATE invite_short_links SET url = $2 WHERE invitation_id = $1 AND expires_at > now() RETURNING code"
+    )
+    .bind(invite_id)
+    .bind(url)
+    .fetch_optional(pool)
+    .await;
+    match existing {
+        Ok(Some(code)) => return Some(short_url(&code)),
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!(error = %e, invite_id, "invite_short_links lookup failed");
+            return None;
+        }
+    }
+
+    for _ in 0..SHORT_CODE_ATTEMPTS {
+        let code = make_short_code();
+        let result = sqlx::query(
+            r#"INSERT INTO invite_short_links (code, url, invitation_id, org_id, expires_at)
+               VALUES ($1, $2, $3, $4, now() + make_interval(days => $5))
+               ON CONFLICT (code) DO NOTHING"#
+        )
+        .bind(&code)
+        .bind(url)
+        .bind(invite_id)
+        .bind(org_id)
+        .bind(SHORT_LINK_TTL_DAYS)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(r) if r.rows_affected() == 1 => return Some(short_url(&code)),
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::error!(error = %e, invite_id, "invite_short_links insert failed");
+                return None;
+            }
+        }
+    }
+    tracing::error!(invite_id, "invite_short_links: no free code after {} attempts", SHORT_CODE_ATTEMPTS);
+    None
 }
 
 /// GET /api/v1/invite/:code — Public redirect to Clerk invite URL
 pub async fn redirect_invite(
+    State(pool): State<PgPool>,
     Path(code): Path<String>,
 ) -> Result<Redirect, StatusCode> {
-    let links = SHORT_LINKS.read().await;
-    match links.get(&code) {
-        Some(url) => Ok(Redirect::temporary(url)),
+    let url: Option<String> = sqlx::query_scalar(
+        "SELECT url FROM invite_short_links WHERE code = $1 AND expires_at > now()"
+    )
+    .bind(&code)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(error = %e, "invite_short_links lookup failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    match url {
+        Some(url) => Ok(Redirect::temporary(&url)),
         None => Err(StatusCode::NOT_FOUND),
     }
 }
@@ -36,6 +108,33 @@ pub struct InviteRequest {
     pub role: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BulkInviteRow {
+    #[serde(alias = "email_address")]
+    pub email: String,
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkInviteRequest {
+    pub invites: Vec<BulkInviteRow>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkInviteResult {
+    pub email: String,
+    pub success: bool,
+    pub invite: Option<InviteResponse>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkInviteResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkInviteResult>,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct InviteResponse {
     pub id: String,
@@ -73,6 +172,7 @@ fn get_clerk_secret() -> Result<String, (StatusCode, String)> {
 /// GET /api/v1/invites — List pending org invitations with their URLs.
 pub async fn list(
     Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
 ) -> Result<Json<ApiResponse<Vec<InviteResponse>>>, (StatusCode, String)> {
     let org_id = auth.org_id.as_deref().ok_or((
         StatusCode::BAD_REQUEST,
@@ -81,8 +181,7 @@ pub async fn list(
 
     let clerk_secret = get_clerk_secret()?;
 
-    let client = reqwest::Client::new();
-    let resp = client
+    let resp = crate::util::http::clerk()
         .get(format!(
             "https://api.clerk.com/v1/organizations/{}/invitations?status=pending",
             org_id
@@ -103,56 +202,56 @@ pub async fn list(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!(r#"{{"error":"{}"}}"#, e)))?;
 
     let mut invites = Vec::new();
-    {
-        let mut links = SHORT_LINKS.write().await;
-        for inv in clerk_resp.data {
-            let short_url = if let Some(ref url) = inv.url {
-                let code = make_short_code(&inv.id);
-                links.insert(code.clone(), url.clone());
-                Some(format!("https://api.baaton.dev/api/v1/invite/{}", code))
-            } else {
-                None
-            };
-            invites.push(InviteResponse {
-                id: inv.id,
-                email_address: inv.email_address,
-                status: inv.status,
-                role: inv.role,
-                url: inv.url,
-                short_url,
-            });
-        }
+    for inv in clerk_resp.data {
+        let short_url = match inv.url {
+            Some(ref url) => store_short_link(&pool, org_id, &inv.id, url).await,
+            None => None,
+        };
+        invites.push(InviteResponse {
+            id: inv.id,
+            email_address: inv.email_address,
+            status: inv.status,
+            role: inv.role,
+            url: inv.url,
+            short_url,
+        });
     }
 
     Ok(Json(ApiResponse::new(invites)))
 }
 
-/// POST /api/v1/invites — Create an org invitation via Clerk Backend API.
-/// Returns the invitation URL so the frontend can display a "Copy link" button.
-pub async fn create(
-    Extension(auth): Extension<AuthUser>,
-    Json(body): Json<InviteRequest>,
-) -> Result<Json<ApiResponse<InviteResponse>>, (StatusCode, String)> {
-    let org_id = auth.org_id.as_deref().ok_or((
-        StatusCode::BAD_REQUEST,
-        r#"{"error":"No active organization"}"#.to_string(),
-    ))?;
-
-    let clerk_secret = get_clerk_secret()?;
+/// HTTP client plus secret for Clerk Backend API calls.
+struct ClerkApi {
+    http: reqwest::Client,
+    secret: String,
+}
 
-    let role = body.role.unwrap_or_else(|| "org:member".to_string());
+impl ClerkApi {
+    fn from_env() -> Result<Self, (StatusCode, String)> {
+        Ok(Self { http: crate::util::http::clerk().clone(), secret: get_clerk_secret()? })
+    }
+}
 
-    // Call Clerk Backend API
-    let client = reqwest::Client::new();
-    let resp = client
+/// Create one Clerk invitation, store its short link and audit it.
+async fn send_invite(
+    pool: &PgPool,
+    auth: &AuthUser,
+    headers: &HeaderMap,
+    clerk: &ClerkApi,
+    org_id: &str,
+    email_address: &str,
+    role: &str,
+) -> Result<InviteResponse, (StatusCode, String)> {
+    let resp = clerk
+        .http
         .post(format!(
             "https://api.clerk.com/v1/organizations/{}/invitations",
             org_id
         ))
-        .header("Authorization", format!("Bearer {}", clerk_secret))
+        .header("Authorization", format!("Bearer {}", clerk.secret))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
-            "email_address": body.email_address,
+            "email_address": email_address,
             "role": role,
             "redirect_url": "https://app.baaton.dev/dashboard",
         }))
@@ -181,20 +280,206 @@ pub async fn create(
         )
     })?;
 
-    let short_url = if let Some(ref url) = clerk_resp.url {
-        let code = make_short_code(&clerk_resp.id);
-        SHORT_LINKS.write().await.insert(code.clone(), url.clone());
-        Some(format!("https://api.baaton.dev/api/v1/invite/{}", code))
-    } else {
-        None
+    let short_url = match clerk_resp.url {
+        Some(ref url) => store_short_link(pool, org_id, &clerk_resp.id, url).await,
+        None => None,
     };
 
-    Ok(Json(ApiResponse::new(InviteResponse {
+    crate::routes::audit::record(
+        pool, auth, headers,
+        "member.invited", "invitation", &clerk_resp.id,
+        serde_json::json!({"email": clerk_resp.email_address, "role": clerk_resp.role}),
+    ).await;
+
+    Ok(InviteResponse {
         id: clerk_resp.id,
         email_address: clerk_resp.email_address,
         status: clerk_resp.status,
         role: clerk_resp.role,
         url: clerk_resp.url,
         short_url,
+    })
+}
+
+/// Check that `role` can be invited and that the caller may grant it:
+/// `org:admin` takes the admin role (`admin:full` for API keys).
+fn check_invite_role(auth: &AuthUser, role: &str) -> Result<(), (StatusCode, String)> {
+    if !INVITE_ROLES.contains(&role) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid role '{}'. Accepted: {}", role, INVITE_ROLES.join(", ")),
+        ));
+    }
+    if role == ROLE_ADMIN && require_role(auth, ROLE_ADMIN).is_err() {
+        return Err((StatusCode::FORBIDDEN, "Only org admins can invite admins".to_string()));
+    }
+    Ok(())
+}
+
+/// POST /api/v1/invites — Create an org invitation via Clerk Backend API.
+/// Returns the invitation URL so the frontend can display a "Copy link" button.
+pub async fn create(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Json(body): Json<InviteRequest>,
+) -> Result<Json<ApiResponse<InviteResponse>>, (StatusCode, String)> {
+    let org_id = auth.org_id.as_deref().ok_or((
+        StatusCode::BAD_REQUEST,
+        r#"{"error":"No active organization"}"#.to_string(),
+    ))?;
+
+    let role = body.role.unwrap_or_else(|| ROLE_MEMBER.to_string());
+    check_invite_role(&auth, &role)
+        .map_err(|(status, error)| (status, serde_json::json!({"error": error, "field": "role"}).to_string()))?;
+
+    let clerk = ClerkApi::from_env()?;
+
+    let invite = send_invite(&pool, &auth, &headers, &clerk, org_id, &body.email_address, &role).await?;
+
+    Ok(Json(ApiResponse::new(invite)))
+}
+
+/// Parse `email,role` rows. A leading header row and blank lines are skipped;
+/// a missing role falls back to the default.
+fn parse_csv(text: &str) -> Vec<BulkInviteRow> {
+    text.lines()
+        .map(|line| line.split(',').map(|c| c.trim().trim_matches('"').trim()).collect::<Vec<_>>())
+        .filter(|cells| !cells[0].is_empty())
+        .enumerate()
+        .filter(|(i, cells)| !(*i == 0 && matches!(cells[0].to_lowercase().as_str(), "email" | "email_address")))
+        .map(|(_, cells)| BulkInviteRow {
+            email: cells[0].to_string(),
+            role: cells.get(1).filter(|r| !r.is_empty()).map(|r| r.to_string()),
+        })
+        .collect()
+}
+
+/// Best-effort human message from a Clerk error body (`{"errors":[{"long_message":…}]}`).
+fn clerk_error_message(body: &str) -> String {
+    let parsed = serde_json::from_str::<serde_json::Value>(body).ok();
+    parsed
+        .as_ref()
+        .and_then(|v| {
+            let err = v.get("errors").and_then(|e| e.get(0));
+            err.and_then(|e| e.get("long_message").or_else(|| e.get("message")))
+                .or_else(|| v.get("error"))?
+                .as_str()
+                .map(String::from)
+        })
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// POST /api/v1/invites/bulk — Invite many people at once.
+/// Accepts `{"invites":[{"email","role"}]}` or a `text/csv` body of `email,role` rows.
+/// Each row succeeds or fails on its own; the response lists every row in order.
+pub async fn bulk_create(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ApiResponse<BulkInviteResponse>>, (StatusCode, String)> {
+    let org_id = auth.org_id.as_deref().ok_or((
+        StatusCode::BAD_REQUEST,
+        r#"{"error":"No active organization"}"#.to_string(),
+    ))?;
+
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|ct| ct.starts_with("text/csv"))
+        .unwrap_or(false);
+
+    let rows = if is_csv {
+        let text = std::str::from_utf8(&body).map_err(|_| {
+            (StatusCode::BAD_REQUEST, r#"{"error":"CSV body must be UTF-8"}"#.to_string())
+        })?;
+        parse_csv(text)
+    } else {
+        serde_json::from_slice::<BulkInviteRequest>(&body)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!(r#"{{"error":"Invalid body: {}"}}"#, e)))?
+            .invites
+    };
+
+    if rows.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, r#"{"error":"No invites provided"}"#.to_string()));
+    }
+    if rows.len() > MAX_BULK_INVITES {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(r#"{{"error":"Too many invites. Maximum is {}"}}"#, MAX_BULK_INVITES),
+        ));
+    }
+
+    let clerk = ClerkApi::from_env()?;
+
+    let results: Vec<BulkInviteResult> = stream::iter(rows)
+        .map(|row| {
+            let (pool, auth, headers, clerk) = (&pool, &auth, &headers, &clerk);
+            async move {
+                let email = row.email.trim().to_string();
+                if !email.contains('@') {
+                    return BulkInviteResult { email, success: false, invite: None, error: Some("Invalid email address".into()) };
+                }
+                let role = row.role.unwrap_or_else(|| ROLE_MEMBER.to_string());
+                if let Err((_, error)) = check_invite_role(auth, &role) {
+                    return BulkInviteResult { email, success: false, invite: None, error: Some(error) };
+                }
+                match send_invite(pool, auth, headers, clerk, org_id, &email, &role).await {
+                    Ok(invite) => BulkInviteResult { email, success: true, invite: Some(invite), error: None },
+                    Err((_, body)) => BulkInviteResult { email, success: false, invite: None, error: Some(clerk_error_message(&body)) },
+                }
+            }
+        })
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    Ok(Json(ApiResponse::new(BulkInviteResponse {
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
     })))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv("Email,Role\nalice@acme.io, org:admin\n\n\"bob@acme.io\"\ncarol@acme.io,\n");
+        let emails: Vec<_> = rows.iter().map(|r| r.email.as_str()).collect();
+        assert_eq!(emails, vec!["alice@acme.io", "bob@acme.io", "carol@acme.io"]);
+        assert_eq!(rows[0].role.as_deref(), Some("org:admin"));
+        assert_eq!(rows[1].role, None);
+        assert_eq!(rows[2].role, None);
+    }
+
+    #[test]
+    fn test_check_invite_role() {
+        let mut auth = AuthUser {
+            user_id: "user_1".to_string(),
+            org_id: Some("org_1".to_string()),
+            org_slug: None,
+            org_role: Some("org:member".to_string()),
+            email: None,
+            display_name: None,
+            scoped_org_ids: vec!["org_1".to_string()],
+            scoped_project_ids: vec![],
+            api_key_permissions: None,
+        };
+        assert!(check_invite_role(&auth, "org:member").is_ok());
+        assert_eq!(check_invite_role(&auth, "org:admin").unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_invite_role(&auth, "org:owner").unwrap_err().0, StatusCode::BAD_REQUEST);
+
+        auth.org_role = Some("org:admin".to_string());
+        assert!(check_invite_role(&auth, "org:admin").is_ok());
+
+        auth.org_role = None;
+        auth.api_key_permissions = Some(vec!["members:invite".to_string()]);
+        assert!(check_invite_role(&auth, "org:member").is_ok());
+        assert!(check_invite_role(&auth, "org:admin").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Extension, Json,
 };
 use serde::{Deserialize, Serialize};
@@ -12,6 +12,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::middleware::rate_limit::client_ip;
 use crate::middleware::AuthUser;
 
 // ─── User Org IDs Cache ──────────────────────────────
@@ -31,12 +32,12 @@ use crate::models::{ApiResponse, Comment, CreateIssue, Issue, IssueDetail, Tldr,
 use crate::routes::activity::log_activity;
 use crate::routes::automations::evaluate_automations;
 use crate::routes::notifications::create_notification;
-use crate::routes::sla::apply_sla_deadline;
-use crate::routes::sse::{broadcast_event, EventSender};
+use crate::routes::sla::apply_sla_policy;
+use crate::routes::sse::{broadcast_event, BaatonEvent, EventSender, PositionEnvelope};
 use crate::routes::webhooks::dispatch_event;
 
 /// Log internal error details and return a sanitized error response to the client.
-fn internal_err(e: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+pub(crate) fn internal_err(e: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
     tracing::error!(error = %e, "Internal error");
     (
         StatusCode::INTERNAL_SERVER_ERROR,
@@ -46,7 +47,7 @@ fn internal_err(e: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Valu
 
 /// Strip inline data: URIs from HTML descriptions to prevent oversized JSON responses.
 /// Replaces `data:image/...;base64,...` with a placeholder, preserving the rest of the HTML.
-fn sanitize_description(desc: &str) -> String {
+pub(crate) fn sanitize_description(desc: &str) -> String {
     // Regex-free approach: find data: URIs and replace them
     let mut result = String::with_capacity(desc.len());
     let mut remaining = desc;
@@ -119,6 +120,58 @@ pub async fn list_children(
     Ok(Json(ApiResponse::new(children)))
 }
 
+/// A likely duplicate, scored by trigram similarity (0–1).
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SimilarIssueMatch {
+    pub id: Uuid,
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+    pub similarity: f32,
+}
+
+/// GET /issues/{id}/similar — top 5 likely duplicates in the same project,
+/// by `pg_trgm` similarity on title + description.
+pub async fn list_similar(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<SimilarIssueMatch>>>, (StatusCode, Json<serde_json::Value>)> {
+    let (_current_org_id, org_ids) = require_user_org_scope(&pool, &auth).await?;
+
+    let (project_id, text): (Uuid, String) = sqlx::query_as(
+        "SELECT i.project_id, i.title || ' ' || COALESCE(i.description, '') FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = ANY($2)"
+    )
+    .bind(id)
+    .bind(&org_ids)
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_err)?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))))?;
+
+    // `%` uses the trigram index (default threshold 0.3)
+    let matches = sqlx::query_as::<_, SimilarIssueMatch>(
+        r#"
+        SELECT id, display_id, title, status,
+               similarity(title || ' ' || COALESCE(description, ''), $3) AS similarity
+        FROM issues
+        WHERE project_id = $1
+          AND id <> $2
+          AND (title || ' ' || COALESCE(description, '')) % $3
+        ORDER BY similarity DESC
+        LIMIT 5
+        "#,
+    )
+    .bind(project_id)
+    .bind(id)
+    .bind(&text)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    Ok(Json(ApiResponse::new(matches)))
+}
+
 // ─── Validation constants ─────────────────────────────
 
 const VALID_PRIORITIES: &[&str] = &["urgent", "high", "medium", "low"];
@@ -165,6 +218,7 @@ fn validate_status(
             StatusCode::BAD_REQUEST,
             Json(json!({
                 "error": format!("Invalid status '{}'. Accepted values: {}", status, valid_statuses.join(", ")),
+                "code": "invalid_status",
                 "accepted_values": valid_statuses,
                 "field": "status"
             })),
@@ -173,6 +227,71 @@ fn validate_status(
     Ok(())
 }
 
+/// With `projects.strict_assignees` on, every assignee must be on the
+/// project roster.
+async fn validate_assignees(
+    pool: &PgPool,
+    project_id: Uuid,
+    org_id: &str,
+    assignee_ids: &[String],
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if assignee_ids.is_empty() {
+        return Ok(());
+    }
+    let strict: bool =
+        sqlx::query_scalar("SELECT strict_assignees FROM projects WHERE id = $1 AND org_id = $2")
+            .bind(project_id)
+            .bind(org_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(internal_err)?
+            .unwrap_or(false);
+    if !strict {
+        return Ok(());
+    }
+
+    let unknown =
+        crate::routes::project_members::non_members(pool, org_id, project_id, assignee_ids).await?;
+    if !unknown.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Not project members: {}", unknown.join(", ")),
+                "code": "not_project_member",
+                "invalid_assignee_ids": unknown,
+                "field": "assignee_ids"
+            })),
+        ));
+    }
+    Ok(())
+}
+
+/// With `projects.validate_categories` on, every category must be defined
+/// in `project_categories`.
+async fn validate_categories(
+    pool: &PgPool,
+    project_id: Uuid,
+    org_id: &str,
+    categories: &[String],
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let unknown =
+        crate::routes::categories::undefined_categories(pool, project_id, org_id, categories)
+            .await
+            .map_err(internal_err)?;
+    if !unknown.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("Unknown categories: {}", unknown.join(", ")),
+                "code": "unknown_category",
+                "invalid_categories": unknown,
+                "field": "category"
+            })),
+        ));
+    }
+    Ok(())
+}
+
 fn validate_priority(priority: &str) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
     if !VALID_PRIORITIES.contains(&priority) {
         return Err((
@@ -288,7 +407,7 @@ async fn resolve_user_org_ids(_pool: &PgPool, current_org_id: &str, user_id: &st
     vec![current_org_id.to_string()]
 }
 
-async fn require_user_org_scope(
+pub(crate) async fn require_user_org_scope(
     pool: &PgPool,
     auth: &AuthUser,
 ) -> Result<(String, Vec<String>), (StatusCode, Json<serde_json::Value>)> {
@@ -339,20 +458,170 @@ pub struct ListParams {
     pub after: Option<String>,
     /// Cursor pagination: base64 cursor for backwards pagination
     pub before: Option<String>,
+    /// Add `rollup_estimate` (sum of children's estimates) to parent issues
+    pub rollup_estimate: Option<bool>,
+    /// `GET /issues` only: comma-separated project UUIDs to restrict the list to
+    pub project_ids: Option<String>,
 }
 
+/// Max projects in a single `?project_ids=` filter.
+pub const MAX_PROJECT_FILTER: usize = 50;
+
 impl ListParams {
     /// Resolve effective limit from `limit` or `per_page` alias.
     pub fn effective_limit(&self) -> i64 {
         self.limit.or(self.per_page).unwrap_or(100).min(500)
     }
 
+    /// Parse `project_ids` (comma-separated UUIDs). `None` when absent or empty.
+    pub fn project_id_filter(
+        &self,
+    ) -> Result<Option<Vec<Uuid>>, (StatusCode, Json<serde_json::Value>)> {
+        let Some(raw) = self.project_ids.as_deref() else {
+            return Ok(None);
+        };
+        let mut ids = Vec::new();
+        for part in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let id = Uuid::parse_str(part).map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": format!("Invalid project id: '{}'", part),
+                        "code": "validation_error",
+                        "field": "project_ids",
+                    })),
+                )
+            })?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        if ids.len() > MAX_PROJECT_FILTER {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("Too many project ids (max {})", MAX_PROJECT_FILTER),
+                    "code": "validation_error",
+                    "field": "project_ids",
+                })),
+            ));
+        }
+        Ok(if ids.is_empty() { None } else { Some(ids) })
+    }
+
     /// Resolve effective search: `search` takes priority, `title` is alias.
     pub fn effective_search(&self) -> Option<&str> {
         self.search.as_deref().or(self.title.as_deref())
     }
 }
 
+/// Set `rollup_estimate` on every issue that has children: the sum of its
+/// direct, non-archived children's estimates. The stored `estimate` is untouched.
+async fn fill_rollup_estimates(pool: &PgPool, issues: &mut [Issue]) {
+    if issues.is_empty() {
+        return;
+    }
+    let ids: Vec<Uuid> = issues.iter().map(|i| i.id).collect();
+    let sums: Vec<(Uuid, i64)> = sqlx::query_as(
+        r#"SELECT parent_id, COALESCE(SUM(estimate), 0)::bigint
+           FROM issues
+           WHERE parent_id = ANY($1) AND archived = false
+           GROUP BY parent_id"#,
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "issues.rollup_estimate query failed");
+        vec![]
+    });
+    let sums: HashMap<Uuid, i64> = sums.into_iter().collect();
+    for issue in issues.iter_mut() {
+        issue.rollup_estimate = sums.get(&issue.id).copied();
+    }
+}
+
+/// Most digits a project's `display_id_padding` may ask for.
+pub const MAX_DISPLAY_ID_PADDING: i64 = 8;
+
+/// `PREFIX-N`, with N zero-padded to `padding` digits (`BAA-0007`).
+pub fn format_display_id(prefix: &str, number: i64, padding: i32) -> String {
+    format!(
+        "{}-{:0width$}",
+        prefix,
+        number,
+        width = padding.max(0) as usize
+    )
+}
+
+/// Most holidays a project calendar may list.
+pub const MAX_PROJECT_HOLIDAYS: usize = 366;
+
+/// `start` plus `days` working days. Only ISO weekdays in `working_days`
+/// (1 = Monday) that aren't in `holidays` count; `None` counts every weekday,
+/// which is plain calendar arithmetic when there are no holidays either.
+pub fn add_working_days(
+    start: chrono::NaiveDate,
+    days: i64,
+    working_days: Option<&[i32]>,
+    holidays: &[chrono::NaiveDate],
+) -> chrono::NaiveDate {
+    use chrono::Datelike;
+
+    if working_days.is_none() && holidays.is_empty() {
+        return start + chrono::Duration::days(days);
+    }
+    // An empty calendar would never advance; treat it like "unconfigured"
+    let working_days = working_days.filter(|w| w.iter().any(|d| (1..=7).contains(d)));
+    let is_working = |date: chrono::NaiveDate| {
+        working_days.is_none_or(|w| w.contains(&(date.weekday().number_from_monday() as i32)))
+            && !holidays.contains(&date)
+    };
+    let mut date = start;
+    let mut remaining = days;
+    // Bounded: at least one weekday works and holidays are capped
+    while remaining > 0 {
+        date += chrono::Duration::days(1);
+        if is_working(date) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+/// Claim the next display_id for the project from `project_issue_counters`.
+/// The upsert locks the counter row until the caller's transaction ends, so
+/// concurrent creates never share a number and a rollback doesn't leave a gap.
+/// A project without a counter row yet starts after its highest existing
+/// number (padded or not).
+pub(crate) async fn next_display_id<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    project_id: Uuid,
+    prefix: &str,
+) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+    let (next_number, padding): (i64, i32) = sqlx::query_as(
+        r#"
+        INSERT INTO project_issue_counters (project_id, last_number)
+        VALUES (
+          $1,
+          (SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
+           FROM issues
+           WHERE project_id = $1 AND display_id ~ '^[^-]+-[0-9]+$')
+        )
+        ON CONFLICT (project_id)
+        DO UPDATE SET last_number = project_issue_counters.last_number + 1
+        RETURNING last_number,
+                  (SELECT display_id_padding FROM projects WHERE id = $1)
+        "#,
+    )
+    .bind(project_id)
+    .fetch_one(executor)
+    .await
+    .map_err(internal_err)?;
+
+    Ok(format_display_id(prefix, next_number, padding))
+}
+
 #[derive(sqlx::FromRow)]
 struct ProjectAutoAssignRow {
     prefix: String,
@@ -457,6 +726,9 @@ pub async fn list_all(
     })?;
 
     let all_org_ids = resolve_user_org_ids(&pool, org_id, &auth.user_id).await;
+    // Projects outside the caller's orgs (or the API key's project scope)
+    // simply match nothing
+    let project_ids = auth.restrict_project_filter(params.project_id_filter()?);
 
     let limit = params.effective_limit();
     let offset = params.offset.unwrap_or(0);
@@ -511,6 +783,7 @@ pub async fn list_all(
     };
 
     let include_archived = params.include_archived.unwrap_or(false);
+    let include_snoozed = params.include_snoozed.unwrap_or(false);
     let fetch_limit = limit + 1;
 
     let query = format!(
@@ -526,6 +799,8 @@ pub async fn list_all(
           AND (i.archived = false OR $8::boolean)
           AND ($9::text IS NULL OR i.created_at > $9::timestamptz)
           AND ($10::text IS NULL OR i.created_at < $10::timestamptz)
+          AND ($12::uuid[] IS NULL OR i.project_id = ANY($12))
+          AND (i.snoozed_until IS NULL OR i.snoozed_until <= now() OR $13::boolean)
           {}
         ORDER BY {} {}
         LIMIT $6 OFFSET $7
@@ -545,17 +820,22 @@ pub async fn list_all(
         .bind(&params.created_after) // $9
         .bind(&params.created_before) // $10
         .bind(&cursor_ts) // $11 — safe parameterized cursor
+        .bind(&project_ids) // $12
+        .bind(include_snoozed) // $13
         .fetch_all(&pool)
         .await
-        .unwrap_or_else(|e| {
+        .map_err(|e| {
             tracing::error!(error = %e, "issues.list_all query failed");
-            vec![]
-        });
+            crate::routes::query_timeout_response(&e).unwrap_or_else(|| internal_err(e))
+        })?;
 
     let has_next_page = issues.len() > limit as usize;
     if has_next_page {
         issues.truncate(limit as usize);
     }
+    if params.rollup_estimate.unwrap_or(false) {
+        fill_rollup_estimates(&pool, &mut issues).await;
+    }
 
     let page_info = crate::filter::PageInfo {
         has_next_page,
@@ -575,6 +855,46 @@ pub async fn list_all(
     })))
 }
 
+/// ORDER BY expression and direction for a project's issue list. Shared with
+/// saved views so a view sorts exactly like the equivalent list request.
+pub(crate) fn issue_order(
+    order_by: Option<&str>,
+    order_direction: Option<&str>,
+) -> Result<(&'static str, &'static str), (StatusCode, Json<serde_json::Value>)> {
+    let order_col = match order_by {
+        Some("updated_at") => "i.updated_at",
+        Some("priority") => "CASE i.priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'medium' THEN 2 WHEN 'low' THEN 3 ELSE 4 END",
+        Some("created_at") => "i.created_at",
+        Some("due_date") => "i.due_date",
+        Some("position") | None => "i.position",
+        Some(other) => {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": format!("Invalid order_by: '{}'. Valid values: created_at, updated_at, priority, position, due_date", other),
+                "accepted_values": ["created_at", "updated_at", "priority", "position", "due_date"]
+            }))));
+        }
+    };
+    let order_dir = match order_direction {
+        Some("asc") => "ASC",
+        Some("desc") | None => {
+            if order_by == Some("position") {
+                "ASC"
+            } else {
+                "DESC"
+            }
+        }
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("Invalid order_direction: '{}'. Use 'asc' or 'desc'", other)
+                })),
+            ));
+        }
+    };
+    Ok((order_col, order_dir))
+}
+
 pub async fn list_by_project(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -618,37 +938,10 @@ pub async fn list_by_project(
     let include_snoozed = params.include_snoozed.unwrap_or(false);
 
     // Determine order column and direction
-    let order_col = match params.order_by.as_deref() {
-        Some("updated_at") => "i.updated_at",
-        Some("priority") => "CASE i.priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'medium' THEN 2 WHEN 'low' THEN 3 ELSE 4 END",
-        Some("created_at") => "i.created_at",
-        Some("due_date") => "i.due_date",
-        Some("position") | None => "i.position",
-        Some(other) => {
-            return Err((StatusCode::BAD_REQUEST, Json(json!({
-                "error": format!("Invalid order_by: '{}'. Valid values: created_at, updated_at, priority, position, due_date", other),
-                "accepted_values": ["created_at", "updated_at", "priority", "position", "due_date"]
-            }))));
-        }
-    };
-    let order_dir = match params.order_direction.as_deref() {
-        Some("asc") => "ASC",
-        Some("desc") | None => {
-            if params.order_by.as_deref() == Some("position") {
-                "ASC"
-            } else {
-                "DESC"
-            }
-        }
-        Some(other) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": format!("Invalid order_direction: '{}'. Use 'asc' or 'desc'", other)
-                })),
-            ));
-        }
-    };
+    let (order_col, order_dir) = issue_order(
+        params.order_by.as_deref(),
+        params.order_direction.as_deref(),
+    )?;
 
     // Cursor — decode and validate timestamp format to prevent injection
     let cursor_ts: Option<String> = params.after.as_ref().and_then(|cursor| {
@@ -708,7 +1001,7 @@ pub async fn list_by_project(
           AND ($5::text IS NULL OR i.title ILIKE '%' || $5 || '%' OR i.display_id ILIKE $5 || '%')
           AND ($6::text IS NULL OR $6 = ANY(i.category))
           AND (i.archived = false OR $9::boolean)
-          AND (i.snoozed_until IS NULL OR i.snoozed_until <= CURRENT_DATE OR $10::boolean)
+          AND (i.snoozed_until IS NULL OR i.snoozed_until <= now() OR $10::boolean)
           AND ($11::text IS NULL OR i.created_at > $11::timestamptz)
           AND ($12::text IS NULL OR i.created_at < $12::timestamptz)
           {}
@@ -750,6 +1043,9 @@ pub async fn list_by_project(
     if has_next_page {
         issues.truncate(limit as usize);
     }
+    if params.rollup_estimate.unwrap_or(false) {
+        fill_rollup_estimates(&pool, &mut issues).await;
+    }
     let has_previous_page = params.after.is_some() || offset > 0;
 
     let start_cursor = issues
@@ -771,7 +1067,7 @@ pub async fn list_by_project(
               AND ($5::text IS NULL OR i.title ILIKE '%' || $5 || '%' OR i.display_id ILIKE $5 || '%')
               AND ($6::text IS NULL OR $6 = ANY(i.category))
               AND (i.archived = false OR $7::boolean)
-              AND (i.snoozed_until IS NULL OR i.snoozed_until <= CURRENT_DATE OR $8::boolean)
+              AND (i.snoozed_until IS NULL OR i.snoozed_until <= now() OR $8::boolean)
               AND ($9::text IS NULL OR i.created_at > $9::timestamptz)
               AND ($10::text IS NULL OR i.created_at < $10::timestamptz)
             "#,
@@ -816,17 +1112,18 @@ pub async fn create(
     State(pool): State<PgPool>,
     Json(body): Json<CreateIssue>,
 ) -> Result<Json<ApiResponse<Issue>>, (StatusCode, Json<serde_json::Value>)> {
-    let org_id: String = sqlx::query_scalar("SELECT org_id FROM projects WHERE id = $1")
-        .bind(body.project_id)
-        .fetch_optional(&pool)
-        .await
-        .map_err(|e| internal_err(e))?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Project not found"})),
-            )
-        })?;
+    let (org_id, project_archived): (String, bool) =
+        sqlx::query_as("SELECT org_id, archived_at IS NOT NULL FROM projects WHERE id = $1")
+            .bind(body.project_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| internal_err(e))?
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({"error": "Project not found"})),
+                )
+            })?;
 
     if !auth.has_org_access(&org_id) {
         return Err((
@@ -836,6 +1133,12 @@ pub async fn create(
             })),
         ));
     }
+    if project_archived {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({"error": "Project is archived", "code": "project_archived"})),
+        ));
+    }
 
     // ── Plan enforcement: check issue limit (per-user, cross-org) ─────────
     crate::middleware::plan_guard::enforce_quota(
@@ -890,24 +1193,39 @@ pub async fn create(
     let valid_statuses = get_project_statuses(&pool, body.project_id, &org_id).await?;
     validate_status(status, &valid_statuses)?;
 
+    if let Some(ref assignee_ids) = body.assignee_ids {
+        validate_assignees(&pool, body.project_id, &org_id, assignee_ids).await?;
+    }
+    if let Some(ref category) = body.category {
+        validate_categories(&pool, body.project_id, &org_id, category).await?;
+    }
+
     // ── Depth validation for parent_id (max depth 2) ─────
     if let Some(pid) = body.parent_id {
-        // Fetch the parent issue's own parent_id
-        let parent_parent: Option<Option<Uuid>> =
-            sqlx::query_scalar("SELECT parent_id FROM issues WHERE id = $1")
+        // Fetch the parent issue's own parent_id and project
+        let parent: Option<(Option<Uuid>, Uuid)> =
+            sqlx::query_as("SELECT parent_id, project_id FROM issues WHERE id = $1")
                 .bind(pid)
                 .fetch_optional(&pool)
                 .await
                 .map_err(|e| internal_err(e))?;
 
-        match parent_parent {
+        match parent {
             None => {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(json!({"error": "Parent issue not found"})),
                 ));
             }
-            Some(Some(_grandparent)) => {
+            Some((_, parent_project_id)) if !auth.has_project_access(parent_project_id) => {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(
+                        json!({"error": "API key does not have access to the parent issue's project"}),
+                    ),
+                ));
+            }
+            Some((Some(_grandparent), _)) => {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(json!({
@@ -915,7 +1233,7 @@ pub async fn create(
                     })),
                 ));
             }
-            Some(None) => {} // parent is top-level, ok
+            Some((None, _)) => {} // parent is top-level, ok
         }
     }
 
@@ -926,21 +1244,7 @@ pub async fn create(
         resolve_auto_assign_assignees(&mut tx, body.project_id, &org_id, body.assignee_ids.clone())
             .await?;
 
-    let next_number: (i64,) = sqlx::query_as(
-        r#"
-        SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
-        FROM issues
-        WHERE project_id = $1
-          AND display_id ~ ('^' || $2 || '-[0-9]+$')
-        "#,
-    )
-    .bind(body.project_id)
-    .bind(&project_prefix)
-    .fetch_one(tx.as_mut())
-    .await
-    .unwrap_or((1i64,));
-
-    let display_id = format!("{}-{}", project_prefix, next_number.0);
+    let display_id = next_display_id(tx.as_mut(), body.project_id, &project_prefix).await?;
 
     let max_pos: Option<(Option<f64>,)> =
         sqlx::query_as("SELECT MAX(position) FROM issues WHERE project_id = $1 AND status = $2")
@@ -1082,18 +1386,14 @@ pub async fn create(
 
         if !assignees.is_empty() {
             let novu = novu.clone();
+            let pool = pool.clone();
+            let org_id = org_id.clone();
             let actor_name = actor_name.clone();
             let display_id = display_id.clone();
             let title = title.clone();
             tokio::spawn(async move {
-                let subs: Vec<crate::novu::Subscriber> = assignees
-                    .into_iter()
-                    .map(|id| crate::novu::Subscriber {
-                        id,
-                        email: None,
-                        name: None,
-                    })
-                    .collect();
+                let subs =
+                    crate::novu::subscribers_for(&pool, &org_id, "issue-assigned", assignees).await;
                 novu.trigger_many(
                     "issue-assigned",
                     subs,
@@ -1109,6 +1409,8 @@ pub async fn create(
         // Urgent issue notification
         if priority.as_deref() == Some("urgent") {
             let novu = novu.clone();
+            let pool = pool.clone();
+            let org_id = org_id.clone();
             let assignees: Vec<String> = issue
                 .assignee_ids
                 .iter()
@@ -1117,14 +1419,13 @@ pub async fn create(
                 .collect();
             if !assignees.is_empty() {
                 tokio::spawn(async move {
-                    let subs: Vec<crate::novu::Subscriber> = assignees
-                        .into_iter()
-                        .map(|id| crate::novu::Subscriber {
-                            id,
-                            email: None,
-                            name: None,
-                        })
-                        .collect();
+                    let subs = crate::novu::subscribers_for(
+                        &pool,
+                        &org_id,
+                        "urgent-issue-created",
+                        assignees,
+                    )
+                    .await;
                     novu.trigger_many(
                         "urgent-issue-created",
                         subs,
@@ -1139,14 +1440,14 @@ pub async fn create(
         }
     }
 
-    // ── SLA deadline (fire-and-forget) ────────────────
+    // ── SLA targets (fire-and-forget) ─────────────────
     {
         let pool2 = pool.clone();
         let iid = issue.id;
         let pid = issue.project_id;
         let priority = issue.priority.clone();
         tokio::spawn(async move {
-            apply_sla_deadline(&pool2, iid, pid, priority.as_deref()).await;
+            apply_sla_policy(&pool2, iid, pid, priority.as_deref()).await;
         });
     }
 
@@ -1174,8 +1475,7 @@ pub async fn create(
     broadcast_event(
         &sse_tx,
         &org_id,
-        "issue.created",
-        &serde_json::to_string(&issue).unwrap_or_default(),
+        &BaatonEvent::IssueCreated((&issue).into()),
     );
 
     // ── Auto-triage (fire-and-forget if enabled) ──────
@@ -1287,7 +1587,7 @@ pub async fn get_one(
         )
     })?;
 
-    let issue = sqlx::query_as::<_, Issue>(
+    let mut issue = sqlx::query_as::<_, Issue>(
         r#"
         SELECT i.*, p.org_id
         FROM issues i
@@ -1305,6 +1605,15 @@ pub async fn get_one(
             Json(json!({"error": "Issue not found"})),
         )
     })?;
+    fill_rollup_estimates(&pool, std::slice::from_mut(&mut issue)).await;
+
+    {
+        let pool2 = pool.clone();
+        let uid = auth.user_id.clone();
+        tokio::spawn(async move {
+            crate::routes::me::record_issue_view(&pool2, &uid, id).await;
+        });
+    }
 
     // Fetch TLDRs, comments, and active agent session in parallel
     let (tldrs, comments, agent_session) = tokio::join!(
@@ -1328,6 +1637,7 @@ pub async fn get_one(
     let tldrs = tldrs.unwrap_or_default();
     let comments = comments.unwrap_or_default();
     let agent_session = agent_session.unwrap_or(None);
+    let dependencies = crate::routes::relations::issue_dependencies(&pool, id).await;
 
     tracing::info!(
         issue_id = %id,
@@ -1427,12 +1737,18 @@ pub async fn get_one(
         },
     );
 
+    let favorited = crate::routes::favorites::is_favorited(&pool, &auth.user_id, "issue", id).await;
+    let total_minutes = crate::routes::time_entries::total_minutes(&pool, id).await;
+
     Ok(Json(ApiResponse::with_hints(
         IssueDetail {
             issue,
             tldrs,
             comments,
+            dependencies,
             agent_session,
+            favorited,
+            total_minutes,
             context_summary,
         },
         hints,
@@ -1498,6 +1814,13 @@ pub async fn update(
         validate_issue_type(issue_type)?;
     }
 
+    if let Some(ref assignee_ids) = body.assignee_ids {
+        validate_assignees(&pool, existing.project_id, &target_org_id, assignee_ids).await?;
+    }
+    if let Some(ref category) = body.category {
+        validate_categories(&pool, existing.project_id, &target_org_id, category).await?;
+    }
+
     let new_status = body.status.clone().unwrap_or(existing.status.clone());
     let status_changed = new_status != existing.status;
 
@@ -1533,7 +1856,20 @@ pub async fn update(
                 Some("low") => 7,
                 _ => 5,
             };
-            let auto_due = chrono::Utc::now().date_naive() + chrono::Duration::days(days);
+            let (working_days, holidays): (Option<Vec<i32>>, Vec<chrono::NaiveDate>) =
+                sqlx::query_as("SELECT working_days, holidays FROM projects WHERE id = $1")
+                    .bind(existing.project_id)
+                    .fetch_optional(&pool)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default();
+            let auto_due = add_working_days(
+                chrono::Utc::now().date_naive(),
+                days,
+                working_days.as_deref(),
+                &holidays,
+            );
             effective_due_date = Some(Some(auto_due));
         }
     }
@@ -1560,27 +1896,36 @@ pub async fn update(
 
     let snoozed_until_provided = body.snoozed_until.is_some();
     let snoozed_until_value = body.snoozed_until.flatten();
+    let snoozed_by_value = snoozed_until_value.map(|_| auth.user_id.clone());
 
     let parent_id_provided = body.parent_id.is_some();
     let parent_id_value = body.parent_id.flatten();
 
     // Depth check for parent_id update
     if let Some(new_parent_id) = parent_id_value {
-        let parent_parent: Option<Option<Uuid>> =
-            sqlx::query_scalar("SELECT parent_id FROM issues WHERE id = $1")
+        let parent: Option<(Option<Uuid>, Uuid)> =
+            sqlx::query_as("SELECT parent_id, project_id FROM issues WHERE id = $1")
                 .bind(new_parent_id)
                 .fetch_optional(&pool)
                 .await
                 .map_err(|e| internal_err(e))?;
 
-        match parent_parent {
+        match parent {
             None => {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(json!({"error": "Parent issue not found"})),
                 ));
             }
-            Some(Some(_)) => {
+            Some((_, parent_project_id)) if !auth.has_project_access(parent_project_id) => {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(
+                        json!({"error": "API key does not have access to the parent issue's project"}),
+                    ),
+                ));
+            }
+            Some((Some(_), _)) => {
                 return Err((
                     StatusCode::BAD_REQUEST,
                     Json(json!({
@@ -1588,7 +1933,7 @@ pub async fn update(
                     })),
                 ));
             }
-            Some(None) => {}
+            Some((None, _)) => {}
         }
     }
 
@@ -1609,12 +1954,14 @@ pub async fn update(
             estimate = CASE WHEN $17::boolean THEN $18 ELSE estimate END,
             sprint_id = CASE WHEN $19::boolean THEN $20 ELSE sprint_id END,
             status_changed_at = CASE WHEN $21::boolean THEN now() ELSE status_changed_at END,
+            sla_responded_at = CASE WHEN $21::boolean THEN COALESCE(sla_responded_at, now()) ELSE sla_responded_at END,
             closed_at = CASE
                 WHEN $22::boolean THEN now()
                 WHEN $23::boolean THEN NULL
                 ELSE closed_at
             END,
             snoozed_until = CASE WHEN $24::boolean THEN $25 ELSE snoozed_until END,
+            snoozed_by = CASE WHEN $24::boolean THEN $28 ELSE snoozed_by END,
             parent_id = CASE WHEN $26::boolean THEN $27 ELSE parent_id END,
             updated_at = now()
         WHERE id = $1
@@ -1648,6 +1995,7 @@ pub async fn update(
     .bind(snoozed_until_value) // $25
     .bind(parent_id_provided) // $26
     .bind(parent_id_value) // $27
+    .bind(&snoozed_by_value) // $28
     .fetch_one(&pool)
     .await
     .map_err(|e| internal_err(e))?;
@@ -1764,18 +2112,14 @@ pub async fn update(
                 .collect();
             if !added.is_empty() {
                 let novu = novu.clone();
+                let pool = pool.clone();
+                let org_id = target_org_id.clone();
                 let actor_name = actor_name.clone();
                 let display_id = issue.display_id.clone();
                 let title = issue.title.clone();
                 tokio::spawn(async move {
-                    let subs: Vec<crate::novu::Subscriber> = added
-                        .into_iter()
-                        .map(|id| crate::novu::Subscriber {
-                            id,
-                            email: None,
-                            name: None,
-                        })
-                        .collect();
+                    let subs =
+                        crate::novu::subscribers_for(&pool, &org_id, "issue-assigned", added).await;
                     novu.trigger_many(
                         "issue-assigned",
                         subs,
@@ -1789,6 +2133,45 @@ pub async fn update(
             }
         }
 
+        // Resolved → email the external reporter, unless the project opted out
+        if is_closing {
+            if let Some(reporter_email) = issue.reporter_email.clone() {
+                let novu = novu.clone();
+                let pool = pool.clone();
+                let project_id = issue.project_id;
+                let reporter_name = issue.reporter_name.clone();
+                let display_id = issue.display_id.clone();
+                let title = issue.title.clone();
+                let new_status = new_status.clone();
+                tokio::spawn(async move {
+                    let notify: bool =
+                        sqlx::query_scalar("SELECT notify_reporters FROM projects WHERE id = $1")
+                            .bind(project_id)
+                            .fetch_optional(&pool)
+                            .await
+                            .ok()
+                            .flatten()
+                            .unwrap_or(false);
+                    if !notify {
+                        return;
+                    }
+                    // Ad-hoc subscriber keyed by email — reporters have no account
+                    novu.trigger(
+                        "reported-issue-resolved",
+                        &format!("reporter:{}", reporter_email.to_lowercase()),
+                        Some(&reporter_email),
+                        reporter_name.as_deref(),
+                        json!({
+                            "issueId": display_id,
+                            "issueTitle": title,
+                            "status": new_status,
+                        }),
+                    )
+                    .await;
+                });
+            }
+        }
+
         // Status changed → notify all assignees (exclude actor)
         if status_changed {
             let assignees: Vec<String> = issue
@@ -1799,19 +2182,16 @@ pub async fn update(
                 .collect();
             if !assignees.is_empty() {
                 let novu = novu.clone();
+                let pool = pool.clone();
+                let org_id = target_org_id.clone();
                 let actor_name = actor_name.clone();
                 let display_id = issue.display_id.clone();
                 let old_status = existing.status.clone();
                 let new_status = new_status.clone();
                 tokio::spawn(async move {
-                    let subs: Vec<crate::novu::Subscriber> = assignees
-                        .into_iter()
-                        .map(|id| crate::novu::Subscriber {
-                            id,
-                            email: None,
-                            name: None,
-                        })
-                        .collect();
+                    let subs =
+                        crate::novu::subscribers_for(&pool, &org_id, "status-changed", assignees)
+                            .await;
                     novu.trigger_many(
                         "status-changed",
                         subs,
@@ -1888,7 +2268,22 @@ pub async fn update(
         }
     }
 
-    // ── SLA deadline on priority change (fire-and-forget) ─
+    // ── GitHub labels on tag change (fire-and-forget) ─
+    if existing.tags != issue.tags {
+        let pool2 = pool.clone();
+        let iid = issue.id;
+        let old_tags = existing.tags.clone();
+        let new_tags = issue.tags.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                crate::github::jobs::enqueue_label_sync(&pool2, iid, &old_tags, &new_tags).await
+            {
+                tracing::warn!(issue_id = %iid, error = %e, "enqueue_label_sync failed");
+            }
+        });
+    }
+
+    // ── SLA targets on priority change (fire-and-forget) ─
     let priority_changed_flag = body.priority.is_some() && existing.priority != issue.priority;
     if priority_changed_flag {
         let pool2 = pool.clone();
@@ -1896,7 +2291,7 @@ pub async fn update(
         let pid = issue.project_id;
         let priority = issue.priority.clone();
         tokio::spawn(async move {
-            apply_sla_deadline(&pool2, iid, pid, priority.as_deref()).await;
+            apply_sla_policy(&pool2, iid, pid, priority.as_deref()).await;
         });
     }
 
@@ -1937,16 +2332,11 @@ pub async fn update(
 
     // ── SSE broadcast ────────────────────────────────
     let sse_event = if status_changed {
-        "issue.status_changed"
+        BaatonEvent::StatusChanged((&issue).into())
     } else {
-        "issue.updated"
+        BaatonEvent::IssueUpdated((&issue).into())
     };
-    broadcast_event(
-        &sse_tx,
-        &target_org_id,
-        sse_event,
-        &serde_json::to_string(&issue).unwrap_or_default(),
-    );
+    broadcast_event(&sse_tx, &target_org_id, &sse_event);
 
     // AI-first: contextual action hints
     let mut hints = vec![];
@@ -2000,6 +2390,7 @@ pub async fn update(
 
 pub async fn update_position(
     Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
     Json(body): Json<serde_json::Value>,
@@ -2023,14 +2414,40 @@ pub async fn update_position(
         ));
     }
 
-    let status = body
-        .get("status")
-        .and_then(|v| v.as_str())
-        .unwrap_or("todo");
-    let position = body
-        .get("position")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(1000.0);
+    let anchor_field =
+        |field: &str| -> Result<Option<Uuid>, (StatusCode, Json<serde_json::Value>)> {
+            match body.get(field).filter(|v| !v.is_null()) {
+            None => Ok(None),
+            Some(v) => v
+                .as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .map(Some)
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({"error": format!("{} must be an issue id", field), "field": field})),
+                    )
+                }),
+        }
+        };
+    // (anchor, place_before_anchor)
+    let anchor = match (anchor_field("before_id")?, anchor_field("after_id")?) {
+        (Some(_), Some(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Pass either before_id or after_id, not both"})),
+            ))
+        }
+        (Some(before), None) => Some((before, true)),
+        (None, Some(after)) => Some((after, false)),
+        (None, None) => None,
+    };
+    if anchor.is_some_and(|(anchor_id, _)| anchor_id == id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "An issue cannot be positioned relative to itself"})),
+        ));
+    }
 
     // Validate status against project config
     let project_id: Uuid = sqlx::query_scalar("SELECT project_id FROM issues WHERE id = $1")
@@ -2052,7 +2469,75 @@ pub async fn update_position(
     .await
     .map_err(internal_err)?;
     let valid_statuses = get_project_statuses(&pool, project_id, &target_org_id).await?;
-    validate_status(status, &valid_statuses)?;
+
+    let mut tx = pool.begin().await.map_err(internal_err)?;
+
+    let (status, position, reindexed) = match anchor {
+        None => {
+            let status = body
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("todo")
+                .to_string();
+            let position = body
+                .get("position")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(1000.0);
+            (status, position, false)
+        }
+        Some((anchor_id, before)) => {
+            // Serialize reorders on the board, like reindex_positions
+            sqlx::query("SELECT id FROM projects WHERE id = $1 FOR UPDATE")
+                .bind(project_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(internal_err)?;
+
+            let anchor_status: Option<String> = sqlx::query_scalar(
+                "SELECT status FROM issues WHERE id = $1 AND project_id = $2 AND archived = false",
+            )
+            .bind(anchor_id)
+            .bind(project_id)
+            .fetch_optional(tx.as_mut())
+            .await
+            .map_err(internal_err)?;
+            let field = if before { "before_id" } else { "after_id" };
+            let anchor_status = anchor_status.ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": format!("{} must be an issue in the same project", field), "field": field})),
+                )
+            })?;
+            let status = body
+                .get("status")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or(anchor_status.clone());
+            if status != anchor_status {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": format!("{} is in '{}', not '{}'", field, anchor_status, status),
+                        "field": field
+                    })),
+                ));
+            }
+
+            match anchored_position(&mut tx, project_id, id, anchor_id, &status, before).await? {
+                Some(position) => (status, position, false),
+                None => {
+                    // Neighbours too close to split: re-space the column and retry
+                    respace_positions(&mut tx, project_id, Some(&status)).await?;
+                    let position =
+                        anchored_position(&mut tx, project_id, id, anchor_id, &status, before)
+                            .await?
+                            .ok_or_else(|| internal_err("no room to insert after reindex"))?;
+                    (status, position, true)
+                }
+            }
+        }
+    };
+    validate_status(&status, &valid_statuses)?;
 
     let issue = sqlx::query_as::<_, Issue>(
         r#"
@@ -2062,15 +2547,201 @@ pub async fn update_position(
         "#,
     )
     .bind(id)
-    .bind(status)
+    .bind(&status)
     .bind(position)
-    .fetch_one(&pool)
+    .fetch_one(tx.as_mut())
     .await
     .map_err(|e| internal_err(e))?;
 
+    tx.commit().await.map_err(internal_err)?;
+
+    broadcast_event(
+        &sse_tx,
+        &target_org_id,
+        &BaatonEvent::IssueUpdated((&issue).into()),
+    );
+    broadcast_event(
+        &sse_tx,
+        &target_org_id,
+        &BaatonEvent::PositionChanged(PositionEnvelope {
+            issue_id: issue.id,
+            project_id: issue.project_id,
+            status: issue.status.clone(),
+            position: issue.position,
+            reindexed,
+        }),
+    );
+
     Ok(Json(ApiResponse::new(issue)))
 }
 
+/// Gap between the positions written by a reindex.
+const POSITION_STEP: f64 = 1000.0;
+
+/// Smallest gap a midpoint insert may leave; anything tighter re-spaces the column.
+const MIN_POSITION_GAP: f64 = 1e-6;
+
+/// Position halfway between two neighbours, or a full step past the only
+/// one at a column edge. `None` when the neighbours are too close to split.
+fn midpoint_position(prev: Option<f64>, next: Option<f64>) -> Option<f64> {
+    let mid = match (prev, next) {
+        (Some(p), Some(n)) => (p + n) / 2.0,
+        (Some(p), None) => p + POSITION_STEP,
+        (None, Some(n)) => n - POSITION_STEP,
+        (None, None) => POSITION_STEP,
+    };
+    let clear = |x: Option<f64>| x.is_none_or(|x| (mid - x).abs() >= MIN_POSITION_GAP);
+    (clear(prev) && clear(next)).then_some(mid)
+}
+
+/// Position that puts `issue_id` just before (or after) `anchor_id` in the
+/// `status` column, ordering by (position, created_at) like a reindex does.
+async fn anchored_position(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: Uuid,
+    issue_id: Uuid,
+    anchor_id: Uuid,
+    status: &str,
+    before: bool,
+) -> Result<Option<f64>, (StatusCode, Json<serde_json::Value>)> {
+    let (anchor_pos, anchor_created): (f64, chrono::DateTime<chrono::Utc>) =
+        sqlx::query_as("SELECT position, created_at FROM issues WHERE id = $1")
+            .bind(anchor_id)
+            .fetch_one(tx.as_mut())
+            .await
+            .map_err(internal_err)?;
+
+    let neighbour_sql = if before {
+        r#"SELECT position FROM issues
+           WHERE project_id = $1 AND status = $2 AND archived = false AND id <> $3
+             AND (position, created_at) < ($4, $5)
+           ORDER BY position DESC, created_at DESC
+           LIMIT 1"#
+    } else {
+        r#"SELECT position FROM issues
+           WHERE project_id = $1 AND status = $2 AND archived = false AND id <> $3
+             AND (position, created_at) > ($4, $5)
+           ORDER BY position ASC, created_at ASC
+           LIMIT 1"#
+    };
+    let neighbour: Option<f64> = sqlx::query_scalar(neighbour_sql)
+        .bind(project_id)
+        .bind(status)
+        .bind(issue_id)
+        .bind(anchor_pos)
+        .bind(anchor_created)
+        .fetch_optional(tx.as_mut())
+        .await
+        .map_err(internal_err)?;
+
+    Ok(if before {
+        midpoint_position(neighbour, Some(anchor_pos))
+    } else {
+        midpoint_position(Some(anchor_pos), neighbour)
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReindexParams {
+    /// Only reindex this status column (default: every column).
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct IssuePosition {
+    pub id: Uuid,
+    pub display_id: String,
+    pub status: String,
+    pub position: f64,
+}
+
+/// Rewrite positions to 1000, 2000, … per status column, keeping the current
+/// order (ties broken by creation time). Archived issues keep their position.
+async fn respace_positions(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: Uuid,
+    status: Option<&str>,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    sqlx::query(
+        r#"
+        UPDATE issues i SET position = r.rn * $3
+        FROM (
+          SELECT id, ROW_NUMBER() OVER (PARTITION BY status ORDER BY position ASC, created_at ASC) AS rn
+          FROM issues
+          WHERE project_id = $1 AND archived = false AND ($2::text IS NULL OR status = $2)
+        ) r
+        WHERE i.id = r.id
+        "#,
+    )
+    .bind(project_id)
+    .bind(status)
+    .bind(POSITION_STEP)
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+    Ok(())
+}
+
+/// POST /projects/{id}/issues/reindex?status=todo
+///
+/// Evenly re-space board positions once repeated midpoint inserts have
+/// squeezed the gaps. Returns the new ordering.
+pub async fn reindex_positions(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<ReindexParams>,
+) -> Result<Json<ApiResponse<Vec<IssuePosition>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+
+    let mut tx = pool.begin().await.map_err(internal_err)?;
+
+    // Lock the project so concurrent reindexes of the same board serialize
+    let exists: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM projects WHERE id = $1 AND org_id = $2 FOR UPDATE")
+            .bind(project_id)
+            .bind(org_id)
+            .fetch_optional(tx.as_mut())
+            .await
+            .map_err(internal_err)?;
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        ));
+    }
+
+    if let Some(ref status) = params.status {
+        let valid_statuses = get_project_statuses(&pool, project_id, org_id).await?;
+        validate_status(status, &valid_statuses)?;
+    }
+
+    respace_positions(&mut tx, project_id, params.status.as_deref()).await?;
+
+    let ordering = sqlx::query_as::<_, IssuePosition>(
+        r#"
+        SELECT id, display_id, status, position
+        FROM issues
+        WHERE project_id = $1 AND archived = false AND ($2::text IS NULL OR status = $2)
+        ORDER BY status, position
+        "#,
+    )
+    .bind(project_id)
+    .bind(params.status.as_deref())
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+
+    tx.commit().await.map_err(internal_err)?;
+
+    Ok(Json(ApiResponse::new(ordering)))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MineParams {
     pub assignee_id: String,
@@ -2166,8 +2837,7 @@ pub async fn remove(
         broadcast_event(
             &sse_tx,
             &target_org_id,
-            "issue.deleted",
-            &format!(r#"{{"id":"{}"}}"#, id),
+            &BaatonEvent::IssueDeleted { issue_id: id },
         );
         Ok(Json(ApiResponse::new(())))
     } else {
@@ -2201,6 +2871,7 @@ pub struct BatchDeleteBody {
 
 pub async fn batch_update(
     Extension(auth): Extension<AuthUser>,
+    Extension(novu): Extension<Option<crate::novu::NovuClient>>,
     Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
     Json(body): Json<BatchUpdateBody>,
@@ -2244,6 +2915,27 @@ pub async fn batch_update(
     let mut updated_count: i64 = 0;
 
     for issue_id in &body.issue_ids {
+        // Previous assignees, to notify only the people newly added
+        let previous_assignees: Vec<String> = if body.changes.assignee_ids.is_some() {
+            sqlx::query_scalar("SELECT assignee_ids FROM issues WHERE id = $1")
+                .bind(issue_id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(internal_err)?
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+        let previous_tags: Option<Vec<String>> = if body.changes.tags.is_some() {
+            sqlx::query_scalar("SELECT tags FROM issues WHERE id = $1")
+                .bind(issue_id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(internal_err)?
+        } else {
+            None
+        };
+
         // Build dynamic update — only touch provided fields
         let issue = sqlx::query_as::<_, Issue>(
             r#"
@@ -2252,6 +2944,8 @@ pub async fn batch_update(
                 priority   = CASE WHEN $3::text IS NOT NULL THEN $3 ELSE priority END,
                 assignee_ids = CASE WHEN $4::text[] IS NOT NULL THEN $4 ELSE assignee_ids END,
                 tags       = CASE WHEN $5::text[] IS NOT NULL THEN $5 ELSE tags END,
+                sla_responded_at = CASE WHEN $2::text IS DISTINCT FROM status AND $2::text IS NOT NULL
+                                        THEN COALESCE(sla_responded_at, now()) ELSE sla_responded_at END,
                 updated_at = now()
             WHERE id = $1
               AND project_id IN (SELECT id FROM projects WHERE org_id = ANY($6))
@@ -2270,6 +2964,19 @@ pub async fn batch_update(
 
         if let Some(issue) = issue {
             updated_count += 1;
+            if let Some(old_tags) = previous_tags.filter(|t| *t != issue.tags) {
+                let pool2 = pool.clone();
+                let iid = issue.id;
+                let new_tags = issue.tags.clone();
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        crate::github::jobs::enqueue_label_sync(&pool2, iid, &old_tags, &new_tags)
+                            .await
+                    {
+                        tracing::warn!(issue_id = %iid, error = %e, "enqueue_label_sync failed");
+                    }
+                });
+            }
             let event = if body.changes.status.is_some() {
                 "status.changed"
             } else {
@@ -2289,16 +2996,45 @@ pub async fn batch_update(
             )
             .await;
             let sse_event = if body.changes.status.is_some() {
-                "issue.status_changed"
+                BaatonEvent::StatusChanged((&issue).into())
             } else {
-                "issue.updated"
+                BaatonEvent::IssueUpdated((&issue).into())
             };
-            broadcast_event(
-                &sse_tx,
-                &issue_org_id,
-                sse_event,
-                &serde_json::to_string(&issue).unwrap_or_default(),
-            );
+            broadcast_event(&sse_tx, &issue_org_id, &sse_event);
+
+            // ── Novu: newly added assignees (fire-and-forget) ──
+            if let (Some(novu), Some(new_ids)) = (&novu, &body.changes.assignee_ids) {
+                let added: Vec<String> = new_ids
+                    .iter()
+                    .filter(|id| !previous_assignees.contains(id) && **id != auth.user_id)
+                    .cloned()
+                    .collect();
+                if !added.is_empty() {
+                    let novu = novu.clone();
+                    let pool = pool.clone();
+                    let org_id = issue_org_id.clone();
+                    let actor_name = auth
+                        .display_name
+                        .clone()
+                        .unwrap_or_else(|| auth.user_id.clone());
+                    let display_id = issue.display_id.clone();
+                    let title = issue.title.clone();
+                    tokio::spawn(async move {
+                        let subs =
+                            crate::novu::subscribers_for(&pool, &org_id, "issue-assigned", added)
+                                .await;
+                        novu.trigger_many(
+                            "issue-assigned",
+                            subs,
+                            json!({
+                                "actorName": actor_name,
+                                "issueId": display_id,
+                                "issueTitle": title,
+                            }),
+                        );
+                    });
+                }
+            }
         }
     }
 
@@ -2307,6 +3043,7 @@ pub async fn batch_update(
 
 pub async fn batch_delete(
     Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
     Json(body): Json<BatchDeleteBody>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
@@ -2316,16 +3053,28 @@ pub async fn batch_delete(
         return Ok(Json(json!({"deleted": 0})));
     }
 
-    let result = sqlx::query(
-        "DELETE FROM issues WHERE id = ANY($1) AND project_id IN (SELECT id FROM projects WHERE org_id = ANY($2))"
+    let deleted: Vec<(Uuid, String)> = sqlx::query_as(
+        "DELETE FROM issues i USING projects p \
+         WHERE p.id = i.project_id AND i.id = ANY($1) AND p.org_id = ANY($2) \
+         RETURNING i.id, p.org_id",
     )
     .bind(&body.issue_ids)
     .bind(&org_ids)
-    .execute(&pool)
+    .fetch_all(&pool)
     .await
     .map_err(|e| internal_err(e))?;
 
-    Ok(Json(json!({"deleted": result.rows_affected()})))
+    for (issue_id, org_id) in &deleted {
+        broadcast_event(
+            &sse_tx,
+            org_id,
+            &BaatonEvent::IssueDeleted {
+                issue_id: *issue_id,
+            },
+        );
+    }
+
+    Ok(Json(json!({"deleted": deleted.len()})))
 }
 
 // ─── Global Search ────────────────────────────────────
@@ -2372,6 +3121,8 @@ pub async fn search(
     let limit = params.limit.unwrap_or(20).min(100);
 
     let is_overdue = params.is_overdue.unwrap_or(false);
+    // Project-scoped API keys only search their projects
+    let project_ids = auth.restrict_project_filter(None);
 
     let results = sqlx::query_as::<_, SearchResult>(
         r#"
@@ -2391,6 +3142,7 @@ pub async fn search(
           AND ($3::uuid IS NULL OR i.project_id = $3)
           AND ($4::text IS NULL OR i.status = $4)
           AND (NOT $6::boolean OR (i.due_date < CURRENT_DATE AND i.status NOT IN ('done', 'cancelled')))
+          AND ($7::uuid[] IS NULL OR i.project_id = ANY($7))
         ORDER BY ts_rank(i.search_vector, plainto_tsquery('english', $1)) DESC
         LIMIT $5
         "#,
@@ -2401,6 +3153,7 @@ pub async fn search(
     .bind(&params.status)
     .bind(limit)
     .bind(is_overdue)
+    .bind(&project_ids)
     .fetch_all(&pool)
     .await
     .map_err(|e| internal_err(e))?;
@@ -2546,7 +3299,7 @@ pub async fn fetch_user_org_ids(user_id: &str) -> Result<Vec<String>, String> {
         user_id
     );
 
-    let response = reqwest::Client::new()
+    let response = crate::util::http::clerk()
         .get(&url)
         .bearer_auth(&secret)
         .send()
@@ -2623,13 +3376,132 @@ pub struct PublicSubmission {
     pub reporter_email: Option<String>,
     pub token: Option<String>,
     pub attachments: Option<serde_json::Value>,
+    /// Honeypot — hidden in the form, so only bots fill it in.
+    pub website: Option<String>,
+}
+
+/// Minimum gap between two public submissions from the same IP when the
+/// project's spam filter is on (on top of the hourly rate limit).
+const PUBLIC_SUBMIT_MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Submissions with more links than this are flagged as spam.
+const SPAM_MAX_LINKS: usize = 3;
+
+const SPAM_KEYWORDS: &[&str] = &[
+    "viagra",
+    "cialis",
+    "casino",
+    "crypto investment",
+    "forex",
+    "payday loan",
+    "seo services",
+    "backlinks",
+    "buy followers",
+    "escort",
+];
+
+static PUBLIC_SUBMIT_LAST_SEEN: OnceLock<RwLock<HashMap<String, Instant>>> = OnceLock::new();
+
+fn public_submit_last_seen() -> &'static RwLock<HashMap<String, Instant>> {
+    PUBLIC_SUBMIT_LAST_SEEN.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Record a submission from `ip`; false if the previous one was too recent.
+async fn public_submit_interval_ok(ip: &str) -> bool {
+    let now = Instant::now();
+    let mut seen = public_submit_last_seen().write().await;
+    if let Some(last) = seen.get(ip) {
+        if now.duration_since(*last) < PUBLIC_SUBMIT_MIN_INTERVAL {
+            return false;
+        }
+    }
+    if seen.len() > 10_000 {
+        seen.retain(|_, last| now.duration_since(*last) < PUBLIC_SUBMIT_MIN_INTERVAL);
+    }
+    seen.insert(ip.to_string(), now);
+    true
+}
+
+/// Keyword / link-count heuristics. Flagged submissions are still stored,
+/// tagged `spam` and left unassigned.
+fn looks_like_spam(title: &str, description: Option<&str>) -> bool {
+    let text = format!("{} {}", title, description.unwrap_or_default()).to_lowercase();
+    let links = text.matches("http://").count() + text.matches("https://").count();
+    links > SPAM_MAX_LINKS || SPAM_KEYWORDS.iter().any(|k| text.contains(k))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicSubmitQuery {
+    pub token: Option<String>,
+}
+
+/// Whether a submission may proceed given the project's token. Projects
+/// without a token accept any submission; otherwise the token must match.
+fn public_token_matches(expected: Option<&str>, provided: Option<&str>) -> bool {
+    match (expected, provided) {
+        (None, _) => true,
+        (Some(expected), Some(provided)) => {
+            expected.len() == provided.len()
+                && expected
+                    .bytes()
+                    .zip(provided.bytes())
+                    .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                    == 0
+        }
+        (Some(_), None) => false,
+    }
 }
 
 pub async fn public_submit(
+    Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
     Path(slug): Path<String>,
+    Query(query): Query<PublicSubmitQuery>,
+    headers: HeaderMap,
     Json(body): Json<PublicSubmission>,
 ) -> Result<Json<ApiResponse<Issue>>, (StatusCode, Json<serde_json::Value>)> {
+    // Token may come in the body (form widget) or as `?token=` (shared links)
+    let token = body.token.clone().or(query.token);
+    let issue = submit_public_issue(
+        &pool,
+        &sse_tx,
+        &slug,
+        token.as_deref(),
+        &headers,
+        &body,
+        PublicSource::Form,
+    )
+    .await?;
+    Ok(Json(ApiResponse::new(issue)))
+}
+
+/// Where a public submission came from; stored as `issues.source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PublicSource {
+    Form,
+    Email,
+}
+
+impl PublicSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Form => "form",
+            Self::Email => "email",
+        }
+    }
+}
+
+/// Shared by the form and email endpoints: checks the project accepts public
+/// submissions, applies the spam filter and creates the issue.
+pub(crate) async fn submit_public_issue(
+    pool: &PgPool,
+    sse_tx: &EventSender,
+    slug: &str,
+    token: Option<&str>,
+    headers: &HeaderMap,
+    body: &PublicSubmission,
+    source: PublicSource,
+) -> Result<Issue, (StatusCode, Json<serde_json::Value>)> {
     if body.title.trim().is_empty() || body.title.len() > 500 {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -2666,13 +3538,19 @@ pub async fn public_submit(
 
     let mut tx = pool.begin().await.map_err(|e| internal_err(e))?;
 
-    let project = sqlx::query_as::<_, (Uuid, String, String, bool, Option<String>)>(
-        "SELECT id, prefix, org_id, public_submit_enabled, public_submit_token FROM projects WHERE slug = $1 FOR UPDATE"
+    let project = sqlx::query_as::<_, (Uuid, String, String, bool, Option<String>, bool)>(
+        "SELECT id, prefix, org_id, public_submit_enabled, public_submit_token, public_submit_spam_filter FROM projects WHERE slug = $1 AND archived_at IS NULL FOR UPDATE"
     )
-    .bind(&slug)
-    .fetch_one(tx.as_mut())
+    .bind(slug)
+    .fetch_optional(tx.as_mut())
     .await
-    .map_err(|e| (StatusCode::NOT_FOUND, Json(json!({"error": format!("Project not found: {}", e)}))))?;
+    .map_err(internal_err)?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        )
+    })?;
 
     if !project.3 {
         return Err((
@@ -2680,34 +3558,48 @@ pub async fn public_submit(
             Json(json!({"error": "Public submission disabled"})),
         ));
     }
-    match (&project.4, body.token.as_deref()) {
-        (Some(expected), Some(provided)) if expected == provided => {}
-        _ => {
+    if !public_token_matches(project.4.as_deref().filter(|t| !t.is_empty()), token) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Invalid public token"})),
+        ));
+    }
+
+    let spam_filter = project.5;
+    if spam_filter {
+        if body
+            .website
+            .as_deref()
+            .is_some_and(|w| !w.trim().is_empty())
+        {
             return Err((
-                StatusCode::FORBIDDEN,
-                Json(json!({"error": "Invalid public token"})),
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Submission rejected", "code": "spam_rejected"})),
             ));
         }
+        // Email arrives from the mail provider's IPs, not the sender's
+        if let Some(ip) = client_ip(headers).filter(|_| source == PublicSource::Form) {
+            if !public_submit_interval_ok(&ip).await {
+                return Err((
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(json!({"error": "Please wait a few seconds before submitting again"})),
+                ));
+            }
+        }
     }
+    let is_spam = spam_filter && looks_like_spam(&body.title, body.description.as_deref());
 
-    let (_, resolved_assignees) =
-        resolve_auto_assign_assignees(&mut tx, project.0, &project.2, None).await?;
-
-    let next_number: (i64,) = sqlx::query_as(
-        r#"
-        SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
-        FROM issues
-        WHERE project_id = $1
-          AND display_id ~ ('^' || $2 || '-[0-9]+$')
-        "#,
-    )
-    .bind(project.0)
-    .bind(&project.1)
-    .fetch_one(tx.as_mut())
-    .await
-    .unwrap_or((1i64,));
+    // Flagged submissions skip auto-assign so nobody gets pinged for spam
+    let (resolved_assignees, tags) = if is_spam {
+        tracing::info!(project_id = %project.0, "public_submit.flagged_as_spam");
+        (Vec::new(), vec!["spam".to_string()])
+    } else {
+        let (_, assignees) =
+            resolve_auto_assign_assignees(&mut tx, project.0, &project.2, None).await?;
+        (assignees, Vec::new())
+    };
 
-    let display_id = format!("{}-{}", project.1, next_number.0);
+    let display_id = next_display_id(tx.as_mut(), project.0, &project.1).await?;
 
     // Validate attachments: max 5, each must have url/name/size/mime_type
     let attachments_json = if let Some(ref atts) = body.attachments {
@@ -2742,9 +3634,9 @@ pub async fn public_submit(
             project_id, display_id, title, description, type, status,
             priority, category,
             reporter_name, reporter_email, source, position, assignee_ids,
-            attachments
+            attachments, tags
         )
-        VALUES ($1, $2, $3, $4, $5, 'backlog', $6, $7, $8, $9, 'form', 99999, $10, $11)
+        VALUES ($1, $2, $3, $4, $5, 'backlog', $6, $7, $8, $9, $13, 99999, $10, $11, $12)
         RETURNING *
         "#,
     )
@@ -2759,13 +3651,80 @@ pub async fn public_submit(
     .bind(&body.reporter_email)
     .bind(&resolved_assignees)
     .bind(&attachments_json)
+    .bind(&tags)
+    .bind(source.as_str())
     .fetch_one(tx.as_mut())
     .await
     .map_err(|e| internal_err(e))?;
 
     tx.commit().await.map_err(|e| internal_err(e))?;
 
-    Ok(Json(ApiResponse::new(issue)))
+    broadcast_event(
+        sse_tx,
+        &project.2,
+        &BaatonEvent::IssueCreated((&issue).into()),
+    );
+
+    Ok(issue)
+}
+
+/// What a form submitter may see about their report — nothing internal.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PublicIssueStatus {
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// GET /public/{slug}/issues/{display_id} — status lookup for issues that came
+/// in through the public form. 404s for anything else, including projects with
+/// public submit disabled, so the endpoint can't be used to probe a board.
+/// Projects with a submit token require it as `?token=`, like submissions.
+pub async fn public_status(
+    State(pool): State<PgPool>,
+    Path((slug, display_id)): Path<(String, String)>,
+    Query(query): Query<PublicSubmitQuery>,
+) -> Result<Json<ApiResponse<PublicIssueStatus>>, (StatusCode, Json<serde_json::Value>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Issue not found"})),
+        )
+    };
+    let (project_id, submit_token): (Uuid, Option<String>) = sqlx::query_as(
+        "SELECT id, public_submit_token FROM projects WHERE slug = $1 AND public_submit_enabled = true AND archived_at IS NULL",
+    )
+    .bind(&slug)
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_err)?
+    .ok_or_else(not_found)?;
+
+    if !public_token_matches(submit_token.as_deref(), query.token.as_deref()) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Invalid public token"})),
+        ));
+    }
+
+    let status = sqlx::query_as::<_, PublicIssueStatus>(
+        r#"
+        SELECT display_id, title, status, created_at
+        FROM issues
+        WHERE project_id = $1
+          AND source IN ('form', 'email')
+          AND UPPER(display_id) = UPPER($2)
+        "#,
+    )
+    .bind(project_id)
+    .bind(&display_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_err)?
+    .ok_or_else(not_found)?;
+
+    Ok(Json(ApiResponse::new(status)))
 }
 
 // ─── Archive / Unarchive ──────────────────────────────
@@ -2847,8 +3806,7 @@ pub async fn archive(
     broadcast_event(
         &sse_tx,
         &org_id,
-        "issue.archived",
-        &serde_json::to_string(&issue).unwrap_or_default(),
+        &BaatonEvent::IssueArchived((&issue).into()),
     );
 
     Ok(Json(ApiResponse::new(issue)))
@@ -2931,9 +3889,360 @@ pub async fn unarchive(
     broadcast_event(
         &sse_tx,
         &org_id,
-        "issue.unarchived",
-        &serde_json::to_string(&issue).unwrap_or_default(),
+        &BaatonEvent::IssueUnarchived((&issue).into()),
     );
 
     Ok(Json(ApiResponse::new(issue)))
 }
+
+// ─── Snooze ───────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct SnoozeRequest {
+    /// RFC 3339 timestamp or `YYYY-MM-DD` (start of that day, UTC).
+    #[serde(deserialize_with = "crate::models::deserialize_snooze_until")]
+    pub until: chrono::DateTime<chrono::Utc>,
+}
+
+/// How often expired snoozes are swept.
+const SNOOZE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Set or clear an issue's snooze on behalf of `auth`, log it and broadcast
+/// the change. Shared by the snooze and unsnooze endpoints.
+async fn set_snooze(
+    pool: &PgPool,
+    sse_tx: &EventSender,
+    auth: &AuthUser,
+    id: Uuid,
+    until: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Issue, (StatusCode, Json<serde_json::Value>)> {
+    let (_current_org_id, org_ids) = require_user_org_scope(pool, auth).await?;
+
+    let org_id: String = sqlx::query_scalar(
+        "SELECT p.org_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = ANY($2)"
+    )
+    .bind(id)
+    .bind(&org_ids)
+    .fetch_optional(pool)
+    .await
+    .map_err(internal_err)?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))))?;
+
+    let issue = sqlx::query_as::<_, Issue>(
+        "UPDATE issues SET snoozed_until = $2, snoozed_by = $3, updated_at = now() WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .bind(until)
+    .bind(until.map(|_| auth.user_id.as_str()))
+    .fetch_one(pool)
+    .await
+    .map_err(internal_err)?;
+
+    {
+        let pool2 = pool.clone();
+        let uid = auth.user_id.clone();
+        let uname = auth.display_name.clone();
+        let pid = issue.project_id;
+        let oid = org_id.clone();
+        let new_value = until.map(|t| t.to_rfc3339());
+        tokio::spawn(async move {
+            log_activity(
+                &pool2,
+                &oid,
+                Some(pid),
+                Some(id),
+                &uid,
+                uname.as_deref(),
+                if new_value.is_some() {
+                    "issue_snoozed"
+                } else {
+                    "issue_unsnoozed"
+                },
+                Some("snoozed_until"),
+                None,
+                new_value.as_deref(),
+                None,
+            )
+            .await;
+        });
+    }
+
+    broadcast_event(sse_tx, &org_id, &BaatonEvent::IssueUpdated((&issue).into()));
+    Ok(issue)
+}
+
+/// POST /issues/{id}/snooze — hide the issue from default lists and boards
+/// until `until`, then resurface it and notify the caller.
+pub async fn snooze(
+    Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SnoozeRequest>,
+) -> Result<Json<ApiResponse<Issue>>, (StatusCode, Json<serde_json::Value>)> {
+    if body.until <= chrono::Utc::now() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "until must be in the future",
+                "code": "validation_error",
+                "field": "until",
+            })),
+        ));
+    }
+    let issue = set_snooze(&pool, &sse_tx, &auth, id, Some(body.until)).await?;
+    Ok(Json(ApiResponse::new(issue)))
+}
+
+/// DELETE /issues/{id}/snooze
+pub async fn unsnooze(
+    Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Issue>>, (StatusCode, Json<serde_json::Value>)> {
+    let issue = set_snooze(&pool, &sse_tx, &auth, id, None).await?;
+    Ok(Json(ApiResponse::new(issue)))
+}
+
+/// Background task: clear snoozes that have run out, notify whoever set them
+/// and broadcast the update so boards show the issue again.
+pub async fn snooze_worker(pool: PgPool, sse_tx: EventSender) {
+    tracing::info!("Snooze worker started");
+    loop {
+        tokio::time::sleep(SNOOZE_SWEEP_INTERVAL).await;
+        if let Err(e) = wake_snoozed_issues(&pool, &sse_tx).await {
+            tracing::warn!(error = %e, "snooze_worker: sweep failed");
+        }
+    }
+}
+
+async fn wake_snoozed_issues(pool: &PgPool, sse_tx: &EventSender) -> Result<(), sqlx::Error> {
+    use sqlx::{FromRow, Row};
+
+    let rows = sqlx::query(
+        r#"WITH due AS (
+             SELECT id, snoozed_by FROM issues
+             WHERE snoozed_until <= now()
+             ORDER BY snoozed_until
+             LIMIT 200
+             FOR UPDATE SKIP LOCKED
+           )
+           UPDATE issues i
+           SET snoozed_until = NULL, snoozed_by = NULL, updated_at = now()
+           FROM due, projects p
+           WHERE i.id = due.id AND p.id = i.project_id
+           RETURNING i.*, p.org_id, due.snoozed_by AS snoozer"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for row in rows {
+        let issue = Issue::from_row(&row)?;
+        let snoozer: Option<String> = row.try_get("snoozer")?;
+        let Some(org_id) = issue.org_id.clone() else {
+            continue;
+        };
+        if let Some(user_id) = snoozer {
+            let title = format!("{} is back: {}", issue.display_id, issue.title);
+            create_notification(
+                pool,
+                &user_id,
+                &org_id,
+                "snooze_ended",
+                Some(issue.id),
+                Some(issue.project_id),
+                &title,
+                None,
+            )
+            .await;
+        }
+        broadcast_event(sse_tx, &org_id, &BaatonEvent::IssueUpdated((&issue).into()));
+    }
+    Ok(())
+}
+
+// ─── Merge ────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct MergeIssueRequest {
+    pub into: Uuid,
+}
+
+/// POST /issues/{id}/merge — fold a duplicate into `into`: its comments,
+/// TLDRs and GitHub links move to the target, a "Merged from" comment is
+/// added, and the source is archived with `merged_into_id` set. Runs in one
+/// transaction that locks both issues first, so concurrent merges of either
+/// one wait and then see the result. Returns the target's detail.
+pub async fn merge(
+    Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<MergeIssueRequest>,
+) -> Result<Json<ApiResponse<IssueDetail>>, (StatusCode, Json<serde_json::Value>)> {
+    if body.into == id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Cannot merge an issue into itself"})),
+        ));
+    }
+    let (_current_org_id, org_ids) = require_user_org_scope(&pool, &auth).await?;
+
+    let mut tx = pool.begin().await.map_err(internal_err)?;
+
+    // Lock in id order so two merges of the same pair can't deadlock
+    let rows: Vec<(Uuid, String, String, Option<Uuid>, Uuid)> = sqlx::query_as(
+        "SELECT i.id, i.display_id, p.org_id, i.merged_into_id, i.project_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = ANY($1) AND p.org_id = ANY($2) ORDER BY i.id FOR UPDATE OF i",
+    )
+    .bind(vec![id, body.into])
+    .bind(&org_ids)
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+
+    let find = |issue_id: Uuid| rows.iter().find(|r| r.0 == issue_id);
+    let (Some(source), Some(target)) = (find(id), find(body.into)) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Issue not found"})),
+        ));
+    };
+    // Project scope covers the source via the path; the target comes from the body
+    if !auth.has_project_access(source.4) || !auth.has_project_access(target.4) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "API key does not have access to this project"})),
+        ));
+    }
+    if source.2 != target.2 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Both issues must belong to the same organization"})),
+        ));
+    }
+    if source.3.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({"error": "Issue has already been merged"})),
+        ));
+    }
+    if target.3.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({"error": "Target issue has been merged into another issue"})),
+        ));
+    }
+    let source_display_id = source.1.clone();
+    let target_display_id = target.1.clone();
+    let org_id = source.2.clone();
+
+    for table in [
+        "comments",
+        "tldrs",
+        "github_pr_links",
+        "github_commit_links",
+    ] {
+        sqlx::query(&format!(
+            "UPDATE {} SET issue_id = $1 WHERE issue_id = $2",
+            table
+        ))
+        .bind(body.into)
+        .bind(id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal_err)?;
+    }
+    // One GitHub issue per Baaton issue: the target keeps its own link if it has one
+    sqlx::query(
+        "UPDATE github_issue_links SET issue_id = $1 WHERE issue_id = $2 AND NOT EXISTS (SELECT 1 FROM github_issue_links WHERE issue_id = $1)",
+    )
+    .bind(body.into)
+    .bind(id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+
+    let author_name = auth
+        .display_name
+        .clone()
+        .unwrap_or_else(|| "Baaton".to_string());
+    sqlx::query(
+        "INSERT INTO comments (issue_id, author_id, author_name, body) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(body.into)
+    .bind(&auth.user_id)
+    .bind(&author_name)
+    .bind(format!("Merged from {}", source_display_id))
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+
+    let archived = sqlx::query_as::<_, Issue>(
+        "UPDATE issues SET archived = true, archived_at = now(), merged_into_id = $1, updated_at = now() WHERE id = $2 RETURNING *",
+    )
+    .bind(body.into)
+    .bind(id)
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(internal_err)?;
+
+    tx.commit().await.map_err(internal_err)?;
+
+    log_activity(
+        &pool,
+        &org_id,
+        Some(archived.project_id),
+        Some(body.into),
+        &auth.user_id,
+        auth.display_name.as_deref(),
+        "issue_merged",
+        None,
+        Some(&source_display_id),
+        Some(&target_display_id),
+        Some(json!({"source_id": id, "target_id": body.into})),
+    )
+    .await;
+
+    broadcast_event(
+        &sse_tx,
+        &org_id,
+        &BaatonEvent::IssueArchived((&archived).into()),
+    );
+
+    let detail = get_one(Extension(auth), State(pool), Path(body.into)).await?;
+    broadcast_event(
+        &sse_tx,
+        &org_id,
+        &BaatonEvent::IssueUpdated((&detail.0.data.issue).into()),
+    );
+    Ok(detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_add_working_days_skips_weekends_and_holidays() {
+        let fri = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let weekdays = [1, 2, 3, 4, 5];
+        // Unconfigured: plain calendar days
+        assert_eq!(
+            add_working_days(fri, 3, None, &[]),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+        );
+        // Mon-Fri: Fri + 3 → Wed
+        assert_eq!(
+            add_working_days(fri, 3, Some(&weekdays), &[]),
+            NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+        );
+        // Monday off too → Thu
+        let monday = [NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()];
+        assert_eq!(
+            add_working_days(fri, 3, Some(&weekdays), &monday),
+            NaiveDate::from_ymd_opt(2026, 10, 22).unwrap()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::{resolve_profile_cached, AuthUser};
+use crate::models::ApiResponse;
+
+#[derive(Debug, Serialize)]
+pub struct MeProfile {
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MeResponse {
+    pub user_id: String,
+    pub org_id: Option<String>,
+    pub org_slug: Option<String>,
+    pub org_role: Option<String>,
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+    /// "jwt" for Clerk sessions, "api_key" for `baa_` keys
+    pub auth_method: &'static str,
+    pub scoped_org_ids: Vec<String>,
+    /// Clerk profile (persisted/cached) — None for API keys
+    pub profile: Option<MeProfile>,
+}
+
+/// GET /me — authoritative view of the caller's auth context, so clients
+/// don't have to re-derive org role / display name from the raw JWT.
+pub async fn get_me(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Json<ApiResponse<MeResponse>> {
+    let is_api_key = auth.user_id.starts_with("apikey:");
+
+    let profile = if is_api_key {
+        None
+    } else {
+        resolve_profile_cached(Some(&pool), &auth.user_id)
+            .await
+            .map(|(display_name, email)| MeProfile {
+                display_name,
+                email,
+            })
+    };
+
+    let display_name = auth
+        .display_name
+        .clone()
+        .or_else(|| profile.as_ref().and_then(|p| p.display_name.clone()));
+    let email = auth
+        .email
+        .clone()
+        .or_else(|| profile.as_ref().and_then(|p| p.email.clone()));
+
+    Json(ApiResponse::new(MeResponse {
+        user_id: auth.user_id,
+        org_id: auth.org_id,
+        org_slug: auth.org_slug,
+        org_role: auth.org_role,
+        email,
+        display_name,
+        auth_method: if is_api_key { "api_key" } else { "jwt" },
+        scoped_org_ids: auth.scoped_org_ids,
+        profile,
+    }))
+}
+
+/// Views kept per user; older ones are dropped as new ones come in.
+const MAX_RECENT_ISSUES: i64 = 50;
+
+/// Remember that `user_id` opened `issue_id`, keeping only the latest
+/// `MAX_RECENT_ISSUES`. API keys have no history. Called fire-and-forget.
+pub async fn record_issue_view(pool: &PgPool, user_id: &str, issue_id: Uuid) {
+    if user_id.starts_with("apikey:") {
+        return;
+    }
+    let result = sqlx::query(
+        r#"INSERT INTO issue_views (user_id, issue_id) VALUES ($1, $2)
+           ON CONFLICT (user_id, issue_id) DO UPDATE SET viewed_at = now()"#,
+    )
+    .bind(user_id)
+    .bind(issue_id)
+    .execute(pool)
+    .await;
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "issue_views insert failed");
+        return;
+    }
+    let _ = sqlx::query(
+        r#"DELETE FROM issue_views
+           WHERE user_id = $1
+             AND issue_id NOT IN (
+               SELECT issue_id FROM issue_views WHERE user_id = $1
+               ORDER BY viewed_at DESC LIMIT $2
+             )"#,
+    )
+    .bind(user_id)
+    .bind(MAX_RECENT_ISSUES)
+    .execute(pool)
+    .await;
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentIssuesParams {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RecentIssue {
+    pub id: Uuid,
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+    pub priority: Option<String>,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub project_slug: String,
+    pub project_prefix: String,
+    pub viewed_at: DateTime<Utc>,
+}
+
+/// GET /me/recent-issues?limit=20 — issues the caller opened most recently
+/// in the current org, newest first.
+pub async fn recent_issues(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Query(params): Query<RecentIssuesParams>,
+) -> Result<Json<ApiResponse<Vec<RecentIssue>>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    let limit = params.limit.unwrap_or(20).clamp(1, MAX_RECENT_ISSUES);
+
+    let issues = sqlx::query_as::<_, RecentIssue>(
+        r#"SELECT i.id, i.display_id, i.title, i.status, i.priority,
+                  p.id AS project_id, p.name AS project_name, p.slug AS project_slug,
+                  p.prefix AS project_prefix, v.viewed_at
+           FROM issue_views v
+           JOIN issues i ON i.id = v.issue_id
+           JOIN projects p ON p.id = i.project_id
+           WHERE v.user_id = $1 AND p.org_id = $2
+           ORDER BY v.viewed_at DESC
+           LIMIT $3"#,
+    )
+    .bind(&auth.user_id)
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    })?;
+
+    Ok(Json(ApiResponse::new(issues)))
+}
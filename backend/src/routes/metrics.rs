@@ -1,10 +1,13 @@
 use axum::{extract::{Path, Query, State}, http::StatusCode, Extension, Json};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::PgPool;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::middleware::roles::{require_role, ROLE_ADMIN};
+use crate::middleware::scopes::has_scope;
 use crate::middleware::AuthUser;
 
 #[derive(Debug, Deserialize)]
@@ -345,3 +348,466 @@ pub async fn burndown(
         "burndown": data,
     })))
 }
+
+// ─── Cycle time ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct CycleTimeParams {
+    /// Inclusive start of the closed_at range (default: 30 days ago)
+    pub from: Option<NaiveDate>,
+    /// Inclusive end of the closed_at range (default: today)
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ClosedIssue {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    closed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct StatusTransition {
+    issue_id: Uuid,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// Hours spent in each status between creation and close, replayed from the
+/// issue's status transitions (oldest first). Time before the first logged
+/// transition counts toward that transition's old status. Empty when the
+/// issue has no logged transitions.
+fn time_in_status(
+    created_at: DateTime<Utc>,
+    closed_at: DateTime<Utc>,
+    transitions: &[&StatusTransition],
+) -> HashMap<String, f64> {
+    let mut hours: HashMap<String, f64> = HashMap::new();
+    let Some(first) = transitions.first() else {
+        return hours;
+    };
+
+    let mut cursor = created_at;
+    let mut current = first.old_value.clone();
+    for t in transitions {
+        let at = t.created_at.clamp(cursor, closed_at.max(cursor));
+        if let Some(status) = current.take() {
+            *hours.entry(status).or_default() += (at - cursor).num_seconds() as f64 / 3600.0;
+        }
+        cursor = at;
+        current = t.new_value.clone();
+    }
+    if let Some(status) = current {
+        if closed_at > cursor {
+            *hours.entry(status).or_default() += (closed_at - cursor).num_seconds() as f64 / 3600.0;
+        }
+    }
+    hours
+}
+
+/// avg / p50 / p90 (nearest rank) of `values`, rounded to 0.1h.
+fn duration_stats(mut values: Vec<f64>) -> serde_json::Value {
+    if values.is_empty() {
+        return json!({"avg_hours": null, "p50_hours": null, "p90_hours": null});
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let rank = |p: f64| values[((p * values.len() as f64).ceil() as usize).clamp(1, values.len()) - 1];
+    let round = |h: f64| (h * 10.0).round() / 10.0;
+    let avg = values.iter().sum::<f64>() / values.len() as f64;
+    json!({
+        "avg_hours": round(avg),
+        "p50_hours": round(rank(0.5)),
+        "p90_hours": round(rank(0.9)),
+    })
+}
+
+/// GET /projects/{id}/reports/cycle-time?from=2026-01-01&to=2026-01-31
+///
+/// Lead time and time-in-status for issues closed (not cancelled) in the range.
+/// Time-in-status is reconstructed from `status_changed` activity.
+pub async fn cycle_time(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<CycleTimeParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let to = params.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = params.from.unwrap_or(to - chrono::Duration::days(29));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({"error": "'from' must be on or before 'to'"}))));
+    }
+
+    let issues = sqlx::query_as::<_, ClosedIssue>(
+        r#"
+        SELECT id, created_at, closed_at
+        FROM issues
+        WHERE project_id = $1
+          AND closed_at IS NOT NULL
+          AND status <> 'cancelled'
+          AND closed_at >= $2::date AND closed_at < $3::date + 1
+        "#,
+    )
+    .bind(project_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("cycle_time issues query error: {e}");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch cycle time data"})))
+    })?;
+
+    let ids: Vec<Uuid> = issues.iter().map(|i| i.id).collect();
+    let transitions = sqlx::query_as::<_, StatusTransition>(
+        r#"
+        SELECT issue_id, old_value, new_value, created_at
+        FROM activity_log
+        WHERE issue_id = ANY($1) AND action = 'status_changed' AND created_at IS NOT NULL
+        ORDER BY issue_id, created_at ASC
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("cycle_time transitions query error: {e}");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch cycle time data"})))
+    })?;
+
+    let mut by_issue: HashMap<Uuid, Vec<&StatusTransition>> = HashMap::new();
+    for t in &transitions {
+        by_issue.entry(t.issue_id).or_default().push(t);
+    }
+
+    let mut lead_times = Vec::with_capacity(issues.len());
+    let mut per_status: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut without_history = 0;
+    for issue in &issues {
+        lead_times.push((issue.closed_at - issue.created_at).num_seconds().max(0) as f64 / 3600.0);
+        let history = by_issue.get(&issue.id).map(Vec::as_slice).unwrap_or_default();
+        let spent = time_in_status(issue.created_at, issue.closed_at, history);
+        if spent.is_empty() {
+            without_history += 1;
+        }
+        for (status, hours) in spent {
+            per_status.entry(status).or_default().push(hours);
+        }
+    }
+
+    let mut statuses: Vec<serde_json::Value> = per_status
+        .into_iter()
+        .map(|(status, hours)| {
+            let mut entry = duration_stats(hours.clone());
+            entry["status"] = json!(status);
+            entry["issue_count"] = json!(hours.len());
+            entry
+        })
+        .collect();
+    // Slowest status first: that's where work waits
+    statuses.sort_by(|a, b| {
+        b["avg_hours"].as_f64().unwrap_or(0.0).total_cmp(&a["avg_hours"].as_f64().unwrap_or(0.0))
+    });
+
+    // Time logged on the same issues, per person
+    let time_rows = sqlx::query_as::<_, (String, Option<String>, i64)>(
+        r#"
+        SELECT user_id, MAX(user_name), SUM(minutes)::bigint AS minutes
+        FROM time_entries
+        WHERE issue_id = ANY($1)
+        GROUP BY user_id
+        ORDER BY minutes DESC
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(&pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!("cycle_time time entries query error: {e}");
+        vec![]
+    });
+    let time_logged_minutes: i64 = time_rows.iter().map(|r| r.2).sum();
+    let time_by_user: Vec<serde_json::Value> = time_rows
+        .into_iter()
+        .map(|(user_id, user_name, minutes)| json!({"user_id": user_id, "user_name": user_name, "minutes": minutes}))
+        .collect();
+
+    Ok(Json(json!({
+        "project_id": project_id,
+        "from": from.to_string(),
+        "to": to.to_string(),
+        "issue_count": issues.len(),
+        "issues_without_history": without_history,
+        "lead_time": duration_stats(lead_times),
+        "statuses": statuses,
+        "time_logged_minutes": time_logged_minutes,
+        "time_by_user": time_by_user,
+    })))
+}
+
+// ─── Throughput ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ThroughputParams {
+    /// Completed weeks to report on (default 8, max 52)
+    pub weeks: Option<i64>,
+}
+
+/// GET /projects/{id}/reports/throughput?weeks=8
+///
+/// Issues closed per ISO week (count and summed estimate), by type. The
+/// current week is included but flagged `partial` and left out of averages.
+pub async fn throughput(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<ThroughputParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let weeks = params.weeks.unwrap_or(8).clamp(1, 52);
+    let current_week = Utc::now().date_naive().week(chrono::Weekday::Mon).first_day();
+    let first_week = current_week - chrono::Duration::weeks(weeks);
+
+    let rows = sqlx::query_as::<_, (NaiveDate, String, i64, i64)>(
+        r#"
+        SELECT date_trunc('week', closed_at)::date AS week,
+               issue_type,
+               COUNT(*) AS closed,
+               COALESCE(SUM(estimate), 0)::bigint AS points
+        FROM issues
+        WHERE project_id = $1
+          AND closed_at IS NOT NULL
+          AND status <> 'cancelled'
+          AND closed_at >= $2::date
+        GROUP BY 1, 2
+        "#,
+    )
+    .bind(project_id)
+    .bind(first_week)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("throughput query error: {e}");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch throughput data"})))
+    })?;
+
+    let mut by_week: HashMap<NaiveDate, Vec<(String, i64, i64)>> = HashMap::new();
+    for (week, issue_type, closed, points) in rows {
+        by_week.entry(week).or_default().push((issue_type, closed, points));
+    }
+
+    let mut series = Vec::with_capacity(weeks as usize + 1);
+    let (mut total_closed, mut total_points) = (0i64, 0i64);
+    let mut week = first_week;
+    while week <= current_week {
+        let partial = week == current_week;
+        let mut types = by_week.remove(&week).unwrap_or_default();
+        types.sort_by(|a, b| a.0.cmp(&b.0));
+        let closed: i64 = types.iter().map(|t| t.1).sum();
+        let points: i64 = types.iter().map(|t| t.2).sum();
+        if !partial {
+            total_closed += closed;
+            total_points += points;
+        }
+        let by_type: serde_json::Map<String, serde_json::Value> = types
+            .into_iter()
+            .map(|(t, c, p)| (t, json!({"closed": c, "points": p})))
+            .collect();
+        series.push(json!({
+            "week_start": week.to_string(),
+            "partial": partial,
+            "closed": closed,
+            "points": points,
+            "by_type": by_type,
+        }));
+        week += chrono::Duration::weeks(1);
+    }
+
+    let avg = |total: i64| ((total as f64 / weeks as f64) * 10.0).round() / 10.0;
+    Ok(Json(json!({
+        "project_id": project_id,
+        "weeks": weeks,
+        "average": {
+            "closed_per_week": avg(total_closed),
+            "points_per_week": avg(total_points),
+        },
+        "series": series,
+    })))
+}
+
+// ─── Assignee report ───────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct AssigneeReportParams {
+    /// Count issues closed on or after this date (default: 30 days ago)
+    pub since: Option<NaiveDate>,
+}
+
+/// GET /projects/{id}/reports/assignees?since=2026-01-01
+///
+/// Closed issues and summed estimates per assignee. Org admins only (API keys
+/// need `admin:full`), and only once the org enables `assignee_reports_enabled`.
+/// Multi-assignee issues are credited in full to each assignee.
+pub async fn assignees(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<AssigneeReportParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    match auth.api_key_permissions {
+        Some(ref permissions) if !has_scope(permissions, "admin:full") => {
+            return Err((StatusCode::FORBIDDEN, Json(json!({
+                "error": "API key is missing the 'admin:full' permission",
+                "code": "insufficient_scope",
+                "required": "admin:full"
+            }))));
+        }
+        Some(_) => {}
+        None => require_role(&auth, ROLE_ADMIN)?,
+    }
+
+    if !crate::routes::orgs::org_settings(&pool, org_id).await.assignee_reports_enabled {
+        return Err((StatusCode::FORBIDDEN, Json(json!({
+            "error": "Assignee reports are disabled for this organization",
+            "code": "feature_disabled",
+            "setting": "assignee_reports_enabled"
+        }))));
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let since = params.since.unwrap_or_else(|| Utc::now().date_naive() - chrono::Duration::days(30));
+
+    let rows = sqlx::query_as::<_, (String, i64, i64)>(
+        r#"
+        SELECT a.user_id, COUNT(*) AS closed, COALESCE(SUM(i.estimate), 0)::bigint AS points
+        FROM issues i, unnest(i.assignee_ids) AS a(user_id)
+        WHERE i.project_id = $1
+          AND i.closed_at IS NOT NULL
+          AND i.status <> 'cancelled'
+          AND i.closed_at >= $2::date
+        GROUP BY a.user_id
+        ORDER BY closed DESC, points DESC
+        "#,
+    )
+    .bind(project_id)
+    .bind(since)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("assignee report query error: {e}");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Failed to fetch assignee report"})))
+    })?;
+
+    let (closed_issues, unassigned): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COUNT(*) FILTER (WHERE COALESCE(cardinality(assignee_ids), 0) = 0)
+        FROM issues
+        WHERE project_id = $1 AND closed_at IS NOT NULL AND status <> 'cancelled' AND closed_at >= $2::date
+        "#,
+    )
+    .bind(project_id)
+    .bind(since)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or((0, 0));
+
+    let user_ids: Vec<String> = rows.iter().map(|r| r.0.clone()).collect();
+    let mut profiles = crate::middleware::resolve_profiles_cached(&pool, &user_ids).await;
+    let assignees: Vec<serde_json::Value> = rows
+        .into_iter()
+        .map(|(user_id, closed, points)| {
+            let (display_name, _) = profiles.remove(&user_id).unwrap_or_default();
+            json!({
+                "user_id": user_id,
+                "display_name": display_name,
+                "closed": closed,
+                "points": points,
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "project_id": project_id,
+        "since": since.to_string(),
+        "closed_issues": closed_issues,
+        "unassigned": unassigned,
+        "credit": "full_per_assignee",
+        "assignees": assignees,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_in_status_replays_transitions() {
+        let t0 = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let h = |n: i64| t0 + chrono::Duration::hours(n);
+        let issue_id = Uuid::nil();
+        let step = |old: &str, new: &str, at| StatusTransition {
+            issue_id,
+            old_value: Some(old.into()),
+            new_value: Some(new.into()),
+            created_at: at,
+        };
+        let log = [
+            step("todo", "in_progress", h(2)),
+            step("in_progress", "in_review", h(10)),
+            step("in_review", "in_progress", h(12)),
+            step("in_progress", "done", h(15)),
+        ];
+        let refs: Vec<&StatusTransition> = log.iter().collect();
+        let spent = time_in_status(t0, h(15), &refs);
+        assert_eq!(spent["todo"], 2.0);
+        assert_eq!(spent["in_progress"], 11.0);
+        assert_eq!(spent["in_review"], 2.0);
+        assert!(!spent.contains_key("done"));
+        assert!(time_in_status(t0, h(15), &[]).is_empty());
+    }
+}
@@ -1,7 +1,11 @@
-use axum::{Router, routing::{get, post, put, patch, delete}, middleware as axum_mw, extract::DefaultBodyLimit};
+use axum::{Router, routing::{get, post, put, patch, delete}, middleware as axum_mw, extract::DefaultBodyLimit, handler::Handler};
 use sqlx::PgPool;
 
-use crate::middleware::{auth_middleware, JwksKeys};
+use crate::middleware::{
+    auth_middleware, project_scope::enforce_api_key_project_scope, scopes::enforce_api_key_scopes,
+    JwksKeys,
+};
+use crate::middleware::rate_limit::{org_limit_ai_chat, org_limit_issue_create, org_limit_public_lookup};
 
 mod projects;
 pub(crate) mod issues;
@@ -9,22 +13,24 @@ mod comments;
 mod approvals;
 mod tldrs;
 mod tags;
+mod categories;
 mod invites;
 mod milestones;
 mod sprints;
 mod cycles;
 mod templates;
-mod ai;
+pub(crate) mod ai;
 pub mod ai_agent;
 pub mod ai_chat;
 pub mod ai_tools;
 pub mod activity;
 pub mod automations;
 pub mod github;
-mod sla;
+pub mod sla;
 mod views;
 pub mod notifications;
-mod api_keys;
+pub mod api_keys;
+mod audit;
 mod docs;
 pub mod webhooks;
 mod metrics;
@@ -39,24 +45,70 @@ pub mod agent_sessions;
 pub mod slack;
 pub(crate) mod admin;
 mod orgs;
+mod me;
+mod favorites;
+mod time_entries;
 mod initiatives;
 mod import_export;
 pub mod gamification;
 pub mod sse;
+pub mod presence;
 pub mod event_bus;
 pub mod project_context;
+pub mod project_members;
 pub mod project_templates;
 mod dashboard;
+pub mod health;
+
+/// 503 for a query Postgres cancelled on `statement_timeout` (SQLSTATE
+/// 57014); `None` for any other error.
+pub(crate) fn query_timeout_response(
+    e: &sqlx::Error,
+) -> Option<(axum::http::StatusCode, axum::Json<serde_json::Value>)> {
+    match e {
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("57014") => Some((
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            axum::Json(serde_json::json!({
+                "error": "The query took too long and was cancelled. Narrow the filters or try again shortly.",
+                "code": "query_timeout"
+            })),
+        )),
+        _ => None,
+    }
+}
+
+/// Body limit for every API route. Routes that take uploads raise it with
+/// their own `DefaultBodyLimit`, which wins because it sits closer to the
+/// handler.
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Body limit for attachment uploads: the file plus multipart framing.
+fn attachment_body_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(attachments::MAX_UPLOAD_BYTES + 64 * 1024)
+}
 
 pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
     let routes = Router::new()
+        // Current user
+        .route("/me", get(me::get_me))
+        .route("/me/recent-issues", get(me::recent_issues))
+        .route("/me/favorites", get(favorites::list_mine))
+        .route("/favorites", post(favorites::add).delete(favorites::remove))
+        .route("/me/notification-preferences", get(notifications::get_my_preferences).put(notifications::put_my_preferences))
         // Projects
         .route("/projects", get(projects::list).post(projects::create))
         .route("/projects/{id}", get(projects::get_one).patch(projects::update).delete(projects::remove))
+        .route("/projects/{id}/unarchive", post(projects::unarchive))
+        .route("/projects/{id}/statuses", get(projects::get_statuses).patch(projects::update_statuses))
+        .route("/projects/{id}/purge", post(projects::purge))
+        .route("/projects/{id}/duplicate", post(projects::duplicate))
         .route("/projects/{id}/auto-assign", get(projects::get_auto_assign_settings).patch(projects::update_auto_assign_settings))
         .route("/projects/{id}/refresh-github", post(projects::refresh_github))
         .route("/projects/{id}/issues", get(issues::list_by_project))
+        .route("/projects/{id}/issues/reindex", post(issues::reindex_positions))
         .route("/projects/{id}/tags", get(tags::list_by_project).post(tags::create))
+        .route("/projects/{id}/categories", get(categories::list_by_project).post(categories::create))
+        .route("/projects/{id}/categories/usage", get(categories::usage))
         .route("/projects/{id}/public-submit", get(projects::get_public_submit_settings).patch(projects::update_public_submit_settings))
         .route("/projects/by-slug/{slug}/board", get(projects::board_by_slug))
         // Milestones
@@ -68,7 +120,7 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/cycles/{id}", get(cycles::get_one).patch(cycles::update))
         .route("/cycles/{id}/complete", post(cycles::complete))
         // Issues
-        .route("/issues", get(issues::list_all).post(issues::create))
+        .route("/issues", get(issues::list_all).post(issues::create.layer(axum_mw::from_fn(org_limit_issue_create))))
         .route("/issues/mine", get(issues::list_mine))
         .route("/issues/batch", patch(issues::batch_update).delete(issues::batch_delete))
         .route("/search", get(issues::search))
@@ -77,19 +129,28 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/issues/{id}/position", patch(issues::update_position))
         .route("/issues/{id}/archive", post(issues::archive))
         .route("/issues/{id}/unarchive", post(issues::unarchive))
+        .route("/issues/{id}/snooze", post(issues::snooze).delete(issues::unsnooze))
+        .route("/issues/{id}/merge", post(issues::merge))
+        .route("/issues/{id}/presence", post(presence::heartbeat))
         .route("/issues/{id}/comments", get(comments::list_by_issue).post(comments::create))
         .route("/issues/{issue_id}/comments/{comment_id}", delete(comments::remove))
         // Approval workflow
         .route("/issues/{id}/approval-request", post(approvals::create_approval_request))
         .route("/issues/{id}/approval-response", post(approvals::create_approval_response))
-        .route("/issues/{id}/tldr", post(tldrs::create))
+        .route("/issues/{id}/tldr", get(tldrs::list).post(tldrs::create))
+        .route("/issues/{id}/time", get(time_entries::list).post(time_entries::create))
+        .route("/tldr/{id}", patch(tldrs::update))
         // Sub-issues (children)
         .route("/issues/{id}/children", get(issues::list_children))
+        .route("/issues/{id}/similar", get(issues::list_similar))
         // Relations (BAA-3)
         .route("/issues/{id}/relations", get(relations::list).post(relations::create))
         .route("/issues/{id}/relations/{relation_id}", delete(relations::remove))
+        .route("/issues/{id}/dependencies", get(relations::issue_dependency_graph).post(relations::add_dependency))
+        .route("/issues/{id}/dependencies/{depends_on_id}", delete(relations::remove_dependency))
         // Activity
         .route("/issues/{id}/activity", get(activity::list_by_issue))
+        .route("/issues/{id}/history", get(activity::issue_history))
         .route("/activity", get(activity::list_recent))
         // GitHub
         .route("/github/install", get(github::oauth::install_redirect))
@@ -99,21 +160,28 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/github/repos", get(github::repos::list_available))
         .route("/github/mappings", get(github::repos::list_mappings).post(github::repos::create_mapping))
         .route("/github/mappings/{id}", patch(github::repos::update_mapping).delete(github::repos::delete_mapping))
+        .route("/github/mappings/{id}/preview", post(github::repos::preview_mapping))
         .route("/issues/{id}/github", get(github::repos::get_issue_github_data))
         // AI
-        .route("/ai/chat", post(ai_chat::chat_handler))
-        .route("/ai/chat-legacy", post(ai::chat))
-        .route("/ai/key", get(ai::get_key))
+        .route("/ai/chat", post(ai_chat::chat_handler).layer(axum_mw::from_fn(org_limit_ai_chat)))
+        .route("/ai/chat-legacy", post(ai::chat).layer(axum_mw::from_fn(org_limit_ai_chat)))
         .route("/ai/pm-full-review", post(ai::pm_full_review))
-        .route("/ai/agent", post(ai_agent::agent_chat))
+        .route("/ai/usage", get(ai::org_ai_usage))
+        .route("/ai/pm-reviews", get(ai::list_pm_reviews))
+        .route("/ai/pm-reviews/{id}", get(ai::get_pm_review))
+        .route("/ai/agent", post(ai_agent::agent_chat).layer(axum_mw::from_fn(org_limit_ai_chat)))
         // Tags
         .route("/tags/{id}", delete(tags::remove))
+        .route("/categories/{id}", delete(categories::remove))
         // Milestones by ID
         .route("/milestones/{id}", get(milestones::get_one).put(milestones::update).delete(milestones::remove))
         // Templates (BAA-13)
         .route("/projects/{id}/templates", get(templates::list).post(templates::create))
         .route("/templates/{id}", get(templates::get_one).patch(templates::update).delete(templates::remove))
         // SLA (BAA-8)
+        .route("/projects/{id}/sla-policies", get(sla::list).post(sla::create))
+        .route("/sla-policies/{id}", delete(sla::remove))
+        // Deprecated pre-policy paths, kept for existing API clients
         .route("/projects/{id}/sla-rules", get(sla::list).post(sla::create))
         .route("/sla-rules/{id}", delete(sla::remove))
         .route("/projects/{id}/sla-stats", get(sla::stats))
@@ -126,6 +194,7 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/views", get(views::list).post(views::create))
         .route("/views/{id}", patch(views::update).delete(views::remove))
         .route("/views/{id}/issues", get(views::get_issues))
+        .route("/views/{id}/make-default", post(views::make_default))
         // Notifications
         .route("/notifications", get(notifications::list))
         .route("/notifications/count", get(notifications::count))
@@ -136,7 +205,9 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/api-keys", get(api_keys::list).post(api_keys::create))
         .route("/api-keys/{id}", patch(api_keys::update).delete(api_keys::remove))
         .route("/api-keys/{id}/regenerate", post(api_keys::regenerate))
+        .route("/api-keys/{id}/usage", get(api_keys::usage))
         .route("/invites", get(invites::list).post(invites::create))
+        .route("/invites/bulk", post(invites::bulk_create))
         // Docs (public, auth skipped via path prefix)
         .route("/public/docs", get(docs::api_docs))
         .route("/public/skill", get(docs::agent_skill))
@@ -144,12 +215,21 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/invite/{code}", get(invites::redirect_invite))
         // Public routes (auth skipped in middleware based on path)
         .route("/public/{slug}/submit", post(issues::public_submit)
-            .layer(DefaultBodyLimit::max(20 * 1024 * 1024))) // 20MB for base64 attachments
+            .layer(DefaultBodyLimit::max(20 * 1024 * 1024)) // 20MB for base64 attachments
+            .layer(axum_mw::from_fn(org_limit_issue_create))) // keyed on IP (no AuthUser)
+        .route("/public/{slug}/submit/email", post(email_intake::public_submit_email)
+            .layer(DefaultBodyLimit::max(5 * 1024 * 1024)) // attachments are ignored, but arrive in the body
+            .layer(axum_mw::from_fn(org_limit_issue_create)))
+        .route("/public/{slug}/email-intake", post(email_intake::intake)
+            .layer(axum_mw::from_fn(org_limit_issue_create)))
+        .route("/public/{slug}/issues/{display_id}", get(issues::public_status)
+            .layer(axum_mw::from_fn(org_limit_public_lookup)))
         .route("/public/resolve/{token}", get(projects::resolve_public_token))
         // Webhook (GitHub integration)
         .route("/webhooks/github", post(github::webhooks::handle))
-        // Slack webhook (public, no auth)
+        // Slack slash command (no Clerk auth; verified with Slack's signing secret)
         .route("/public/slack/command", post(slack::handle_command))
+        .route("/integrations/slack/command", post(slack::handle_command))
         // Baaton Webhooks (org-level event subscriptions)
         .route("/webhooks", get(webhooks::list).post(webhooks::create))
         .route("/webhooks/{id}", get(webhooks::get_one).patch(webhooks::update).delete(webhooks::remove))
@@ -160,6 +240,10 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         // Metrics & burndown
         .route("/metrics", get(metrics::get_metrics))
         .route("/projects/{id}/burndown", get(metrics::burndown))
+        .route("/projects/{id}/reports/cycle-time", get(metrics::cycle_time))
+        .route("/projects/{id}/reports/throughput", get(metrics::throughput))
+        .route("/projects/{id}/reports/assignees", get(metrics::assignees))
+        .route("/projects/{id}/reports/sla", get(sla::report))
         // Custom fields
         .route("/projects/{id}/custom-fields", get(custom_fields::list).post(custom_fields::create))
         .route("/custom-fields/{id}", patch(custom_fields::update).delete(custom_fields::remove))
@@ -167,11 +251,15 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         .route("/issues/{id}/triage", post(triage::analyze))
         .route("/triage", get(triage::list_untriaged))
         .route("/triage/batch", post(triage::batch_triage))
-        .route("/public/{slug}/email-intake", post(email_intake::intake))
-        .route("/issues/{id}/attachments", get(attachments::list).post(attachments::create))
+        .route("/issues/{id}/attachments", get(attachments::list).post(attachments::create
+            .layer(attachment_body_limit())))
         .route("/issues/{id}/attachments/{att_id}", delete(attachments::remove))
         // Org members
         .route("/orgs/{org_id}/members", get(orgs::list_members))
+        .route("/audit", get(audit::list))
+        .route("/org/role-requirements", get(orgs::get_role_requirements).put(orgs::update_role_requirements))
+        .route("/org", get(orgs::get_current))
+        .route("/org/settings", get(orgs::get_settings).patch(orgs::update_settings))
         // Admin (BAA-1)
         .route("/admin/orgs/{id}/plan", patch(admin::set_plan))
         .route("/admin/superadmin/check", get(admin::check_superadmin))
@@ -207,6 +295,8 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
         // Project Context
         .route("/projects/{id}/context", get(project_context::get_or_create).patch(project_context::update))
         .route("/projects/{id}/context/append", post(project_context::append))
+        .route("/projects/{id}/members", get(project_members::list).post(project_members::add))
+        .route("/projects/{id}/members/{user_id}", delete(project_members::remove))
         // Dependency Graph
         .route("/projects/{id}/dependency-graph", get(relations::dependency_graph))
         // Project Templates
@@ -223,8 +313,51 @@ pub fn api_router(pool: PgPool, jwks: JwksKeys) -> Router {
 
     // Apply auth middleware and inject JWKS state
     // Layer order: last added runs first (outer). Auth needs JWKS, so JWKS must be outer.
+    // API-key scope enforcement runs after auth has attached the AuthUser.
     routes
+        .layer(axum_mw::from_fn(enforce_api_key_project_scope))
+        .layer(axum_mw::from_fn(enforce_api_key_scopes))
         .layer(axum_mw::from_fn(auth_middleware))
         .layer(axum::Extension(jwks))
+        .layer(DefaultBodyLimit::max(DEFAULT_BODY_LIMIT))
         .with_state(pool)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Multipart, http::{Request, StatusCode}};
+    use tower::ServiceExt;
+
+    async fn read_upload(mut multipart: Multipart) -> Result<String, StatusCode> {
+        let mut total = 0;
+        while let Some(field) = multipart.next_field().await.map_err(|e| e.status())? {
+            total += field.bytes().await.map_err(|e| e.status())?.len();
+        }
+        Ok(total.to_string())
+    }
+
+    fn multipart_request(uri: &str, size: usize) -> Request<Body> {
+        let mut body = b"--X\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.bin\"\r\n\r\n".to_vec();
+        body.extend(std::iter::repeat_n(b'a', size));
+        body.extend_from_slice(b"\r\n--X--\r\n");
+        Request::post(uri)
+            .header("content-type", "multipart/form-data; boundary=X")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_attachment_uploads_exceed_default_body_limit() {
+        let app = Router::new()
+            .route("/upload", post(read_upload.layer(attachment_body_limit())))
+            .route("/other", post(read_upload))
+            .layer(DefaultBodyLimit::max(DEFAULT_BODY_LIMIT));
+        let size = 5 * 1024 * 1024;
+
+        let res = app.clone().oneshot(multipart_request("/upload", size)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = app.oneshot(multipart_request("/other", size)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -29,11 +29,44 @@ pub struct Notification {
 pub struct NotificationPreference {
     pub user_id: String,
     pub org_id: String,
-    #[sqlx(rename = "type")]
+    #[sqlx(rename = "workflow")]
     pub pref_type: String,
+    pub channel: String,
+    pub enabled: bool,
+}
+
+/// One entry of `GET/PUT /me/notification-preferences`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreferenceSetting {
+    pub workflow: String,
+    pub channel: String,
     pub enabled: bool,
 }
 
+/// Channel for Novu workflows (email and whatever else the workflow sends).
+pub const CHANNEL_EMAIL: &str = "email";
+/// Channel for the in-app inbox (`issue_notifications`).
+pub const CHANNEL_IN_APP: &str = "in_app";
+
+/// Every preference a user can set: (workflow, channel, enabled by default).
+/// The digest is the one opt-in; everything else is on until muted.
+pub const PREFERENCE_CATALOG: &[(&str, &str, bool)] = &[
+    ("issue-assigned", CHANNEL_EMAIL, true),
+    ("urgent-issue-created", CHANNEL_EMAIL, true),
+    ("status-changed", CHANNEL_EMAIL, true),
+    ("comment-on-assigned-issue", CHANNEL_EMAIL, true),
+    ("mentioned-in-comment", CHANNEL_EMAIL, true),
+    ("sla-breach-imminent", CHANNEL_EMAIL, true),
+    (DAILY_DIGEST, CHANNEL_EMAIL, false),
+    ("assigned", CHANNEL_IN_APP, true),
+    ("mentioned", CHANNEL_IN_APP, true),
+    ("status_changed", CHANNEL_IN_APP, true),
+    ("comment_added", CHANNEL_IN_APP, true),
+    ("issue_created", CHANNEL_IN_APP, true),
+    ("sla_breach", CHANNEL_IN_APP, true),
+    ("snooze_ended", CHANNEL_IN_APP, true),
+];
+
 #[derive(Debug, Deserialize)]
 pub struct ListParams {
     pub unread: Option<bool>,
@@ -49,8 +82,38 @@ pub struct UpdatePreference {
 
 // ─── Helper ───────────────────────────────────────────
 
-/// Fire-and-forget helper to insert a notification row.
-/// Designed to be called inside a `tokio::spawn` block.
+/// Drop the users who muted `workflow` on `channel` (directly or through an
+/// `all` row). No row means enabled.
+pub async fn filter_muted(
+    pool: &PgPool,
+    org_id: &str,
+    workflow: &str,
+    channel: &str,
+    user_ids: Vec<String>,
+) -> Vec<String> {
+    if user_ids.is_empty() {
+        return user_ids;
+    }
+    let muted: Vec<String> = sqlx::query_scalar(
+        r#"SELECT DISTINCT user_id FROM notification_preferences
+           WHERE org_id = $1 AND workflow = $2 AND channel IN ('all', $3)
+             AND enabled = false AND user_id = ANY($4)"#
+    )
+    .bind(org_id)
+    .bind(workflow)
+    .bind(channel)
+    .bind(&user_ids)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "notification_preferences lookup failed");
+        vec![]
+    });
+    user_ids.into_iter().filter(|id| !muted.contains(id)).collect()
+}
+
+/// Fire-and-forget helper to insert a notification row, unless the user muted
+/// that type in-app. Designed to be called inside a `tokio::spawn` block.
 pub async fn create_notification(
     pool: &PgPool,
     user_id: &str,
@@ -63,7 +126,12 @@ pub async fn create_notification(
 ) {
     let result = sqlx::query(
         r#"INSERT INTO issue_notifications (user_id, org_id, type, issue_id, project_id, title, body)
-           VALUES ($1, $2, $3, $4, $5, $6, $7)"#
+           SELECT $1, $2, $3, $4, $5, $6, $7
+           WHERE NOT EXISTS (
+             SELECT 1 FROM notification_preferences
+             WHERE user_id = $1 AND org_id = $2 AND workflow = $3
+               AND channel IN ('all', 'in_app') AND enabled = false
+           )"#
     )
     .bind(user_id)
     .bind(org_id)
@@ -214,9 +282,9 @@ pub async fn update_preferences(
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
     let pref = sqlx::query_as::<_, NotificationPreference>(
-        r#"INSERT INTO notification_preferences (user_id, org_id, type, enabled)
+        r#"INSERT INTO notification_preferences (user_id, org_id, workflow, enabled)
            VALUES ($1, $2, $3, $4)
-           ON CONFLICT (user_id, org_id, type)
+           ON CONFLICT (user_id, org_id, workflow, channel)
            DO UPDATE SET enabled = EXCLUDED.enabled
            RETURNING *"#
     )
@@ -230,3 +298,270 @@ pub async fn update_preferences(
 
     Ok(Json(ApiResponse::new(pref)))
 }
+
+/// The caller's settings for every catalogued workflow/channel, defaults
+/// filled in. A channel-specific row beats an `all` row.
+async fn effective_preferences(pool: &PgPool, user_id: &str, org_id: &str) -> Result<Vec<PreferenceSetting>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String, bool)>(
+        "SELECT workflow, channel, enabled FROM notification_preferences WHERE user_id = $1 AND org_id = $2"
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PREFERENCE_CATALOG.iter().map(|(workflow, channel, default)| {
+        let stored = |ch: &str| rows.iter()
+            .find(|(w, c, _)| w == workflow && c == ch)
+            .map(|(_, _, enabled)| *enabled);
+        PreferenceSetting {
+            workflow: workflow.to_string(),
+            channel: channel.to_string(),
+            enabled: stored(channel).or_else(|| stored("all")).unwrap_or(*default),
+        }
+    }).collect())
+}
+
+/// GET /me/notification-preferences
+pub async fn get_my_preferences(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<Vec<PreferenceSetting>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let prefs = effective_preferences(&pool, &auth.user_id, org_id).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(prefs)))
+}
+
+/// PUT /me/notification-preferences — upserts the listed settings; the rest
+/// are left as they are. Returns the full effective list.
+pub async fn put_my_preferences(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Json(body): Json<Vec<PreferenceSetting>>,
+) -> Result<Json<ApiResponse<Vec<PreferenceSetting>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    for pref in &body {
+        if !PREFERENCE_CATALOG.iter().any(|(w, c, _)| *w == pref.workflow && *c == pref.channel) {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": format!("Unknown notification preference '{}' on channel '{}'", pref.workflow, pref.channel),
+                "code": "unknown_preference",
+                "field": "workflow"
+            }))));
+        }
+    }
+
+    let internal = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()})));
+    let mut tx = pool.begin().await.map_err(internal)?;
+    for pref in &body {
+        sqlx::query(
+            r#"INSERT INTO notification_preferences (user_id, org_id, workflow, channel, enabled)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (user_id, org_id, workflow, channel)
+               DO UPDATE SET enabled = EXCLUDED.enabled"#
+        )
+        .bind(&auth.user_id)
+        .bind(org_id)
+        .bind(&pref.workflow)
+        .bind(&pref.channel)
+        .bind(pref.enabled)
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal)?;
+    }
+    tx.commit().await.map_err(internal)?;
+
+    let prefs = effective_preferences(&pool, &auth.user_id, org_id).await.map_err(internal)?;
+    Ok(Json(ApiResponse::new(prefs)))
+}
+
+// ─── Daily digest ─────────────────────────────────────
+
+/// Preference type users opt into for the daily digest (no row = no digest).
+pub const DAILY_DIGEST: &str = "daily_digest";
+
+#[derive(Debug, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+struct DigestItem {
+    issue_id: String,
+    issue_title: String,
+    actor_name: Option<String>,
+    old_status: Option<String>,
+    new_status: Option<String>,
+}
+
+/// Background task: once a day, after `DIGEST_HOUR_UTC` (default 8), send each
+/// user with `daily_digest` enabled a Novu `daily-digest` summary of the last
+/// 24h. `notification_digests` records who was sent what day, so restarts and
+/// replicas don't send twice.
+pub async fn digest_worker(novu: crate::novu::NovuClient, pool: PgPool) {
+    let send_hour: u32 = std::env::var("DIGEST_HOUR_UTC").ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|h| *h < 24)
+        .unwrap_or(8);
+    tracing::info!(send_hour, "Daily digest worker started");
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(15 * 60)).await;
+        if chrono::Timelike::hour(&Utc::now()) < send_hour {
+            continue;
+        }
+
+        let due = sqlx::query_as::<_, (String, String)>(
+            r#"SELECT p.user_id, p.org_id FROM notification_preferences p
+               WHERE p.workflow = $1 AND p.channel IN ('all', 'email')
+                 AND NOT EXISTS (
+                   SELECT 1 FROM notification_digests d
+                   WHERE d.user_id = p.user_id AND d.org_id = p.org_id
+                     AND d.digest_date = (now() AT TIME ZONE 'UTC')::date
+                 )
+               GROUP BY p.user_id, p.org_id
+               HAVING bool_and(p.enabled)"#
+        )
+        .bind(DAILY_DIGEST)
+        .fetch_all(&pool)
+        .await;
+
+        let due = match due {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::error!(error = %e, "digest: query failed");
+                continue;
+            }
+        };
+
+        for (user_id, org_id) in due {
+            send_digest(&novu, &pool, &user_id, &org_id).await;
+        }
+    }
+}
+
+async fn send_digest(novu: &crate::novu::NovuClient, pool: &PgPool, user_id: &str, org_id: &str) {
+    // Claim today's digest first — another replica may be on the same user
+    let claimed = sqlx::query(
+        r#"INSERT INTO notification_digests (user_id, org_id, digest_date)
+           VALUES ($1, $2, (now() AT TIME ZONE 'UTC')::date)
+           ON CONFLICT DO NOTHING"#
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .execute(pool)
+    .await;
+    match claimed {
+        Ok(r) if r.rows_affected() == 1 => {}
+        Ok(_) => return,
+        Err(e) => {
+            tracing::error!(error = %e, user_id, "digest: claim failed");
+            return;
+        }
+    }
+
+    let summary = match digest_summary(pool, user_id, org_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!(error = %e, user_id, "digest: summary failed");
+            // Release the claim so the next tick retries
+            let _ = sqlx::query(
+                "DELETE FROM notification_digests WHERE user_id = $1 AND org_id = $2 AND digest_date = (now() AT TIME ZONE 'UTC')::date"
+            )
+            .bind(user_id)
+            .bind(org_id)
+            .execute(pool)
+            .await;
+            return;
+        }
+    };
+    let Some((assigned, mentions, status_changes)) = summary else {
+        return;
+    };
+
+    let Some(sub) = crate::novu::resolve_subscribers(pool, vec![user_id.to_string()]).await.pop() else {
+        return;
+    };
+    novu.trigger(
+        "daily-digest",
+        &sub.id,
+        sub.email.as_deref(),
+        sub.name.as_deref(),
+        json!({
+            "assignedCount": assigned.len(),
+            "mentionCount": mentions.len(),
+            "statusChangeCount": status_changes.len(),
+            "assigned": assigned,
+            "mentions": mentions,
+            "statusChanges": status_changes,
+        }),
+    ).await;
+}
+
+type DigestSummary = (Vec<DigestItem>, Vec<DigestItem>, Vec<DigestItem>);
+
+/// Issues assigned to the user, @mentions of them and status changes on their
+/// issues over the last 24h, excluding their own actions. `None` when there's
+/// nothing to report.
+async fn digest_summary(pool: &PgPool, user_id: &str, org_id: &str) -> Result<Option<DigestSummary>, sqlx::Error> {
+    let assigned = sqlx::query_as::<_, DigestItem>(
+        r#"SELECT i.display_id AS issue_id, i.title AS issue_title, a.user_name AS actor_name,
+                  NULL::text AS old_status, NULL::text AS new_status
+           FROM activity_log a JOIN issues i ON i.id = a.issue_id
+           WHERE a.org_id = $2 AND a.user_id <> $1
+             AND a.created_at > now() - interval '24 hours'
+             AND (
+               (a.action = 'assignee_changed'
+                AND $1 = ANY(string_to_array(a.new_value, ','))
+                AND NOT $1 = ANY(string_to_array(COALESCE(a.old_value, ''), ',')))
+               OR (a.action = 'issue_created' AND $1 = ANY(i.assignee_ids))
+             )
+           ORDER BY a.created_at DESC
+           LIMIT 20"#
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    // activity_log only keeps a truncated comment preview, so mentions come
+    // from the comments themselves
+    let mentions = sqlx::query_as::<_, DigestItem>(
+        r#"SELECT i.display_id AS issue_id, i.title AS issue_title, c.author_name AS actor_name,
+                  NULL::text AS old_status, NULL::text AS new_status
+           FROM comments c
+           JOIN issues i ON i.id = c.issue_id
+           JOIN projects p ON p.id = i.project_id
+           WHERE p.org_id = $2 AND c.author_id <> $1
+             AND c.created_at > now() - interval '24 hours'
+             AND c.body ~ ('@' || $1 || '([^a-zA-Z0-9]|$)')
+           ORDER BY c.created_at DESC
+           LIMIT 20"#
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    let status_changes = sqlx::query_as::<_, DigestItem>(
+        r#"SELECT i.display_id AS issue_id, i.title AS issue_title, a.user_name AS actor_name,
+                  a.old_value AS old_status, a.new_value AS new_status
+           FROM activity_log a JOIN issues i ON i.id = a.issue_id
+           WHERE a.org_id = $2 AND a.user_id <> $1
+             AND a.created_at > now() - interval '24 hours'
+             AND a.action = 'status_changed'
+             AND $1 = ANY(i.assignee_ids)
+           ORDER BY a.created_at DESC
+           LIMIT 20"#
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    if assigned.is_empty() && mentions.is_empty() && status_changes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some((assigned, mentions, status_changes)))
+}
@@ -1,7 +1,12 @@
-use axum::{extract::{Extension, Path}, http::StatusCode, Json};
+use axum::{extract::{Extension, Path, State}, http::{HeaderMap, StatusCode}, Json};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::collections::HashMap;
 
+use crate::middleware::roles::{require_role, required_role, RoleAction, ROLE_ADMIN, ROLE_MEMBER};
 use crate::middleware::AuthUser;
+use crate::models::ApiResponse;
 use super::admin::fetch_org_members;
 
 /// GET /orgs/{org_id}/members — list members of a specific org via Clerk API.
@@ -25,3 +30,199 @@ pub async fn list_members(
     let members = fetch_org_members(&org_id).await;
     Ok(Json(json!({ "data": members })))
 }
+
+#[derive(Debug, Serialize)]
+pub struct RoleRequirement {
+    pub action: &'static str,
+    pub role: String,
+}
+
+async fn role_requirements(pool: &PgPool, org_id: &str) -> Vec<RoleRequirement> {
+    let mut out = Vec::new();
+    for action in RoleAction::ALL {
+        out.push(RoleRequirement { action: action.key(), role: required_role(pool, org_id, *action).await });
+    }
+    out
+}
+
+/// GET /org/role-requirements — the role each guarded action needs in the current org.
+pub async fn get_role_requirements(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<Vec<RoleRequirement>>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    Ok(Json(ApiResponse::new(role_requirements(&pool, org_id).await)))
+}
+
+/// PUT /org/role-requirements — `{ "projects.delete": "org:member", ... }`.
+/// Only org admins can change requirements, whatever they are set to.
+pub async fn update_role_requirements(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Json(body): Json<HashMap<String, String>>,
+) -> Result<Json<ApiResponse<Vec<RoleRequirement>>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    if auth.api_key_permissions.is_some() {
+        return Err((StatusCode::FORBIDDEN, Json(json!({
+            "error": "API keys cannot change role requirements",
+            "code": "forbidden"
+        }))));
+    }
+    require_role(&auth, ROLE_ADMIN)?;
+
+    for (action, role) in &body {
+        if RoleAction::from_key(action).is_none() {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": format!("Unknown action '{}'", action),
+                "field": "action"
+            }))));
+        }
+        if role != ROLE_ADMIN && role != ROLE_MEMBER {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": format!("Invalid role '{}'. Accepted values: {}, {}", role, ROLE_ADMIN, ROLE_MEMBER),
+                "field": "role"
+            }))));
+        }
+    }
+
+    for (action, role) in &body {
+        sqlx::query(
+            r#"INSERT INTO org_role_requirements (org_id, action, role, updated_by)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (org_id, action) DO UPDATE
+               SET role = EXCLUDED.role, updated_by = EXCLUDED.updated_by, updated_at = now()"#
+        )
+        .bind(org_id)
+        .bind(action)
+        .bind(role)
+        .bind(&auth.user_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+    }
+
+    Ok(Json(ApiResponse::new(role_requirements(&pool, org_id).await)))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CurrentOrg {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub image_url: Option<String>,
+    /// False when Clerk couldn't be reached and `name` is a local fallback.
+    pub resolved: bool,
+}
+
+/// GET /org — the active org's name, slug and logo for the UI header.
+/// Resolved via Clerk (cached); falls back to the stored row, then to the
+/// token's slug, when Clerk is unavailable or `CLERK_SECRET_KEY` is unset.
+pub async fn get_current(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<CurrentOrg>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    if let Some((name, slug, image_url)) = crate::routes::dashboard::fetch_org_metadata(org_id).await {
+        return Ok(Json(ApiResponse::new(CurrentOrg {
+            id: org_id.to_string(), name, slug, image_url, resolved: true,
+        })));
+    }
+
+    let stored: Option<(String, String)> = sqlx::query_as(
+        "SELECT name, slug FROM organizations WHERE id = $1"
+    )
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "org lookup failed");
+        None
+    });
+    let (name, slug) = stored.unwrap_or_else(|| {
+        let slug = auth.org_slug.clone().unwrap_or_else(|| org_id.to_string());
+        (slug.clone(), slug)
+    });
+
+    Ok(Json(ApiResponse::new(CurrentOrg {
+        id: org_id.to_string(), name, slug, image_url: None, resolved: false,
+    })))
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrgSettings {
+    /// Per-assignee completion report (`/projects/{id}/reports/assignees`).
+    pub assignee_reports_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrgSettings {
+    pub assignee_reports_enabled: Option<bool>,
+}
+
+/// Org settings, with defaults for orgs that have no `organizations` row yet.
+pub async fn org_settings(pool: &PgPool, org_id: &str) -> OrgSettings {
+    let enabled: Option<bool> = sqlx::query_scalar(
+        "SELECT assignee_reports_enabled FROM organizations WHERE id = $1"
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "org settings lookup failed");
+        None
+    });
+    OrgSettings { assignee_reports_enabled: enabled.unwrap_or(false) }
+}
+
+/// GET /org/settings
+pub async fn get_settings(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+) -> Result<Json<ApiResponse<OrgSettings>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    Ok(Json(ApiResponse::new(org_settings(&pool, org_id).await)))
+}
+
+/// PATCH /org/settings — org admins only; API keys cannot change settings.
+pub async fn update_settings(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateOrgSettings>,
+) -> Result<Json<ApiResponse<OrgSettings>>, (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    if auth.api_key_permissions.is_some() {
+        return Err((StatusCode::FORBIDDEN, Json(json!({
+            "error": "API keys cannot change org settings",
+            "code": "forbidden"
+        }))));
+    }
+    require_role(&auth, ROLE_ADMIN)?;
+
+    if let Some(enabled) = body.assignee_reports_enabled {
+        crate::routes::admin::ensure_org(&pool, org_id, auth.org_slug.as_deref())
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+        sqlx::query("UPDATE organizations SET assignee_reports_enabled = $2 WHERE id = $1")
+            .bind(org_id)
+            .bind(enabled)
+            .execute(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+        crate::routes::audit::record(
+            &pool, &auth, &headers, "org.settings_updated", "org", org_id,
+            json!({"assignee_reports_enabled": enabled}),
+        ).await;
+    }
+
+    Ok(Json(ApiResponse::new(org_settings(&pool, org_id).await)))
+}
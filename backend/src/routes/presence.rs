@@ -0,0 +1,127 @@
+//! Issue presence — who is currently viewing an issue.
+//!
+//! Clients send a heartbeat every ~15s while an issue is open. Each heartbeat
+//! refreshes an in-memory entry and broadcasts an `issue.presence` SSE event to
+//! the org; clients filter by `issue_id` and drop viewers they haven't heard
+//! from within `PRESENCE_TTL`. State is per-instance and lost on restart,
+//! which is fine for a "who's here" hint.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::ApiResponse;
+use crate::routes::issues::require_user_org_scope;
+use crate::routes::sse::{broadcast_event, BaatonEvent, EventSender, PresenceEnvelope};
+
+/// A viewer is dropped once no heartbeat arrived for this long.
+const PRESENCE_TTL: Duration = Duration::from_secs(45);
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(30);
+
+struct PresenceEntry {
+    display_name: Option<String>,
+    last_seen: Instant,
+}
+
+/// (issue_id, user_id) → entry
+type PresenceMap = HashMap<(Uuid, String), PresenceEntry>;
+
+static PRESENCE: OnceLock<RwLock<PresenceMap>> = OnceLock::new();
+
+fn presence() -> &'static RwLock<PresenceMap> {
+    PRESENCE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+#[derive(Debug, Serialize)]
+pub struct Viewer {
+    pub user_id: String,
+    pub display_name: Option<String>,
+}
+
+/// POST /issues/{id}/presence — heartbeat. Returns the issue's current viewers.
+pub async fn heartbeat(
+    Extension(auth): Extension<AuthUser>,
+    Extension(sse_tx): Extension<EventSender>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Viewer>>>, (StatusCode, Json<serde_json::Value>)> {
+    let (_current_org_id, org_ids) = require_user_org_scope(&pool, &auth).await?;
+
+    let org_id: String = sqlx::query_scalar(
+        "SELECT p.org_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = ANY($2)",
+    )
+    .bind(issue_id)
+    .bind(&org_ids)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Issue not found"})),
+        )
+    })?;
+
+    let display_name = auth.display_name.clone().or_else(|| auth.email.clone());
+
+    let viewers = {
+        let mut map = presence().write().await;
+        map.insert(
+            (issue_id, auth.user_id.clone()),
+            PresenceEntry {
+                display_name: display_name.clone(),
+                last_seen: Instant::now(),
+            },
+        );
+        map.iter()
+            .filter(|((id, _), entry)| *id == issue_id && entry.last_seen.elapsed() < PRESENCE_TTL)
+            .map(|((_, user_id), entry)| Viewer {
+                user_id: user_id.clone(),
+                display_name: entry.display_name.clone(),
+            })
+            .collect()
+    };
+
+    broadcast_event(
+        &sse_tx,
+        &org_id,
+        &BaatonEvent::Presence(PresenceEnvelope {
+            issue_id,
+            user_id: auth.user_id.clone(),
+            display_name,
+        }),
+    );
+
+    Ok(Json(ApiResponse::new(viewers)))
+}
+
+/// Background task: evict viewers whose heartbeat expired.
+pub async fn cleanup_task() {
+    let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+    loop {
+        interval.tick().await;
+        let mut map = presence().write().await;
+        let before = map.len();
+        map.retain(|_, entry| entry.last_seen.elapsed() < PRESENCE_TTL);
+        let evicted = before - map.len();
+        if evicted > 0 {
+            tracing::debug!(evicted, "presence: evicted stale viewers");
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use axum::{extract::{Path, State}, http::StatusCode, Extension, Json};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::roles::{require_action_role, RoleAction};
+use crate::middleware::AuthUser;
+use crate::models::{AddProjectMember, ApiResponse, ProjectMember};
+
+const VALID_ROLES: &[&str] = &["admin", "member"];
+
+type ApiError = (StatusCode, Json<serde_json::Value>);
+
+fn internal(e: sqlx::Error) -> ApiError {
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()})))
+}
+
+/// Clerk org roles look like `org:admin` / `org:member`.
+fn project_role_for(clerk_role: &str) -> &'static str {
+    if clerk_role.contains("admin") { "admin" } else { "member" }
+}
+
+/// Verify the project belongs to the caller's org and return that org.
+async fn scoped_project<'a>(pool: &PgPool, auth: &'a AuthUser, project_id: Uuid) -> Result<&'a str, ApiError> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)"
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_one(pool)
+    .await
+    .map_err(internal)?;
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+    Ok(org_id)
+}
+
+/// First read of a roster copies the Clerk org membership into it. Nothing is
+/// marked seeded when Clerk returns no one (not configured / unreachable), so
+/// the next read tries again.
+async fn seed_from_org(pool: &PgPool, org_id: &str, project_id: Uuid) -> Result<(), ApiError> {
+    let seeded: bool = sqlx::query_scalar(
+        "SELECT members_seeded_at IS NOT NULL FROM projects WHERE id = $1 AND org_id = $2"
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(internal)?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))))?;
+    if seeded {
+        return Ok(());
+    }
+
+    let org_members = crate::routes::admin::fetch_org_members(org_id).await;
+    if org_members.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await.map_err(internal)?;
+    let claimed = sqlx::query(
+        "UPDATE projects SET members_seeded_at = now() WHERE id = $1 AND members_seeded_at IS NULL"
+    )
+    .bind(project_id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal)?;
+    // Another request seeded it in the meantime
+    if claimed.rows_affected() == 0 {
+        return Ok(());
+    }
+
+    for member in &org_members {
+        let Some(user_id) = member.get("user_id").and_then(|u| u.as_str()) else { continue };
+        let role = project_role_for(member.get("role").and_then(|r| r.as_str()).unwrap_or("member"));
+        sqlx::query(
+            "INSERT INTO project_members (project_id, user_id, role) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"
+        )
+        .bind(project_id)
+        .bind(user_id)
+        .bind(role)
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal)?;
+    }
+    tx.commit().await.map_err(internal)?;
+
+    tracing::info!(project_id = %project_id, count = org_members.len(), "project_members.seeded");
+    Ok(())
+}
+
+/// User ids on the project roster, seeding it from the org first if needed.
+pub(crate) async fn roster(pool: &PgPool, org_id: &str, project_id: Uuid) -> Result<Vec<String>, ApiError> {
+    seed_from_org(pool, org_id, project_id).await?;
+
+    sqlx::query_scalar("SELECT user_id FROM project_members WHERE project_id = $1 ORDER BY created_at, user_id")
+        .bind(project_id)
+        .fetch_all(pool)
+        .await
+        .map_err(internal)
+}
+
+/// The `user_ids` that aren't on the project roster. An empty roster (Clerk
+/// not configured and nobody added by hand) can't vouch for anyone, so
+/// nothing is reported then.
+pub(crate) async fn non_members(
+    pool: &PgPool,
+    org_id: &str,
+    project_id: Uuid,
+    user_ids: &[String],
+) -> Result<Vec<String>, ApiError> {
+    if user_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let roster = roster(pool, org_id, project_id).await?;
+    if roster.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(user_ids.iter().filter(|id| !roster.contains(id)).cloned().collect())
+}
+
+// ─── GET /projects/{id}/members ───────────────────────
+
+pub async fn list(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ProjectMember>>>, ApiError> {
+    let org_id = scoped_project(&pool, &auth, project_id).await?;
+    seed_from_org(&pool, org_id, project_id).await?;
+
+    let mut members = sqlx::query_as::<_, ProjectMember>(
+        "SELECT user_id, role, added_by, created_at FROM project_members WHERE project_id = $1 ORDER BY created_at, user_id"
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal)?;
+
+    for member in &mut members {
+        if let Some((display_name, email)) =
+            crate::middleware::resolve_profile_cached(Some(&pool), &member.user_id).await
+        {
+            member.display_name = display_name;
+            member.email = email;
+        }
+    }
+
+    Ok(Json(ApiResponse::new(members)))
+}
+
+// ─── POST /projects/{id}/members ──────────────────────
+
+pub async fn add(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<AddProjectMember>,
+) -> Result<Json<ApiResponse<ProjectMember>>, ApiError> {
+    let org_id = scoped_project(&pool, &auth, project_id).await?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
+
+    let role = body.role.as_deref().unwrap_or("member");
+    if !VALID_ROLES.contains(&role) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({
+            "error": format!("Invalid role '{}'. Accepted values: {}", role, VALID_ROLES.join(", ")),
+            "field": "role"
+        }))));
+    }
+    if body.user_id.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({"error": "user_id is required", "field": "user_id"}))));
+    }
+
+    // Only org members can join a project (skipped when Clerk isn't configured)
+    let org_members = crate::routes::admin::fetch_org_members(org_id).await;
+    if !org_members.is_empty()
+        && !org_members.iter().any(|m| m.get("user_id").and_then(|u| u.as_str()) == Some(body.user_id.as_str()))
+    {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({
+            "error": "User is not a member of this organization",
+            "code": "not_org_member",
+            "field": "user_id"
+        }))));
+    }
+
+    let mut member = sqlx::query_as::<_, ProjectMember>(
+        r#"
+        INSERT INTO project_members (project_id, user_id, role, added_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (project_id, user_id) DO UPDATE SET role = EXCLUDED.role
+        RETURNING user_id, role, added_by, created_at
+        "#,
+    )
+    .bind(project_id)
+    .bind(&body.user_id)
+    .bind(role)
+    .bind(&auth.user_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(internal)?;
+
+    if let Some((display_name, email)) =
+        crate::middleware::resolve_profile_cached(Some(&pool), &member.user_id).await
+    {
+        member.display_name = display_name;
+        member.email = email;
+    }
+
+    Ok(Json(ApiResponse::new(member)))
+}
+
+// ─── DELETE /projects/{id}/members/{user_id} ──────────
+
+pub async fn remove(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path((project_id, user_id)): Path<(Uuid, String)>,
+) -> Result<Json<ApiResponse<()>>, ApiError> {
+    scoped_project(&pool, &auth, project_id).await?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
+
+    let result = sqlx::query("DELETE FROM project_members WHERE project_id = $1 AND user_id = $2")
+        .bind(project_id)
+        .bind(&user_id)
+        .execute(&pool)
+        .await
+        .map_err(internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Member not found"}))));
+    }
+
+    Ok(Json(ApiResponse::new(())))
+}
@@ -1,18 +1,20 @@
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::middleware::roles::{require_action_role, RoleAction};
 use crate::middleware::AuthUser;
 use crate::models::{
     ApiResponse, CreateProject, Project, ProjectAutoAssignSettings, UpdateProjectAutoAssignSettings,
 };
-use crate::routes::issues::fetch_user_org_ids;
+use crate::routes::issues::{fetch_user_org_ids, MAX_DISPLAY_ID_PADDING, MAX_PROJECT_HOLIDAYS};
 
 /// Parse "owner/repo" from a GitHub URL like https://github.com/owner/repo
 fn parse_github_owner_repo(url: &str) -> Option<(String, String)> {
@@ -33,8 +35,7 @@ fn parse_github_owner_repo(url: &str) -> Option<(String, String)> {
 /// Fetch public repo metadata from GitHub API (no auth needed for public repos)
 async fn fetch_github_metadata(url: &str) -> Option<serde_json::Value> {
     let (owner, repo) = parse_github_owner_repo(url)?;
-    let client = reqwest::Client::new();
-    let resp = client
+    let resp = crate::util::http::github()
         .get(format!("https://api.github.com/repos/{}/{}", owner, repo))
         .header("User-Agent", "Baaton/1.0")
         .header("Accept", "application/vnd.github.v3+json")
@@ -130,6 +131,8 @@ pub async fn refresh_github(
 pub struct ListProjectsQuery {
     /// When true, return projects from ALL orgs the user belongs to (cross-org, no session switch)
     pub all: Option<bool>,
+    /// Include archived projects (hidden by default)
+    pub include_archived: Option<bool>,
 }
 
 pub async fn list(
@@ -138,8 +141,9 @@ pub async fn list(
     Query(params): Query<ListProjectsQuery>,
 ) -> Json<ApiResponse<Vec<Project>>> {
     let cross_org = auth.user_id.starts_with("apikey:") || params.all.unwrap_or(false);
+    let include_archived = params.include_archived.unwrap_or(false);
 
-    let projects = if cross_org {
+    let mut projects = if cross_org {
         let org_ids = if auth.user_id.starts_with("apikey:") {
             auth.scoped_org_ids.clone()
         } else {
@@ -158,9 +162,10 @@ pub async fn list(
             vec![]
         } else {
             sqlx::query_as::<_, Project>(
-                "SELECT * FROM projects WHERE org_id = ANY($1) ORDER BY org_id, created_at DESC",
+                "SELECT * FROM projects WHERE org_id = ANY($1) AND (archived_at IS NULL OR $2) ORDER BY org_id, created_at DESC",
             )
             .bind(&org_ids)
+            .bind(include_archived)
             .fetch_all(&pool)
             .await
             .unwrap_or_else(|e| {
@@ -170,9 +175,10 @@ pub async fn list(
         }
     } else if let Some(ref org_id) = auth.org_id {
         sqlx::query_as::<_, Project>(
-            "SELECT * FROM projects WHERE org_id = $1 ORDER BY created_at DESC",
+            "SELECT * FROM projects WHERE org_id = $1 AND (archived_at IS NULL OR $2) ORDER BY created_at DESC",
         )
         .bind(org_id)
+        .bind(include_archived)
         .fetch_all(&pool)
         .await
         .unwrap_or_else(|e| {
@@ -187,6 +193,7 @@ pub async fn list(
     } else {
         vec![]
     };
+    projects.retain(|p| auth.has_project_access(p.id));
 
     tracing::info!(
         user_id = %auth.user_id,
@@ -199,51 +206,64 @@ pub async fn list(
     Json(ApiResponse::new(projects))
 }
 
-/// Create a project — assigns to the user's current org.
-pub async fn create(
-    Extension(auth): Extension<AuthUser>,
-    State(pool): State<PgPool>,
-    Json(body): Json<CreateProject>,
-) -> Result<Json<ApiResponse<Project>>, (StatusCode, Json<serde_json::Value>)> {
-    let effective_org = match &auth.org_id {
-        Some(id) => id.clone(),
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Must have active organization"})),
-            ))
-        }
-    };
-
-    // Input validation
-    if body.name.trim().is_empty() || body.name.len() > 200 {
+/// Name, slug and prefix rules shared by create and duplicate.
+fn validate_project_identity(
+    name: &str,
+    slug: &str,
+    prefix: &str,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if name.trim().is_empty() || name.len() > 200 {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "Project name is required and must be under 200 characters"})),
         ));
     }
     // Slug: non-empty, max 100 chars, alphanumeric + dash only
-    if body.slug.trim().is_empty()
-        || body.slug.len() > 100
-        || !body
-            .slug
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    if slug.trim().is_empty()
+        || slug.len() > 100
+        || !slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
     {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "Invalid slug format"})),
         ));
     }
-    if body.prefix.trim().is_empty() || body.prefix.len() > 10 {
+    if prefix.trim().is_empty() || prefix.len() > 10 {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(json!({"error": "Prefix is required and must be under 10 characters"})),
         ));
     }
+    Ok(())
+}
+
+/// Create a project — assigns to the user's current org.
+pub async fn create(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Json(body): Json<CreateProject>,
+) -> Result<Json<ApiResponse<Project>>, (StatusCode, Json<serde_json::Value>)> {
+    let effective_org = match &auth.org_id {
+        Some(id) => id.clone(),
+        None => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "Must have active organization"})),
+            ))
+        }
+    };
 
-    // Ensure the org exists + resolve name from Clerk in background
-    crate::routes::admin::upsert_org_background(pool.clone(), effective_org.clone());
+    validate_project_identity(&body.name, &body.slug, &body.prefix)?;
+
+    // Ensure the org exists (name is resolved from Clerk in the background)
+    crate::routes::admin::ensure_org(&pool, &effective_org, auth.org_slug.as_deref())
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+        })?;
 
     // ── Project limit guard (per-user, cross-org) ─────────
     crate::middleware::plan_guard::enforce_quota(
@@ -403,7 +423,12 @@ pub async fn get_one(
             })?;
 
     match project {
-        Some(p) => Ok(Json(ApiResponse::new(p))),
+        Some(mut p) => {
+            p.favorited = Some(
+                crate::routes::favorites::is_favorited(&pool, &auth.user_id, "project", p.id).await,
+            );
+            Ok(Json(ApiResponse::new(p)))
+        }
         None => Err((
             StatusCode::NOT_FOUND,
             Json(json!({"error": "Project not found"})),
@@ -424,6 +449,7 @@ pub async fn update(
             Json(json!({"error": "Organization required"})),
         )
     })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
 
     let auto_assign_mode = body.get("auto_assign_mode").and_then(|v| v.as_str());
     if let Some(mode) = auto_assign_mode {
@@ -435,6 +461,79 @@ pub async fn update(
         }
     }
 
+    let display_id_padding = body.get("display_id_padding").and_then(|v| v.as_i64());
+    if let Some(padding) = display_id_padding {
+        if !(0..=MAX_DISPLAY_ID_PADDING).contains(&padding) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("display_id_padding must be between 0 and {}", MAX_DISPLAY_ID_PADDING),
+                    "field": "display_id_padding"
+                })),
+            ));
+        }
+    }
+
+    // working_days: array of ISO weekdays, or null to count every day
+    let working_days: Option<Option<Vec<i32>>> = match body.get("working_days") {
+        None => None,
+        Some(serde_json::Value::Null) => Some(None),
+        Some(v) => {
+            let days: Option<Vec<i32>> = v.as_array().and_then(|a| {
+                a.iter()
+                    .map(|d| d.as_i64().filter(|d| (1..=7).contains(d)).map(|d| d as i32))
+                    .collect()
+            });
+            match days {
+                Some(mut days) if !days.is_empty() => {
+                    days.sort_unstable();
+                    days.dedup();
+                    Some(Some(days))
+                }
+                _ => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "error": "working_days must be a non-empty array of ISO weekdays (1 = Monday … 7 = Sunday), or null",
+                            "field": "working_days"
+                        })),
+                    ))
+                }
+            }
+        }
+    };
+
+    let holidays: Option<Vec<chrono::NaiveDate>> = match body.get("holidays") {
+        None => None,
+        Some(serde_json::Value::Null) => Some(vec![]),
+        Some(v) => {
+            let dates: Option<Vec<chrono::NaiveDate>> = v.as_array().and_then(|a| {
+                a.iter()
+                    .map(|d| {
+                        d.as_str()
+                            .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                    })
+                    .collect()
+            });
+            match dates {
+                Some(mut dates) if dates.len() <= MAX_PROJECT_HOLIDAYS => {
+                    dates.sort_unstable();
+                    dates.dedup();
+                    Some(dates)
+                }
+                _ => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({
+                            "error": format!("holidays must be an array of YYYY-MM-DD dates (max {})", MAX_PROJECT_HOLIDAYS),
+                            "field": "holidays"
+                        })),
+                    ))
+                }
+            }
+        }
+    };
+
     // If github_repo_url changed, re-fetch metadata
     let new_github_url = body.get("github_repo_url").and_then(|v| v.as_str());
     let github_metadata = if let Some(url) = new_github_url {
@@ -454,7 +553,11 @@ pub async fn update(
                auto_assign_mode = COALESCE($5, auto_assign_mode),
                default_assignee_id = CASE WHEN $6::boolean THEN $7 ELSE default_assignee_id END,
                github_repo_url = CASE WHEN $8::boolean THEN $9 ELSE github_repo_url END,
-               github_metadata = CASE WHEN $10::jsonb IS NOT NULL THEN $10 ELSE github_metadata END
+               github_metadata = CASE WHEN $10::jsonb IS NOT NULL THEN $10 ELSE github_metadata END,
+               validate_categories = COALESCE($11, validate_categories),
+               display_id_padding = COALESCE($12, display_id_padding),
+               working_days = CASE WHEN $13::boolean THEN $14 ELSE working_days END,
+               holidays = COALESCE($15, holidays)
            WHERE id = $1 AND org_id = $2
            RETURNING *"#,
     )
@@ -468,6 +571,11 @@ pub async fn update(
     .bind(new_github_url.is_some())
     .bind(new_github_url)
     .bind(&github_metadata)
+    .bind(body.get("validate_categories").and_then(|v| v.as_bool()))
+    .bind(display_id_padding.map(|p| p as i32))
+    .bind(working_days.is_some())
+    .bind(working_days.flatten())
+    .bind(holidays)
     .fetch_optional(&pool)
     .await
     .map_err(|e| {
@@ -496,17 +604,260 @@ pub async fn update(
     }
 }
 
+// ─── Statuses (board columns) ─────────────────────────
+
+const STATUS_CATEGORIES: &[&str] = &["backlog", "unstarted", "started", "completed", "cancelled"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectStatus {
+    pub key: String,
+    pub label: String,
+    pub color: String,
+    /// Workflow bucket: backlog | unstarted | started | completed | cancelled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Where issues in removed statuses go: one target for all, or per status.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum StatusRemap {
+    All(String),
+    PerStatus(HashMap<String, String>),
+}
+
+impl StatusRemap {
+    fn target(&self, from: &str) -> Option<&str> {
+        match self {
+            StatusRemap::All(to) => Some(to),
+            StatusRemap::PerStatus(map) => map.get(from).map(String::as_str),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectStatuses {
+    pub statuses: Vec<ProjectStatus>,
+    pub remap_to: Option<StatusRemap>,
+}
+
+fn validate_statuses(statuses: &[ProjectStatus]) -> Result<(), String> {
+    if statuses.is_empty() {
+        return Err("At least one status is required".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    for status in statuses {
+        if status.key.is_empty()
+            || !status
+                .key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(format!(
+                "Invalid status key '{}': use lowercase letters, digits and underscores",
+                status.key
+            ));
+        }
+        if !seen.insert(status.key.as_str()) {
+            return Err(format!("Duplicate status key '{}'", status.key));
+        }
+        if status.label.trim().is_empty() {
+            return Err(format!("Status '{}' needs a label", status.key));
+        }
+        if !status.color.starts_with('#') {
+            return Err(format!(
+                "Status '{}' color must be a hex value like #3b82f6",
+                status.key
+            ));
+        }
+        if let Some(ref category) = status.category {
+            if !STATUS_CATEGORIES.contains(&category.as_str()) {
+                return Err(format!(
+                    "Invalid category '{}'. Accepted values: {}",
+                    category,
+                    STATUS_CATEGORIES.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// GET /projects/{id}/statuses — the project's ordered board columns
+pub async fn get_statuses(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+
+    let statuses: serde_json::Value =
+        sqlx::query_scalar("SELECT statuses FROM projects WHERE id = $1 AND org_id = $2")
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": e.to_string()})),
+                )
+            })?
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({"error": "Project not found"})),
+                )
+            })?;
+
+    Ok(Json(ApiResponse::new(statuses)))
+}
+
+/// PATCH /projects/{id}/statuses — replace the ordered status list.
+///
+/// Issues whose status is not in the new list must be migrated: pass
+/// `remap_to` as a single status key or `{ "old_key": "new_key" }`. Issues are
+/// remapped and the list saved in one transaction.
+pub async fn update_statuses(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateProjectStatuses>,
+) -> Result<Json<ApiResponse<Vec<ProjectStatus>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
+
+    validate_statuses(&body.statuses)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e}))))?;
+    let new_keys: Vec<&str> = body.statuses.iter().map(|s| s.key.as_str()).collect();
+
+    let internal = |e: sqlx::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    };
+
+    let mut tx = pool.begin().await.map_err(internal)?;
+
+    let exists: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM projects WHERE id = $1 AND org_id = $2 FOR UPDATE")
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(tx.as_mut())
+            .await
+            .map_err(internal)?;
+    if exists.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        ));
+    }
+
+    // Every status issues currently use must survive or be remapped
+    let in_use: Vec<(String, i64)> =
+        sqlx::query_as("SELECT status, COUNT(*) FROM issues WHERE project_id = $1 GROUP BY status")
+            .bind(id)
+            .fetch_all(tx.as_mut())
+            .await
+            .map_err(internal)?;
+
+    let mut remaps: Vec<(String, String)> = Vec::new();
+    let mut unmapped = serde_json::Map::new();
+    for (status, count) in in_use {
+        if new_keys.contains(&status.as_str()) {
+            continue;
+        }
+        match body.remap_to.as_ref().and_then(|r| r.target(&status)) {
+            Some(to) if new_keys.contains(&to) => remaps.push((status, to.to_string())),
+            Some(to) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": format!("remap_to target '{}' is not in the new status list", to),
+                        "field": "remap_to"
+                    })),
+                ));
+            }
+            None => {
+                unmapped.insert(status, json!(count));
+            }
+        }
+    }
+    if !unmapped.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Some removed statuses still have issues; pass remap_to to migrate them",
+                "code": "remap_required",
+                "issues_by_status": unmapped,
+            })),
+        ));
+    }
+
+    for (from, to) in &remaps {
+        sqlx::query(
+            "UPDATE issues SET status = $3, updated_at = now() WHERE project_id = $1 AND status = $2",
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal)?;
+    }
+
+    sqlx::query("UPDATE projects SET statuses = $2 WHERE id = $1")
+        .bind(id)
+        .bind(serde_json::to_value(&body.statuses).unwrap_or_default())
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal)?;
+
+    tx.commit().await.map_err(internal)?;
+
+    if !remaps.is_empty() {
+        tracing::info!(project_id = %id, remapped = ?remaps, "projects.statuses_remapped");
+    }
+    crate::routes::webhooks::dispatch_event(
+        pool.clone(),
+        org_id.to_string(),
+        "project.updated",
+        json!({"id": id.to_string(), "statuses": body.statuses}),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(body.statuses)))
+}
+
 #[derive(Debug, Serialize)]
 pub struct PublicSubmitSettings {
     pub enabled: bool,
     pub token: Option<String>,
     pub slug: String,
+    pub spam_filter: bool,
+    /// Email the reporter when their issue is moved to done/cancelled.
+    pub notify_reporters: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdatePublicSubmitSettings {
     pub enabled: Option<bool>,
     pub rotate_token: Option<bool>,
+    pub spam_filter: Option<bool>,
+    pub notify_reporters: Option<bool>,
 }
 
 /// Get public submit settings for a project
@@ -522,8 +873,8 @@ pub async fn get_public_submit_settings(
         )
     })?;
 
-    let row = sqlx::query_as::<_, (bool, Option<String>, String)>(
-        "SELECT public_submit_enabled, public_submit_token, slug FROM projects WHERE id = $1 AND org_id = $2",
+    let row = sqlx::query_as::<_, (bool, Option<String>, String, bool, bool)>(
+        "SELECT public_submit_enabled, public_submit_token, slug, public_submit_spam_filter, notify_reporters FROM projects WHERE id = $1 AND org_id = $2",
     )
     .bind(id)
     .bind(org_id)
@@ -532,11 +883,15 @@ pub async fn get_public_submit_settings(
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
     match row {
-        Some((enabled, token, slug)) => Ok(Json(ApiResponse::new(PublicSubmitSettings {
-            enabled,
-            token,
-            slug,
-        }))),
+        Some((enabled, token, slug, spam_filter, notify_reporters)) => {
+            Ok(Json(ApiResponse::new(PublicSubmitSettings {
+                enabled,
+                token,
+                slug,
+                spam_filter,
+                notify_reporters,
+            })))
+        }
         None => Err((
             StatusCode::NOT_FOUND,
             Json(json!({"error": "Project not found"})),
@@ -544,7 +899,8 @@ pub async fn get_public_submit_settings(
     }
 }
 
-/// Update public submit settings (enable/disable + rotate token)
+/// Update public submit settings (enable/disable, rotate token, spam filter,
+/// reporter notifications)
 pub async fn update_public_submit_settings(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -557,9 +913,10 @@ pub async fn update_public_submit_settings(
             Json(json!({"error": "Organization required"})),
         )
     })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
 
-    let current = sqlx::query_as::<_, (bool, Option<String>, String)>(
-        "SELECT public_submit_enabled, public_submit_token, slug FROM projects WHERE id = $1 AND org_id = $2 FOR UPDATE",
+    let current = sqlx::query_as::<_, (bool, Option<String>, String, bool, bool)>(
+        "SELECT public_submit_enabled, public_submit_token, slug, public_submit_spam_filter, notify_reporters FROM projects WHERE id = $1 AND org_id = $2 FOR UPDATE",
     )
     .bind(id)
     .bind(org_id)
@@ -567,18 +924,21 @@ pub async fn update_public_submit_settings(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
-    let (current_enabled, current_token, _slug) = match current {
-        Some(row) => row,
-        None => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Project not found"})),
-            ))
-        }
-    };
+    let (current_enabled, current_token, _slug, current_spam_filter, current_notify_reporters) =
+        match current {
+            Some(row) => row,
+            None => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(json!({"error": "Project not found"})),
+                ))
+            }
+        };
 
     let enabled = body.enabled.unwrap_or(current_enabled);
     let rotate = body.rotate_token.unwrap_or(false);
+    let spam_filter = body.spam_filter.unwrap_or(current_spam_filter);
+    let notify_reporters = body.notify_reporters.unwrap_or(current_notify_reporters);
 
     let token = if rotate || (enabled && current_token.is_none()) {
         Some(Uuid::new_v4().to_string())
@@ -586,13 +946,15 @@ pub async fn update_public_submit_settings(
         current_token
     };
 
-    let updated = sqlx::query_as::<_, (bool, Option<String>, String)>(
-        "UPDATE projects SET public_submit_enabled = $3, public_submit_token = $4 WHERE id = $1 AND org_id = $2 RETURNING public_submit_enabled, public_submit_token, slug",
+    let updated = sqlx::query_as::<_, (bool, Option<String>, String, bool, bool)>(
+        "UPDATE projects SET public_submit_enabled = $3, public_submit_token = $4, public_submit_spam_filter = $5, notify_reporters = $6 WHERE id = $1 AND org_id = $2 RETURNING public_submit_enabled, public_submit_token, slug, public_submit_spam_filter, notify_reporters",
     )
     .bind(id)
     .bind(org_id)
     .bind(enabled)
     .bind(&token)
+    .bind(spam_filter)
+    .bind(notify_reporters)
     .fetch_one(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
@@ -601,6 +963,8 @@ pub async fn update_public_submit_settings(
         enabled: updated.0,
         token: updated.1,
         slug: updated.2,
+        spam_filter: updated.3,
+        notify_reporters: updated.4,
     })))
 }
 
@@ -654,7 +1018,7 @@ pub async fn board_by_slug(
             SELECT * FROM issues
             WHERE project_id = $1
               AND (archived = false OR $2::boolean)
-              AND (snoozed_until IS NULL OR snoozed_until <= CURRENT_DATE OR $3::boolean)
+              AND (snoozed_until IS NULL OR snoozed_until <= now() OR $3::boolean)
             ORDER BY position ASC
             "#
         )
@@ -712,11 +1076,285 @@ pub async fn resolve_public_token(
     }
 }
 
-/// Delete a project — must belong to user's active org.
+/// DELETE /projects/{id} — archive the project. Issues and history are kept;
+/// the project disappears from `list` and stops accepting new issues.
+/// `POST /projects/{id}/purge` deletes for real.
 pub async fn remove(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Project>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectDelete).await?;
+
+    let project = sqlx::query_as::<_, Project>(
+        "UPDATE projects SET archived_at = COALESCE(archived_at, now()) WHERE id = $1 AND org_id = $2 RETURNING *",
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        )
+    })?;
+
+    crate::routes::webhooks::dispatch_event(
+        pool.clone(),
+        org_id.to_string(),
+        "project.updated",
+        serde_json::to_value(&project).unwrap_or_default(),
+    )
+    .await;
+    crate::routes::audit::record(
+        &pool,
+        &auth,
+        &headers,
+        "project.archived",
+        "project",
+        &id.to_string(),
+        json!({"name": project.name, "slug": project.slug}),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(project)))
+}
+
+/// POST /projects/{id}/unarchive
+pub async fn unarchive(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Project>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
+
+    let project = sqlx::query_as::<_, Project>(
+        "UPDATE projects SET archived_at = NULL WHERE id = $1 AND org_id = $2 RETURNING *",
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        )
+    })?;
+
+    Ok(Json(ApiResponse::new(project)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateProject {
+    pub name: String,
+    pub slug: String,
+    pub prefix: String,
+    /// Defaults to the source project's description.
+    pub description: Option<String>,
+    /// Also copy milestones (reset to `active`, without issues).
+    #[serde(default)]
+    pub include_milestones: bool,
+}
+
+/// POST /projects/{id}/duplicate — new project in the same org with the
+/// source's statuses, tags and issue templates (and optionally milestones).
+/// Issues are never copied.
+pub async fn duplicate(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<DuplicateProject>,
+) -> Result<Json<ApiResponse<Project>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
+
+    validate_project_identity(&body.name, &body.slug, &body.prefix)?;
+
+    crate::middleware::plan_guard::enforce_quota(
+        &pool,
+        &auth,
+        crate::middleware::plan_guard::QuotaKind::Projects,
+    )
+    .await?;
+
+    let internal = |e: sqlx::Error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    };
+
+    let mut tx = pool.begin().await.map_err(internal)?;
+
+    let (slug_taken, prefix_taken): (bool, bool) = sqlx::query_as(
+        r#"
+        SELECT
+            EXISTS(SELECT 1 FROM projects WHERE org_id = $1 AND slug = $2),
+            EXISTS(SELECT 1 FROM projects WHERE org_id = $1 AND upper(prefix) = upper($3))
+        "#,
+    )
+    .bind(org_id)
+    .bind(&body.slug)
+    .bind(&body.prefix)
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(internal)?;
+    if slug_taken || prefix_taken {
+        let (field, value) = if slug_taken {
+            ("slug", &body.slug)
+        } else {
+            ("prefix", &body.prefix)
+        };
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": format!("A project with {} '{}' already exists in this organization", field, value),
+                "code": format!("{}_taken", field),
+                "field": field
+            })),
+        ));
+    }
+
+    let project = sqlx::query_as::<_, Project>(
+        r#"
+        INSERT INTO projects (org_id, name, slug, description, prefix, statuses, auto_assign_mode, default_assignee_id)
+        SELECT org_id, $3, $4, COALESCE($5, description), $6, statuses, auto_assign_mode, default_assignee_id
+        FROM projects
+        WHERE id = $1 AND org_id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(&body.name)
+    .bind(&body.slug)
+    .bind(&body.description)
+    .bind(&body.prefix)
+    .fetch_optional(tx.as_mut())
+    .await
+    .map_err(|e| match e {
+        // Lost a race with a concurrent create on UNIQUE(org_id, slug)
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => (
+            StatusCode::CONFLICT,
+            Json(json!({"error": "A project with this slug already exists", "code": "slug_taken", "field": "slug"})),
+        ),
+        e => internal(e),
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Project not found"})),
+        )
+    })?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO project_tags (project_id, name, color, group_name, description)
+        SELECT $2, name, color, group_name, description FROM project_tags WHERE project_id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(project.id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO issue_templates (
+            project_id, org_id, name, title_prefix, description,
+            default_tags, default_priority, default_issue_type,
+            default_assignee_ids, is_default
+        )
+        SELECT $2, $3, name, title_prefix, description,
+               default_tags, default_priority, default_issue_type,
+               default_assignee_ids, is_default
+        FROM issue_templates WHERE project_id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(project.id)
+    .bind(org_id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(internal)?;
+
+    if body.include_milestones {
+        sqlx::query(
+            r#"
+            INSERT INTO milestones (project_id, name, description, target_date, status, "order", estimated_days, org_id)
+            SELECT $2, name, description, target_date, 'active', "order", estimated_days, $3
+            FROM milestones WHERE project_id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(project.id)
+        .bind(org_id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(internal)?;
+    }
+
+    tx.commit().await.map_err(internal)?;
+
+    crate::routes::webhooks::dispatch_event(
+        pool.clone(),
+        org_id.to_string(),
+        "project.created",
+        serde_json::to_value(&project).unwrap_or_default(),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(project)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeProject {
+    /// Must be `true` — the delete cascades to every issue, comment and log entry.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// POST /projects/{id}/purge — irreversible cascade delete. Only archived
+/// projects can be purged, and only with `{ "confirm": true }`.
+pub async fn purge(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(body): Json<PurgeProject>,
 ) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<serde_json::Value>)> {
     let org_id = auth.org_id.as_deref().ok_or_else(|| {
         (
@@ -724,8 +1362,51 @@ pub async fn remove(
             Json(json!({"error": "Organization required"})),
         )
     })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectDelete).await?;
+
+    if !body.confirm {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Purging deletes all issues and history permanently. Pass { \"confirm\": true } to proceed.",
+                "code": "confirmation_required"
+            })),
+        ));
+    }
+
+    let archived: Option<bool> = sqlx::query_scalar(
+        "SELECT archived_at IS NOT NULL FROM projects WHERE id = $1 AND org_id = $2",
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+    })?;
 
-    let result = sqlx::query("DELETE FROM projects WHERE id = $1 AND org_id = $2")
+    match archived {
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Project not found"})),
+            ))
+        }
+        Some(false) => {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(
+                    json!({"error": "Archive the project (DELETE /projects/{id}) before purging it"}),
+                ),
+            ))
+        }
+        Some(true) => {}
+    }
+
+    sqlx::query("DELETE FROM projects WHERE id = $1 AND org_id = $2")
         .bind(id)
         .bind(org_id)
         .execute(&pool)
@@ -737,21 +1418,25 @@ pub async fn remove(
             )
         })?;
 
-    if result.rows_affected() > 0 {
-        crate::routes::webhooks::dispatch_event(
-            pool.clone(),
-            org_id.to_string(),
-            "project.deleted",
-            serde_json::json!({"id": id.to_string()}),
-        )
-        .await;
-        Ok(Json(ApiResponse::new(())))
-    } else {
-        Err((
-            StatusCode::NOT_FOUND,
-            Json(json!({"error": "Project not found"})),
-        ))
-    }
+    crate::routes::webhooks::dispatch_event(
+        pool.clone(),
+        org_id.to_string(),
+        "project.deleted",
+        serde_json::json!({"id": id.to_string()}),
+    )
+    .await;
+    crate::routes::audit::record(
+        &pool,
+        &auth,
+        &headers,
+        "project.purged",
+        "project",
+        &id.to_string(),
+        json!({}),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(())))
 }
 
 pub async fn get_auto_assign_settings(
@@ -768,7 +1453,7 @@ pub async fn get_auto_assign_settings(
 
     let settings = sqlx::query_as::<_, ProjectAutoAssignSettings>(
         r#"
-        SELECT id AS project_id, auto_assign_mode, default_assignee_id
+        SELECT id AS project_id, auto_assign_mode, default_assignee_id, strict_assignees
         FROM projects
         WHERE id = $1 AND org_id = $2
         "#,
@@ -805,6 +1490,7 @@ pub async fn update_auto_assign_settings(
             Json(json!({"error": "Organization required"})),
         )
     })?;
+    require_action_role(&pool, &auth, RoleAction::ProjectUpdate).await?;
 
     if !matches!(
         body.auto_assign_mode.as_str(),
@@ -816,19 +1502,42 @@ pub async fn update_auto_assign_settings(
         ));
     }
 
+    // Auto-assigning to someone off the roster would silently do nothing useful
+    if let Some(ref assignee) = body.default_assignee_id {
+        let unknown = crate::routes::project_members::non_members(
+            &pool,
+            org_id,
+            id,
+            std::slice::from_ref(assignee),
+        )
+        .await?;
+        if !unknown.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": format!("Default assignee '{}' is not a member of this project", assignee),
+                    "code": "not_project_member",
+                    "field": "default_assignee_id"
+                })),
+            ));
+        }
+    }
+
     let settings = sqlx::query_as::<_, ProjectAutoAssignSettings>(
         r#"
         UPDATE projects
         SET auto_assign_mode = $3,
-            default_assignee_id = $4
+            default_assignee_id = $4,
+            strict_assignees = COALESCE($5, strict_assignees)
         WHERE id = $1 AND org_id = $2
-        RETURNING id AS project_id, auto_assign_mode, default_assignee_id
+        RETURNING id AS project_id, auto_assign_mode, default_assignee_id, strict_assignees
         "#,
     )
     .bind(id)
     .bind(org_id)
     .bind(&body.auto_assign_mode)
     .bind(&body.default_assignee_id)
+    .bind(body.strict_assignees)
     .fetch_optional(&pool)
     .await
     .map_err(|e| {
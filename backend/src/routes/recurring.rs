@@ -8,6 +8,7 @@ use uuid::Uuid;
 use crate::middleware::AuthUser;
 use crate::models::{ApiResponse, RecurrenceRule};
 use crate::routes::activity::log_activity;
+use crate::routes::issues::next_display_id;
 
 // ─── Request types ────────────────────────────────────
 
@@ -307,8 +308,8 @@ pub async fn trigger(
     }
 
     // Get project prefix for display_id generation
-    let project_prefix: Option<String> = sqlx::query_scalar(
-        "SELECT prefix FROM projects WHERE id = $1 AND org_id = $2"
+    let project: Option<(String, bool)> = sqlx::query_as(
+        "SELECT prefix, archived_at IS NOT NULL FROM projects WHERE id = $1 AND org_id = $2"
     )
     .bind(rule.project_id)
     .bind(org_id)
@@ -316,8 +317,11 @@ pub async fn trigger(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
-    let prefix = project_prefix
+    let (prefix, archived) = project
         .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))))?;
+    if archived {
+        return Err((StatusCode::CONFLICT, Json(json!({"error": "Project is archived", "code": "project_archived"}))));
+    }
 
     let title = expand_title(&rule.title_template, rule.occurrence_count);
     let next_run_at = next_run_from_rrule(&rule.rrule, rule.next_run_at);
@@ -325,22 +329,7 @@ pub async fn trigger(
     let mut tx = pool.begin().await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
-    // Generate display_id
-    let next_number: (i64,) = sqlx::query_as(
-        r#"
-        SELECT COALESCE(MAX((SPLIT_PART(display_id, '-', 2))::bigint), 0) + 1
-        FROM issues
-        WHERE project_id = $1
-          AND display_id ~ ('^' || $2 || '-[0-9]+$')
-        "#,
-    )
-    .bind(rule.project_id)
-    .bind(&prefix)
-    .fetch_one(tx.as_mut())
-    .await
-    .unwrap_or((1i64,));
-
-    let display_id = format!("{}-{}", prefix, next_number.0);
+    let display_id = next_display_id(tx.as_mut(), rule.project_id, &prefix).await?;
 
     // Get max position for backlog
     let max_pos: Option<(Option<f64>,)> = sqlx::query_as(
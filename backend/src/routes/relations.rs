@@ -6,7 +6,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::{ApiResponse, IssueRelation};
+use crate::models::{ApiResponse, DependencyRef, IssueDependencies, IssueRelation};
 use crate::routes::activity::log_activity;
 
 // ─── Request / Response types ─────────────────────────
@@ -40,6 +40,57 @@ fn inverse_relation(rel_type: &str) -> Option<&'static str> {
     }
 }
 
+/// Whether `from` already (transitively) blocks `to`. Adding `to blocks from`
+/// on top of such a path would close a dependency cycle.
+async fn blocks_path_exists(pool: &PgPool, from: Uuid, to: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE reach(id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT r.target_issue_id FROM issue_relations r
+            JOIN reach ON r.source_issue_id = reach.id
+            WHERE r.relation_type = 'blocks'
+        )
+        SELECT EXISTS(SELECT 1 FROM reach WHERE id = $2)
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_one(pool)
+    .await
+}
+
+/// Direct blockers and dependents of an issue, for `IssueDetail`.
+pub async fn issue_dependencies(pool: &PgPool, issue_id: Uuid) -> IssueDependencies {
+    let rows = sqlx::query_as::<_, (String, Uuid, String, String, String, Option<String>)>(
+        r#"
+        SELECT r.relation_type, i.id, i.display_id, i.title, i.status, i.priority
+        FROM issue_relations r
+        JOIN issues i ON i.id = r.target_issue_id
+        WHERE r.source_issue_id = $1 AND r.relation_type IN ('blocks', 'blocked_by')
+        ORDER BY i.display_id ASC
+        "#,
+    )
+    .bind(issue_id)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "issue dependencies query failed");
+        vec![]
+    });
+
+    let mut deps = IssueDependencies::default();
+    for (rel_type, id, display_id, title, status, priority) in rows {
+        let dep = DependencyRef { id, display_id, title, status, priority };
+        match rel_type.as_str() {
+            "blocked_by" => deps.blocked_by.push(dep),
+            _ => deps.blocks.push(dep),
+        }
+    }
+    deps
+}
+
 // ─── POST /issues/{id}/relations ──────────────────────
 
 pub async fn create(
@@ -76,18 +127,37 @@ pub async fn create(
     let project_id = source_project_id
         .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "Source issue not found"}))))?;
 
-    // Verify target issue exists in org
-    let target_exists: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)"
+    // Verify target issue exists in org; the inverse relation is written to it
+    let target_project_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT i.project_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2"
     )
     .bind(body.target_issue_id)
     .bind(org_id)
-    .fetch_one(&pool)
+    .fetch_optional(&pool)
     .await
-    .unwrap_or(false);
+    .unwrap_or(None);
 
-    if !target_exists {
+    let Some(target_project_id) = target_project_id else {
         return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Target issue not found"}))));
+    };
+    if !auth.has_project_access(target_project_id) {
+        return Err((StatusCode::FORBIDDEN, Json(json!({"error": "API key does not have access to the target issue's project"}))));
+    }
+
+    let blocking_edge = match body.relation_type.as_str() {
+        "blocks" => Some((issue_id, body.target_issue_id)),
+        "blocked_by" => Some((body.target_issue_id, issue_id)),
+        _ => None,
+    };
+    if let Some((blocker, blocked)) = blocking_edge {
+        let cycle = blocks_path_exists(&pool, blocked, blocker).await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+        if cycle {
+            return Err((StatusCode::CONFLICT, Json(json!({
+                "error": "This dependency would create a cycle",
+                "code": "dependency_cycle"
+            }))));
+        }
     }
 
     let mut tx = pool.begin().await
@@ -301,6 +371,72 @@ pub async fn remove(
     Ok(Json(ApiResponse::new(())))
 }
 
+// ─── POST /issues/{id}/dependencies ───────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDependency {
+    /// The issue that must finish first
+    pub depends_on_id: Uuid,
+}
+
+/// Mark the issue as blocked by `depends_on_id`. Stored as a `blocked_by`
+/// relation (plus its `blocks` inverse), so it also shows up under relations.
+pub async fn add_dependency(
+    auth: Extension<AuthUser>,
+    pool: State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+    Json(body): Json<CreateDependency>,
+) -> Result<Json<ApiResponse<IssueRelation>>, (StatusCode, Json<serde_json::Value>)> {
+    create(auth, pool, Path(issue_id), Json(CreateRelation {
+        target_issue_id: body.depends_on_id,
+        relation_type: "blocked_by".to_string(),
+    })).await
+}
+
+// ─── DELETE /issues/{id}/dependencies/{depends_on_id} ─
+
+pub async fn remove_dependency(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path((issue_id, depends_on_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)"
+    )
+    .bind(issue_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))));
+    }
+
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM issue_relations
+        WHERE (source_issue_id = $1 AND target_issue_id = $2 AND relation_type = 'blocked_by')
+           OR (source_issue_id = $2 AND target_issue_id = $1 AND relation_type = 'blocks')
+        "#,
+    )
+    .bind(issue_id)
+    .bind(depends_on_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Dependency not found"}))));
+    }
+
+    Ok(Json(ApiResponse::new(())))
+}
+
 // ─── Dependency Graph types ────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -336,6 +472,114 @@ pub struct DependencyGraph {
     pub blocked_issues: Vec<BlockedIssue>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct IssueDependencyGraph {
+    /// The issue, everything it (transitively) waits on, and everything waiting on it
+    pub nodes: Vec<DependencyNode>,
+    /// `blocks` edges between those nodes
+    pub edges: Vec<DependencyEdge>,
+    /// Display IDs of unfinished issues this one is (transitively) blocked by
+    pub open_blockers: Vec<String>,
+}
+
+// ─── GET /issues/{id}/dependencies ────────────────────
+
+pub async fn issue_dependency_graph(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<IssueDependencyGraph>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)"
+    )
+    .bind(issue_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))));
+    }
+
+    // (id, is_upstream): walk blockers upwards and dependents downwards
+    let reachable = sqlx::query_as::<_, (Uuid, bool)>(
+        r#"
+        WITH RECURSIVE up(id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT r.source_issue_id FROM issue_relations r
+            JOIN up ON r.target_issue_id = up.id
+            WHERE r.relation_type = 'blocks'
+        ),
+        down(id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT r.target_issue_id FROM issue_relations r
+            JOIN down ON r.source_issue_id = down.id
+            WHERE r.relation_type = 'blocks'
+        )
+        SELECT id, true FROM up WHERE id <> $1
+        UNION ALL
+        SELECT id, false FROM down
+        "#,
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let ids: Vec<Uuid> = reachable.iter().map(|(id, _)| *id).collect();
+    let upstream: HashSet<Uuid> = reachable.iter().filter(|(_, up)| *up).map(|(id, _)| *id).collect();
+
+    let issues = sqlx::query_as::<_, (Uuid, String, String, String, Option<String>)>(
+        "SELECT id, display_id, title, status, priority FROM issues WHERE id = ANY($1) ORDER BY display_id ASC"
+    )
+    .bind(&ids)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let edges = sqlx::query_as::<_, (Uuid, Uuid)>(
+        r#"
+        SELECT source_issue_id, target_issue_id FROM issue_relations
+        WHERE relation_type = 'blocks' AND source_issue_id = ANY($1) AND target_issue_id = ANY($1)
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let open_blockers = issues.iter()
+        .filter(|(id, _, _, status, _)| upstream.contains(id) && status != "done" && status != "cancelled")
+        .map(|(_, did, _, _, _)| did.clone())
+        .collect();
+
+    let nodes = issues.into_iter()
+        .map(|(id, display_id, title, status, priority)| DependencyNode {
+            id: id.to_string(),
+            display_id,
+            title,
+            status,
+            priority,
+        })
+        .collect();
+
+    let edges = edges.into_iter()
+        .map(|(src, tgt)| DependencyEdge {
+            source: src.to_string(),
+            target: tgt.to_string(),
+            edge_type: "blocks".to_string(),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::new(IssueDependencyGraph { nodes, edges, open_blockers })))
+}
+
 // ─── GET /projects/{id}/dependency-graph ──────────────
 
 pub async fn dependency_graph(
@@ -1,27 +1,36 @@
-use axum::{extract::{Path, State}, http::StatusCode, Extension, Json};
-use chrono::{DateTime, Utc};
+use axum::{extract::{Path, Query, State}, http::StatusCode, Extension, Json};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::ApiResponse;
+use crate::models::{ApiResponse, Issue};
+use crate::novu::NovuClient;
+use crate::routes::notifications::create_notification;
+use crate::routes::sse::{broadcast_event, BaatonEvent, EventSender};
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
-pub struct SlaRule {
+pub struct SlaPolicy {
     pub id: Uuid,
     pub project_id: Uuid,
     pub org_id: String,
     pub priority: String,
-    pub deadline_hours: i32,
+    /// Minutes until someone other than the reporter must react
+    pub response_minutes: Option<i32>,
+    /// Minutes until the issue must be closed
+    pub resolution_minutes: Option<i32>,
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct CreateSlaRule {
+pub struct UpsertSlaPolicy {
     pub priority: String,
-    pub deadline_hours: i32,
+    pub response_minutes: Option<i32>,
+    pub resolution_minutes: Option<i32>,
+    /// Deprecated `/sla-rules` body: resolution target in hours.
+    pub deadline_hours: Option<i32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,49 +41,168 @@ pub struct SlaStats {
     pub achievement_pct: f64,
 }
 
-// ─── Internal Helper ──────────────────────────────────
+/// Longest target a policy may set (one year).
+const MAX_SLA_MINUTES: i32 = 365 * 24 * 60;
 
-/// Apply SLA deadline to an issue based on its priority.
-/// Looks up sla_rules for the project; if found, sets sla_deadline = now + hours.
-pub async fn apply_sla_deadline(pool: &PgPool, issue_id: Uuid, project_id: Uuid, priority: Option<&str>) {
-    let priority = match priority {
-        Some(p) => p,
-        None => return,
-    };
+/// Warn when this fraction of a target's window is left.
+const SLA_WARNING_FRACTION: f64 = 0.25;
+
+const SLA_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
 
-    let deadline_hours: Option<i32> = sqlx::query_scalar(
-        "SELECT deadline_hours FROM sla_rules WHERE project_id = $1 AND priority = $2"
+// ─── Internal Helper ──────────────────────────────────
+
+/// Set an issue's SLA targets from its project's policy for `priority`,
+/// counted from the issue's creation. Clears them when there is no policy.
+/// A breach already flagged is kept: it is history the SLA report counts.
+pub async fn apply_sla_policy(pool: &PgPool, issue_id: Uuid, project_id: Uuid, priority: Option<&str>) {
+    let result = sqlx::query(
+        r#"
+        UPDATE issues SET
+            sla_response_due_at = created_at + interval '1 minute' * (
+                SELECT response_minutes FROM sla_policies WHERE project_id = $2 AND priority = $3
+            ),
+            sla_resolution_due_at = created_at + interval '1 minute' * (
+                SELECT resolution_minutes FROM sla_policies WHERE project_id = $2 AND priority = $3
+            ),
+            sla_warned_at = NULL
+        WHERE id = $1
+        "#,
     )
+    .bind(issue_id)
     .bind(project_id)
     .bind(priority)
-    .fetch_optional(pool)
-    .await
-    .unwrap_or(None);
+    .execute(pool)
+    .await;
 
-    if let Some(hours) = deadline_hours {
-        let _ = sqlx::query(
-            "UPDATE issues SET sla_deadline = now() + interval '1 hour' * $1 WHERE id = $2"
+    if let Err(e) = result {
+        tracing::warn!(error = %e, %issue_id, "apply_sla_policy failed");
+    }
+}
+
+/// Background task: flag issues that missed an SLA target (in-app
+/// `sla_breach` notification to assignees) and warn assignees through the
+/// Novu `sla-breach-imminent` workflow once a target is nearly due.
+pub async fn sla_worker(pool: PgPool, novu: Option<NovuClient>, sse_tx: EventSender) {
+    tracing::info!("SLA worker started");
+    loop {
+        tokio::time::sleep(SLA_SWEEP_INTERVAL).await;
+        if let Err(e) = flag_breaches(&pool, &sse_tx).await {
+            tracing::warn!(error = %e, "sla_worker: breach sweep failed");
+        }
+        if let Some(ref novu) = novu {
+            if let Err(e) = warn_imminent(&pool, novu).await {
+                tracing::warn!(error = %e, "sla_worker: warning sweep failed");
+            }
+        }
+    }
+}
+
+async fn flag_breaches(pool: &PgPool, sse_tx: &EventSender) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        WITH missed AS (
+            SELECT id FROM issues
+            WHERE sla_breached IS NOT TRUE
+              AND (
+                (sla_response_due_at <= now() AND COALESCE(sla_responded_at, now()) > sla_response_due_at)
+                OR (sla_resolution_due_at <= now() AND COALESCE(closed_at, now()) > sla_resolution_due_at)
+              )
+            LIMIT 200
+            FOR UPDATE SKIP LOCKED
         )
-        .bind(hours)
-        .bind(issue_id)
-        .execute(pool)
-        .await;
+        UPDATE issues i SET sla_breached = true
+        FROM missed, projects p
+        WHERE i.id = missed.id AND p.id = i.project_id
+        RETURNING i.*, p.org_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for row in rows {
+        let issue = Issue::from_row(&row)?;
+        let Some(org_id) = issue.org_id.clone() else {
+            continue;
+        };
+        let title = format!("SLA breached: {} {}", issue.display_id, issue.title);
+        for user_id in &issue.assignee_ids {
+            create_notification(
+                pool,
+                user_id,
+                &org_id,
+                "sla_breach",
+                Some(issue.id),
+                Some(issue.project_id),
+                &title,
+                None,
+            )
+            .await;
+        }
+        broadcast_event(sse_tx, &org_id, &BaatonEvent::IssueUpdated((&issue).into()));
     }
+    Ok(())
+}
+
+async fn warn_imminent(pool: &PgPool, novu: &NovuClient) -> Result<(), sqlx::Error> {
+    // A target is "imminent" once the last SLA_WARNING_FRACTION of its window
+    // starts; sla_warned_at before that point means it hasn't been warned yet.
+    let rows = sqlx::query_as::<_, (String, String, Vec<String>, String, DateTime<Utc>)>(
+        r#"
+        WITH pending AS (
+            SELECT i.id, i.created_at, i.sla_warned_at, t.due_at
+            FROM issues i
+            CROSS JOIN LATERAL unnest(ARRAY[
+                CASE WHEN i.sla_responded_at IS NULL THEN i.sla_response_due_at END,
+                i.sla_resolution_due_at
+            ]) AS t(due_at)
+            WHERE i.sla_breached IS NOT TRUE AND i.closed_at IS NULL
+              AND cardinality(i.assignee_ids) > 0
+              AND t.due_at > now()
+        ), warn AS (
+            SELECT id, min(due_at) AS due_at
+            FROM pending
+            WHERE now() >= due_at - (due_at - created_at) * $1
+              AND (sla_warned_at IS NULL OR sla_warned_at < due_at - (due_at - created_at) * $1)
+            GROUP BY id
+        )
+        UPDATE issues i SET sla_warned_at = now()
+        FROM warn w, projects p
+        WHERE i.id = w.id AND p.id = i.project_id
+        RETURNING i.display_id, i.title, i.assignee_ids, p.org_id, w.due_at
+        "#,
+    )
+    .bind(SLA_WARNING_FRACTION)
+    .fetch_all(pool)
+    .await?;
+
+    for (display_id, title, assignee_ids, org_id, due_at) in rows {
+        let subs = crate::novu::subscribers_for(pool, &org_id, "sla-breach-imminent", assignee_ids).await;
+        novu.trigger_many(
+            "sla-breach-imminent",
+            subs,
+            json!({
+                "issueId": display_id,
+                "issueTitle": title,
+                "dueAt": due_at.to_rfc3339(),
+            }),
+        );
+    }
+    Ok(())
 }
 
 // ─── Routes ──────────────────────────────────────────
 
-/// GET /projects/{id}/sla-rules
+/// GET /projects/{id}/sla-policies
 pub async fn list(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
     Path(project_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<Vec<SlaRule>>>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ApiResponse<Vec<SlaPolicy>>>, (StatusCode, Json<serde_json::Value>)> {
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
-    let rules = sqlx::query_as::<_, SlaRule>(
-        "SELECT * FROM sla_rules WHERE project_id = $1 AND org_id = $2 ORDER BY created_at ASC"
+    let rules = sqlx::query_as::<_, SlaPolicy>(
+        "SELECT * FROM sla_policies WHERE project_id = $1 AND org_id = $2 ORDER BY created_at ASC"
     )
     .bind(project_id)
     .bind(org_id)
@@ -85,16 +213,39 @@ pub async fn list(
     Ok(Json(ApiResponse::new(rules)))
 }
 
-/// POST /projects/{id}/sla-rules
+/// POST /projects/{id}/sla-policies — create or replace the policy for a
+/// priority. Applies to issues created or re-prioritized afterwards.
 pub async fn create(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
     Path(project_id): Path<Uuid>,
-    Json(body): Json<CreateSlaRule>,
-) -> Result<Json<ApiResponse<SlaRule>>, (StatusCode, Json<serde_json::Value>)> {
+    Json(body): Json<UpsertSlaPolicy>,
+) -> Result<Json<ApiResponse<SlaPolicy>>, (StatusCode, Json<serde_json::Value>)> {
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
+    if !["urgent", "high", "medium", "low"].contains(&body.priority.as_str()) {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({
+            "error": "priority must be one of urgent, high, medium, low",
+            "field": "priority",
+        }))));
+    }
+    let resolution_minutes = body.resolution_minutes.or(body.deadline_hours.map(|h| h.saturating_mul(60)));
+    for (field, minutes) in [("response_minutes", body.response_minutes), ("resolution_minutes", resolution_minutes)] {
+        if minutes.is_some_and(|m| !(1..=MAX_SLA_MINUTES).contains(&m)) {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({
+                "error": format!("{field} must be between 1 and {MAX_SLA_MINUTES}"),
+                "field": field,
+            }))));
+        }
+    }
+    if body.response_minutes.is_none() && resolution_minutes.is_none() {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({
+            "error": "Set response_minutes, resolution_minutes or both",
+            "field": "resolution_minutes",
+        }))));
+    }
+
     let exists: bool = sqlx::query_scalar(
         "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)"
     )
@@ -108,19 +259,21 @@ pub async fn create(
         return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
     }
 
-    let rule = sqlx::query_as::<_, SlaRule>(
+    let rule = sqlx::query_as::<_, SlaPolicy>(
         r#"
-        INSERT INTO sla_rules (project_id, org_id, priority, deadline_hours)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO sla_policies (project_id, org_id, priority, response_minutes, resolution_minutes)
+        VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (project_id, priority) DO UPDATE
-            SET deadline_hours = EXCLUDED.deadline_hours
+            SET response_minutes = EXCLUDED.response_minutes,
+                resolution_minutes = EXCLUDED.resolution_minutes
         RETURNING *
         "#,
     )
     .bind(project_id)
     .bind(org_id)
     .bind(&body.priority)
-    .bind(body.deadline_hours)
+    .bind(body.response_minutes)
+    .bind(resolution_minutes)
     .fetch_one(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
@@ -128,7 +281,7 @@ pub async fn create(
     Ok(Json(ApiResponse::new(rule)))
 }
 
-/// DELETE /sla-rules/{id}
+/// DELETE /sla-policies/{id}
 pub async fn remove(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -138,7 +291,7 @@ pub async fn remove(
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
     let result = sqlx::query(
-        "DELETE FROM sla_rules WHERE id = $1 AND org_id = $2"
+        "DELETE FROM sla_policies WHERE id = $1 AND org_id = $2"
     )
     .bind(id)
     .bind(org_id)
@@ -149,7 +302,7 @@ pub async fn remove(
     if result.rows_affected() > 0 {
         Ok(Json(ApiResponse::new(())))
     } else {
-        Err((StatusCode::NOT_FOUND, Json(json!({"error": "SLA rule not found"}))))
+        Err((StatusCode::NOT_FOUND, Json(json!({"error": "SLA policy not found"}))))
     }
 }
 
@@ -179,10 +332,11 @@ pub async fn stats(
         r#"
         SELECT
             count(*) as total,
-            count(*) FILTER (WHERE sla_breached = false AND sla_deadline IS NOT NULL) as on_time,
+            count(*) FILTER (WHERE sla_breached IS NOT TRUE) as on_time,
             count(*) FILTER (WHERE sla_breached = true) as breached
         FROM issues
-        WHERE project_id = $1 AND sla_deadline IS NOT NULL
+        WHERE project_id = $1
+          AND (sla_response_due_at IS NOT NULL OR sla_resolution_due_at IS NOT NULL)
         "#,
     )
     .bind(project_id)
@@ -204,3 +358,134 @@ pub async fn stats(
         achievement_pct,
     })))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SlaReportParams {
+    /// Inclusive start of the created_at range (default: 30 days ago)
+    pub from: Option<NaiveDate>,
+    /// Inclusive end of the created_at range (default: today)
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, FromRow)]
+struct SlaPriorityRow {
+    priority: Option<String>,
+    total: i64,
+    breached: i64,
+    response_met: i64,
+    response_missed: i64,
+    response_pending: i64,
+    resolution_met: i64,
+    resolution_missed: i64,
+    resolution_pending: i64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+struct SlaBreachedIssue {
+    id: Uuid,
+    display_id: String,
+    title: String,
+    priority: Option<String>,
+    assignee_ids: Vec<String>,
+    sla_response_due_at: Option<DateTime<Utc>>,
+    sla_resolution_due_at: Option<DateTime<Utc>>,
+}
+
+/// GET /projects/{id}/reports/sla?from=&to= — how issues created in the
+/// range fared against their SLA targets, per priority, plus the open issues
+/// currently in breach.
+pub async fn report(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(project_id): Path<Uuid>,
+    Query(params): Query<SlaReportParams>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND org_id = $2)",
+    )
+    .bind(project_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Project not found"}))));
+    }
+
+    let to = params.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = params.from.unwrap_or(to - chrono::Duration::days(29));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, Json(json!({"error": "'from' must be on or before 'to'"}))));
+    }
+
+    let rows = sqlx::query_as::<_, SlaPriorityRow>(
+        r#"
+        SELECT
+            priority,
+            count(*) AS total,
+            count(*) FILTER (WHERE sla_breached = true) AS breached,
+            count(*) FILTER (WHERE sla_responded_at <= sla_response_due_at) AS response_met,
+            count(*) FILTER (WHERE COALESCE(sla_responded_at, now()) > sla_response_due_at) AS response_missed,
+            count(*) FILTER (WHERE sla_responded_at IS NULL AND sla_response_due_at > now()) AS response_pending,
+            count(*) FILTER (WHERE closed_at <= sla_resolution_due_at) AS resolution_met,
+            count(*) FILTER (WHERE COALESCE(closed_at, now()) > sla_resolution_due_at) AS resolution_missed,
+            count(*) FILTER (WHERE closed_at IS NULL AND sla_resolution_due_at > now()) AS resolution_pending
+        FROM issues
+        WHERE project_id = $1
+          AND (sla_response_due_at IS NOT NULL OR sla_resolution_due_at IS NOT NULL)
+          AND created_at >= $2::date AND created_at < $3::date + 1
+        GROUP BY priority
+        ORDER BY CASE priority
+            WHEN 'urgent' THEN 1 WHEN 'high' THEN 2
+            WHEN 'medium' THEN 3 ELSE 4 END
+        "#,
+    )
+    .bind(project_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let open_breached = sqlx::query_as::<_, SlaBreachedIssue>(
+        r#"
+        SELECT id, display_id, title, priority, assignee_ids, sla_response_due_at, sla_resolution_due_at
+        FROM issues
+        WHERE project_id = $1 AND sla_breached = true AND closed_at IS NULL AND archived = false
+        ORDER BY COALESCE(sla_resolution_due_at, sla_response_due_at) ASC
+        LIMIT 50
+        "#,
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let total: i64 = rows.iter().map(|r| r.total).sum();
+    let breached: i64 = rows.iter().map(|r| r.breached).sum();
+    let by_priority: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|r| json!({
+            "priority": r.priority,
+            "total": r.total,
+            "breached": r.breached,
+            "response": {"met": r.response_met, "missed": r.response_missed, "pending": r.response_pending},
+            "resolution": {"met": r.resolution_met, "missed": r.resolution_missed, "pending": r.resolution_pending},
+        }))
+        .collect();
+
+    Ok(Json(json!({
+        "project_id": project_id,
+        "from": from,
+        "to": to,
+        "total": total,
+        "breached": breached,
+        "achievement_pct": if total > 0 { ((total - breached) as f64 / total as f64 * 1000.0).round() / 10.0 } else { 100.0 },
+        "by_priority": by_priority,
+        "open_breached": open_breached,
+    })))
+}
@@ -1,10 +1,20 @@
-use axum::{extract::{Extension, State}, http::StatusCode, Json};
+use axum::{
+    body::Body,
+    extract::{Extension, Form, FromRequest, Request, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::ApiResponse;
+use crate::models::{ApiResponse, Issue};
+use crate::routes::issues::next_display_id;
+use crate::routes::sse::{broadcast_event, BaatonEvent, EventSender};
+use crate::util::hmac::verify_hmac_sha256;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct SlackIntegration {
@@ -121,85 +131,160 @@ pub async fn remove(
     Ok(Json(json!({"deleted": true})))
 }
 
-/// POST /integrations/slack/command — handle Slack slash command
-/// This is a public endpoint (Slack sends webhooks directly)
+/// Slack rejects requests older than this to stop replays; so do we.
+const SLACK_MAX_SKEW_SECS: i64 = 300;
+/// Slash command payloads are small form posts.
+const SLACK_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Verify Slack's request signature: `X-Slack-Signature` is `v0=` + hex
+/// HMAC-SHA256 of `v0:{X-Slack-Request-Timestamp}:{body}` keyed with the
+/// app's signing secret.
+fn verify_slack_signature(headers: &HeaderMap, body: &[u8], secret: &str, now: i64) -> Result<(), ()> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).ok_or(());
+    let timestamp = header("x-slack-request-timestamp")?;
+    let signature = header("x-slack-signature")?;
+    let sent_at: i64 = timestamp.parse().map_err(|_| ())?;
+    if (now - sent_at).abs() > SLACK_MAX_SKEW_SECS {
+        return Err(());
+    }
+
+    let mut base = format!("v0:{}:", timestamp).into_bytes();
+    base.extend_from_slice(body);
+    if verify_hmac_sha256(&base, secret, signature.strip_prefix("v0=").ok_or(())?) {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+fn ephemeral(text: impl Into<String>) -> Json<serde_json::Value> {
+    Json(json!({ "response_type": "ephemeral", "text": text.into() }))
+}
+
+const SLACK_USAGE: &str = "Usage: `/baaton create <issue title>`";
+
+/// POST /integrations/slack/command (also /public/slack/command) — Slack
+/// slash command. Not behind Clerk auth: requests are verified with the
+/// `SLACK_SIGNING_SECRET` HMAC instead. The channel picks the project through
+/// the integration's `channel_mappings`; the issue is credited to the Slack
+/// user and the reply is an ephemeral message with its display_id.
 pub async fn handle_command(
+    Extension(sse_tx): Extension<EventSender>,
     State(pool): State<PgPool>,
-    axum::extract::Form(form): axum::extract::Form<std::collections::HashMap<String, String>>,
+    request: Request,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    let team_id = form.get("team_id").cloned().unwrap_or_default();
-    let text = form.get("text").cloned().unwrap_or_default();
-    let user_name = form.get("user_name").cloned().unwrap_or_default();
-    let channel_id = form.get("channel_id").cloned().unwrap_or_default();
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, SLACK_MAX_BODY_BYTES).await
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e.to_string()}))))?;
 
-    // Look up integration
+    let secret = std::env::var("SLACK_SIGNING_SECRET").map_err(|_| {
+        tracing::error!("SLACK_SIGNING_SECRET not set");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Slack integration not configured"})))
+    })?;
+    verify_slack_signature(&parts.headers, &body, &secret, chrono::Utc::now().timestamp())
+        .map_err(|_| (StatusCode::UNAUTHORIZED, Json(json!({"error": "Invalid Slack signature"}))))?;
+
+    let request = Request::from_parts(parts, Body::from(body));
+    let Form(form) = Form::<HashMap<String, String>>::from_request(request, &()).await
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({"error": e.body_text()}))))?;
+    let field = |name: &str| form.get(name).map(|v| v.trim()).unwrap_or_default();
+    let (team_id, channel_id) = (field("team_id"), field("channel_id"));
+    let (user_id, user_name) = (field("user_id"), field("user_name"));
+
+    let text = field("text");
+    let title = match text.split_once(char::is_whitespace) {
+        Some(("create", title)) => title.trim(),
+        _ if text == "create" || text == "help" || text.is_empty() => return Ok(ephemeral(SLACK_USAGE)),
+        _ => text,
+    };
+
+    // Several orgs may connect the same workspace; the channel mapping decides
     let integration = sqlx::query_as::<_, (String, serde_json::Value)>(
-        "SELECT org_id, channel_mappings FROM slack_integrations WHERE team_id = $1 LIMIT 1"
+        "SELECT org_id, channel_mappings FROM slack_integrations WHERE team_id = $1 AND channel_mappings ? $2 LIMIT 1"
     )
-    .bind(&team_id)
+    .bind(team_id)
+    .bind(channel_id)
     .fetch_optional(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
     let Some((org_id, mappings)) = integration else {
-        return Ok(Json(json!({
-            "response_type": "ephemeral",
-            "text": "Baaton is not configured for this workspace. Visit baaton.dev to set up."
-        })));
+        return Ok(ephemeral("No Baaton project mapped to this channel. Configure it in Baaton settings."));
+    };
+    let project_id = mappings.get(channel_id).and_then(|v| v.as_str()).and_then(|v| Uuid::parse_str(v).ok());
+    let Some(project_id) = project_id else {
+        return Ok(ephemeral("This channel's Baaton mapping is invalid. Re-map it in Baaton settings."));
     };
 
-    // Find project for this channel
-    let project_id = mappings.get(&channel_id)
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    if project_id.is_empty() {
-        return Ok(Json(json!({
-            "response_type": "ephemeral",
-            "text": "No Baaton project mapped to this channel. Configure in Baaton settings."
-        })));
-    }
-
-    if text.is_empty() {
-        return Ok(Json(json!({
-            "response_type": "ephemeral",
-            "text": "Usage: /baaton [issue title]"
-        })));
-    }
+    let internal = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()})));
+    let mut tx = pool.begin().await.map_err(internal)?;
 
-    // Create issue
-    let pid: uuid::Uuid = project_id.parse().unwrap_or_default();
-    let issue_number: (i32,) = sqlx::query_as(
-        "SELECT COALESCE(MAX(issue_number), 0) + 1 FROM issues WHERE project_id = $1"
+    let prefix: Option<String> = sqlx::query_scalar(
+        "SELECT prefix FROM projects WHERE id = $1 AND org_id = $2 AND archived_at IS NULL"
     )
-    .bind(pid)
-    .fetch_one(&pool)
+    .bind(project_id)
+    .bind(&org_id)
+    .fetch_optional(tx.as_mut())
     .await
-    .unwrap_or((1,));
-
-    let prefix: (String,) = sqlx::query_as("SELECT prefix FROM projects WHERE id = $1")
-        .bind(pid)
-        .fetch_one(&pool)
-        .await
-        .unwrap_or(("UNK".into(),));
+    .map_err(internal)?;
+    let Some(prefix) = prefix else {
+        return Ok(ephemeral("The Baaton project mapped to this channel no longer exists or is archived."));
+    };
 
-    let display_id = format!("{}-{}", prefix.0, issue_number.0);
+    let display_id = next_display_id(tx.as_mut(), project_id, &prefix).await?;
+    let created_by_name = (!user_name.is_empty()).then(|| format!("@{} (Slack)", user_name));
 
-    sqlx::query(
-        r#"INSERT INTO issues (project_id, org_id, title, status, priority, issue_type, issue_number, display_id, source)
-           VALUES ($1, $2, $3, 'backlog', 'medium', 'feature', $4, $5, 'slack')"#,
+    let issue = sqlx::query_as::<_, Issue>(
+        r#"INSERT INTO issues (
+               project_id, display_id, title, type, status, priority, source,
+               position, created_by_id, created_by_name
+           )
+           VALUES (
+               $1, $2, $3, 'feature', 'backlog', 'medium', 'slack',
+               COALESCE((SELECT MAX(position) FROM issues WHERE project_id = $1 AND status = 'backlog'), 0) + 1000,
+               $4, $5
+           )
+           RETURNING *"#,
     )
-    .bind(pid)
-    .bind(&org_id)
-    .bind(&text)
-    .bind(issue_number.0)
+    .bind(project_id)
     .bind(&display_id)
-    .execute(&pool)
+    .bind(title.chars().take(500).collect::<String>())
+    .bind(format!("slack:{}", user_id))
+    .bind(&created_by_name)
+    .fetch_one(tx.as_mut())
     .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+    .map_err(internal)?;
+
+    tx.commit().await.map_err(internal)?;
 
-    Ok(Json(json!({
-        "response_type": "in_channel",
-        "text": format!("✅ Created {} — {}\nby @{}", display_id, text, user_name)
-    })))
+    broadcast_event(&sse_tx, &org_id, &BaatonEvent::IssueCreated((&issue).into()));
+
+    Ok(ephemeral(format!("✅ Created {} — {}", issue.display_id, issue.title)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_headers(secret: &str, timestamp: i64, body: &[u8]) -> HeaderMap {
+        let mut base = format!("v0:{}:", timestamp).into_bytes();
+        base.extend_from_slice(body);
+        let signature = crate::util::hmac::sign_hmac_sha256(&base, secret);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-slack-request-timestamp", timestamp.to_string().parse().unwrap());
+        headers.insert("x-slack-signature", format!("v0={}", signature).parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verify_slack_signature() {
+        let body = b"team_id=T1&channel_id=C1&text=create+Fix+login";
+        let headers = signed_headers("shh", 1_700_000_000, body);
+
+        assert!(verify_slack_signature(&headers, body, "shh", 1_700_000_010).is_ok());
+        assert!(verify_slack_signature(&headers, body, "wrong", 1_700_000_010).is_err());
+        assert!(verify_slack_signature(&headers, b"team_id=T2", "shh", 1_700_000_010).is_err());
+        // Replayed well after it was signed
+        assert!(verify_slack_signature(&headers, body, "shh", 1_700_000_000 + SLACK_MAX_SKEW_SECS + 1).is_err());
+    }
 }
@@ -1,18 +1,23 @@
 use axum::{
     extract::Query,
-    http::HeaderMap,
-    response::sse::{Event, KeepAlive, Sse},
+    http::{header::HeaderName, HeaderMap},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
     Extension,
 };
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
+use uuid::Uuid;
 
 use crate::middleware::AuthUser;
+use crate::models::Issue;
 
 /// Global atomic counter for SSE event IDs (monotonically increasing).
 static EVENT_COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -20,6 +25,11 @@ static EVENT_COUNTER: AtomicU64 = AtomicU64::new(1);
 /// Shared broadcast channel sender for SSE events.
 pub type EventSender = broadcast::Sender<SseEvent>;
 
+/// Flips to `true` when the server starts shutting down, so open streams end
+/// and graceful shutdown isn't held up by them.
+#[derive(Clone)]
+pub struct ShutdownSignal(pub watch::Receiver<bool>);
+
 /// An SSE event scoped to an organization with typed event name.
 #[derive(Debug, Clone, Serialize)]
 pub struct SseEvent {
@@ -50,12 +60,15 @@ pub struct EventStreamParams {
 /// - `?project_id=<uuid>` filter
 /// - Automatic keep-alive every 15s
 /// - Handles `RecvError::Lagged` gracefully (notifies client, continues)
+/// - `X-Accel-Buffering: no` so reverse proxies flush events immediately
+/// - Ends when the server shuts down; clients reconnect with `Last-Event-ID`
 pub async fn event_stream(
     Extension(auth): Extension<AuthUser>,
     Extension(tx): Extension<EventSender>,
+    Extension(ShutdownSignal(mut shutdown)): Extension<ShutdownSignal>,
     headers: HeaderMap,
     Query(params): Query<EventStreamParams>,
-) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
+) -> impl IntoResponse {
     let rx = tx.subscribe();
     let user_org_id = auth.org_id.clone().unwrap_or_default();
 
@@ -69,7 +82,7 @@ pub async fn event_stream(
     let event_type_filter = params.event_type;
     let project_id_filter = params.project_id;
 
-    let stream = BroadcastStream::new(rx).filter_map(move |msg| {
+    let stream = BroadcastStream::new(rx).filter_map(move |msg| -> Option<Result<Event, Infallible>> {
         match msg {
             Ok(evt) => {
                 // Filter by org
@@ -120,26 +133,170 @@ pub async fn event_stream(
         }
     });
 
-    Sse::new(stream).keep_alive(
+    let shutting_down = async move {
+        let _ = shutdown.wait_for(|stop| *stop).await;
+    };
+    let stream = futures::StreamExt::take_until(stream, shutting_down);
+
+    let sse = Sse::new(stream).keep_alive(
         KeepAlive::new()
             .interval(Duration::from_secs(15))
             .text("ping"),
-    )
+    );
+    ([(HeaderName::from_static("x-accel-buffering"), "no")], sse)
 }
 
-/// Broadcast a typed event to all connected SSE clients in an org.
-///
-/// # Arguments
-/// * `tx` - The shared broadcast sender
-/// * `org_id` - Organization scope
-/// * `event_type` - Event name (e.g. "issue.created", "comment.deleted")
-/// * `payload` - JSON string payload
-pub fn broadcast_event(tx: &EventSender, org_id: &str, event_type: &str, payload: &str) {
+/// Compact issue reference carried by issue events. Clients re-fetch whatever
+/// else they need; the full entity goes to webhooks.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueEnvelope {
+    pub issue_id: Uuid,
+    pub project_id: Uuid,
+    pub display_id: String,
+    pub title: String,
+    pub status: String,
+}
+
+impl From<&Issue> for IssueEnvelope {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            issue_id: issue.id,
+            project_id: issue.project_id,
+            display_id: issue.display_id.clone(),
+            title: issue.title.clone(),
+            status: issue.status.clone(),
+        }
+    }
+}
+
+/// Comment reference carried by comment events.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentEnvelope {
+    pub issue_id: Uuid,
+    pub comment_id: Uuid,
+    pub author_name: Option<String>,
+}
+
+/// Heartbeat from someone viewing an issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresenceEnvelope {
+    pub issue_id: Uuid,
+    pub user_id: String,
+    pub display_name: Option<String>,
+}
+
+/// Board move from `PATCH /issues/{id}/position`, so other clients can
+/// re-slot the card without refetching. `reindexed` means the column was
+/// re-spaced to make room: re-fetch it rather than trust cached positions.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionEnvelope {
+    pub issue_id: Uuid,
+    pub project_id: Uuid,
+    pub status: String,
+    pub position: f64,
+    pub reindexed: bool,
+}
+
+/// A real-time event. Serialized once into the SSE `data` field as
+/// `{ "type": <name>, ...fields }`; the same name is the SSE `event` name,
+/// so clients can `addEventListener` per type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum BaatonEvent {
+    #[serde(rename = "issue.created")]
+    IssueCreated(IssueEnvelope),
+    #[serde(rename = "issue.updated")]
+    IssueUpdated(IssueEnvelope),
+    #[serde(rename = "issue.status_changed")]
+    StatusChanged(IssueEnvelope),
+    #[serde(rename = "issue.archived")]
+    IssueArchived(IssueEnvelope),
+    #[serde(rename = "issue.unarchived")]
+    IssueUnarchived(IssueEnvelope),
+    #[serde(rename = "issue.deleted")]
+    IssueDeleted { issue_id: Uuid },
+    #[serde(rename = "comment.created")]
+    CommentAdded(CommentEnvelope),
+    #[serde(rename = "comment.deleted")]
+    CommentDeleted(CommentEnvelope),
+    #[serde(rename = "issue.presence")]
+    Presence(PresenceEnvelope),
+    #[serde(rename = "issue.position_changed")]
+    PositionChanged(PositionEnvelope),
+    /// Events without a dedicated variant (agent sessions, event bus).
+    /// The payload is sent as-is.
+    #[serde(skip)]
+    Custom {
+        event_type: String,
+        payload: serde_json::Value,
+    },
+}
+
+impl BaatonEvent {
+    /// SSE event name, e.g. "issue.created".
+    pub fn event_type(&self) -> &str {
+        match self {
+            Self::IssueCreated(_) => "issue.created",
+            Self::IssueUpdated(_) => "issue.updated",
+            Self::StatusChanged(_) => "issue.status_changed",
+            Self::IssueArchived(_) => "issue.archived",
+            Self::IssueUnarchived(_) => "issue.unarchived",
+            Self::IssueDeleted { .. } => "issue.deleted",
+            Self::CommentAdded(_) => "comment.created",
+            Self::CommentDeleted(_) => "comment.deleted",
+            Self::Presence(_) => "issue.presence",
+            Self::PositionChanged(_) => "issue.position_changed",
+            Self::Custom { event_type, .. } => event_type,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Self::Custom { payload, .. } => payload.to_string(),
+            _ => serde_json::to_string(self).unwrap_or_default(),
+        }
+    }
+}
+
+/// Broadcast an event to all connected SSE clients in an org.
+pub fn broadcast_event(tx: &EventSender, org_id: &str, event: &BaatonEvent) {
     let id = EVENT_COUNTER.fetch_add(1, Ordering::Relaxed);
     let _ = tx.send(SseEvent {
         id,
         org_id: org_id.to_string(),
-        event_type: event_type.to_string(),
-        payload: payload.to_string(),
+        event_type: event.event_type().to_string(),
+        payload: event.to_json(),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serializes_with_type_tag() {
+        let issue_id = Uuid::new_v4();
+        let event = BaatonEvent::CommentAdded(CommentEnvelope {
+            issue_id,
+            comment_id: Uuid::new_v4(),
+            author_name: Some("Jane".to_string()),
+        });
+        let json: serde_json::Value = serde_json::from_str(&event.to_json()).unwrap();
+        assert_eq!(json["type"], event.event_type());
+        assert_eq!(json["type"], "comment.created");
+        assert_eq!(json["issue_id"], issue_id.to_string());
+
+        let deleted = BaatonEvent::IssueDeleted { issue_id };
+        assert_eq!(
+            deleted.to_json(),
+            format!(r#"{{"type":"issue.deleted","issue_id":"{}"}}"#, issue_id)
+        );
+
+        let custom = BaatonEvent::Custom {
+            event_type: "agent_session.step".to_string(),
+            payload: serde_json::json!({"step": 1}),
+        };
+        assert_eq!(custom.event_type(), "agent_session.step");
+        assert_eq!(custom.to_json(), r#"{"step":1}"#);
+    }
+}
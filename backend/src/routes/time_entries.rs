@@ -0,0 +1,190 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::ApiResponse;
+use crate::routes::activity::log_activity;
+use crate::routes::issues::internal_err;
+
+/// Longest single entry: a full day.
+const MAX_ENTRY_MINUTES: i32 = 24 * 60;
+const MAX_NOTE_LEN: usize = 2000;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub minutes: i32,
+    pub note: Option<String>,
+    pub logged_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTimeEntry {
+    pub minutes: i32,
+    pub note: Option<String>,
+    /// When the work happened (default now); can't be in the future.
+    pub logged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeEntryList {
+    pub total_minutes: i64,
+    pub entries: Vec<TimeEntry>,
+}
+
+fn validation_err(field: &str, error: String) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({"error": error, "code": "validation_error", "field": field})),
+    )
+}
+
+/// The issue's project, if the issue belongs to the caller's org.
+async fn issue_project(
+    pool: &PgPool,
+    auth: &AuthUser,
+    issue_id: Uuid,
+) -> Result<(String, Uuid), (StatusCode, Json<Value>)> {
+    let org_id = auth.org_id.as_deref().ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "Organization required"})),
+        )
+    })?;
+    let project_id: Uuid = sqlx::query_scalar(
+        "SELECT i.project_id FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2",
+    )
+    .bind(issue_id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(internal_err)?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Issue not found"})),
+        )
+    })?;
+    Ok((org_id.to_string(), project_id))
+}
+
+/// Sum of minutes logged on an issue.
+pub async fn total_minutes(pool: &PgPool, issue_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(minutes), 0)::bigint FROM time_entries WHERE issue_id = $1",
+    )
+    .bind(issue_id)
+    .fetch_one(pool)
+    .await
+    .unwrap_or(0)
+}
+
+/// POST /issues/{id}/time — log time spent on an issue.
+pub async fn create(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+    Json(body): Json<CreateTimeEntry>,
+) -> Result<Json<ApiResponse<TimeEntry>>, (StatusCode, Json<Value>)> {
+    if !(1..=MAX_ENTRY_MINUTES).contains(&body.minutes) {
+        return Err(validation_err(
+            "minutes",
+            format!("minutes must be between 1 and {}", MAX_ENTRY_MINUTES),
+        ));
+    }
+    let note = body
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    if note.is_some_and(|n| n.chars().count() > MAX_NOTE_LEN) {
+        return Err(validation_err(
+            "note",
+            format!("note too long (max {} chars)", MAX_NOTE_LEN),
+        ));
+    }
+    let logged_at = body.logged_at.unwrap_or_else(Utc::now);
+    if logged_at > Utc::now() {
+        return Err(validation_err(
+            "logged_at",
+            "logged_at can't be in the future".to_string(),
+        ));
+    }
+
+    let (org_id, project_id) = issue_project(&pool, &auth, issue_id).await?;
+
+    let entry = sqlx::query_as::<_, TimeEntry>(
+        r#"INSERT INTO time_entries (issue_id, user_id, user_name, minutes, note, logged_at)
+           VALUES ($1, $2, $3, $4, $5, $6)
+           RETURNING *"#,
+    )
+    .bind(issue_id)
+    .bind(&auth.user_id)
+    .bind(&auth.display_name)
+    .bind(body.minutes)
+    .bind(note)
+    .bind(logged_at)
+    .fetch_one(&pool)
+    .await
+    .map_err(internal_err)?;
+
+    {
+        let pool2 = pool.clone();
+        let uid = auth.user_id.clone();
+        let uname = auth.display_name.clone();
+        let minutes = entry.minutes.to_string();
+        tokio::spawn(async move {
+            log_activity(
+                &pool2,
+                &org_id,
+                Some(project_id),
+                Some(issue_id),
+                &uid,
+                uname.as_deref(),
+                "time_logged",
+                Some("minutes"),
+                None,
+                Some(&minutes),
+                None,
+            )
+            .await;
+        });
+    }
+
+    Ok(Json(ApiResponse::new(entry)))
+}
+
+/// GET /issues/{id}/time — every entry on the issue, newest first, with the total.
+pub async fn list(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TimeEntryList>>, (StatusCode, Json<Value>)> {
+    issue_project(&pool, &auth, issue_id).await?;
+
+    let entries = sqlx::query_as::<_, TimeEntry>(
+        "SELECT * FROM time_entries WHERE issue_id = $1 ORDER BY logged_at DESC",
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_err)?;
+    let total_minutes = entries.iter().map(|e| i64::from(e.minutes)).sum();
+
+    Ok(Json(ApiResponse::new(TimeEntryList {
+        total_minutes,
+        entries,
+    })))
+}
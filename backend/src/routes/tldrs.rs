@@ -4,7 +4,45 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
-use crate::models::{ApiResponse, CreateTldr, Tldr};
+use crate::models::{ApiResponse, CreateTldr, Tldr, UpdateTldr};
+
+/// Canonical `tests_status` values; the UI renders a badge for each.
+const VALID_TESTS_STATUSES: &[&str] = &["passed", "failed", "skipped", "none"];
+
+/// Map common spellings ("PASS", "passing", "green"…) onto `VALID_TESTS_STATUSES`.
+fn normalize_tests_status(raw: &str) -> Option<&'static str> {
+    Some(match raw.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+        "passed" | "pass" | "passing" | "ok" | "green" | "success" | "succeeded" => "passed",
+        "failed" | "fail" | "failing" | "failure" | "red" | "error" | "broken" => "failed",
+        "skipped" | "skip" | "skipping" => "skipped",
+        "none" | "" | "n/a" | "na" | "not_run" | "no_tests" => "none",
+        _ => return None,
+    })
+}
+
+/// The issue's `github_pr_links` row for `pr_url`, so the TLDR can point at the real PR.
+async fn find_pr_link(pool: &PgPool, issue_id: Uuid, pr_url: Option<&str>) -> Option<Uuid> {
+    let pr_url = pr_url?.trim().trim_end_matches('/');
+    sqlx::query_scalar("SELECT id FROM github_pr_links WHERE issue_id = $1 AND pr_url = $2 LIMIT 1")
+        .bind(issue_id)
+        .bind(pr_url)
+        .fetch_optional(pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "tldr pr link lookup failed");
+            None
+        })
+}
+
+/// `normalize_tests_status` for request bodies, with the repo's 400 shape.
+fn validate_tests_status(raw: Option<&str>) -> Result<Option<&'static str>, (StatusCode, Json<serde_json::Value>)> {
+    let Some(raw) = raw else { return Ok(None) };
+    normalize_tests_status(raw).map(Some).ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({
+        "error": format!("Invalid tests_status: '{}'. Valid values: {}", raw, VALID_TESTS_STATUSES.join(", ")),
+        "accepted_values": VALID_TESTS_STATUSES,
+        "field": "tests_status"
+    }))))
+}
 
 pub async fn create(
     Extension(auth): Extension<AuthUser>,
@@ -15,6 +53,8 @@ pub async fn create(
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
+    let tests_status = validate_tests_status(body.tests_status.as_deref())?.unwrap_or("none");
+
     // Verify issue belongs to org
     let exists: bool = sqlx::query_scalar(
         "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)"
@@ -33,10 +73,12 @@ pub async fn create(
     let edge_cases = body.edge_cases.clone().unwrap_or_default();
     let context_updates = body.context_updates.clone().unwrap_or_default();
 
+    let github_pr_link_id = find_pr_link(&pool, issue_id, body.pr_url.as_deref()).await;
+
     let tldr = sqlx::query_as::<_, Tldr>(
         r#"
-        INSERT INTO tldrs (issue_id, agent_name, summary, files_changed, tests_status, pr_url, decisions_made, edge_cases, context_updates)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        INSERT INTO tldrs (issue_id, agent_name, summary, files_changed, tests_status, pr_url, decisions_made, edge_cases, context_updates, github_pr_link_id, commit_sha)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         RETURNING *
         "#,
     )
@@ -44,11 +86,13 @@ pub async fn create(
     .bind(&body.agent_name)
     .bind(&body.summary)
     .bind(&body.files_changed.unwrap_or_default())
-    .bind(body.tests_status.as_deref().unwrap_or("none"))
+    .bind(tests_status)
     .bind(&body.pr_url)
     .bind(&decisions_made)
     .bind(&edge_cases)
     .bind(&context_updates)
+    .bind(github_pr_link_id)
+    .bind(&body.commit_sha)
     .fetch_one(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
@@ -114,3 +158,116 @@ pub async fn create(
 
     Ok(Json(ApiResponse::with_hints(tldr, hints)))
 }
+
+/// GET /issues/{id}/tldr — All TLDRs posted on an issue, newest first.
+pub async fn list(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Tldr>>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM issues i JOIN projects p ON p.id = i.project_id WHERE i.id = $1 AND p.org_id = $2)"
+    )
+    .bind(issue_id)
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap_or(false);
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "Issue not found"}))));
+    }
+
+    let tldrs = sqlx::query_as::<_, Tldr>(
+        "SELECT * FROM tldrs WHERE issue_id = $1 ORDER BY created_at DESC"
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(tldrs)))
+}
+
+/// PATCH /tldr/{id} — Revise a TLDR as the work progresses. Omitted fields are kept.
+pub async fn update(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateTldr>,
+) -> Result<Json<ApiResponse<Tldr>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    let tests_status = validate_tests_status(body.tests_status.as_deref())?;
+
+    // A new pr_url re-resolves the PR link; otherwise the current one is kept.
+    let github_pr_link_id = match body.pr_url.as_deref() {
+        Some(pr_url) => {
+            let issue_id: Option<Uuid> = sqlx::query_scalar("SELECT issue_id FROM tldrs WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+            match issue_id {
+                Some(issue_id) => find_pr_link(&pool, issue_id, Some(pr_url)).await,
+                None => None,
+            }
+        }
+        None => None,
+    };
+
+    let tldr = sqlx::query_as::<_, Tldr>(
+        r#"
+        UPDATE tldrs t SET
+            summary = COALESCE($3, t.summary),
+            files_changed = COALESCE($4, t.files_changed),
+            tests_status = COALESCE($5, t.tests_status),
+            pr_url = COALESCE($6, t.pr_url),
+            github_pr_link_id = CASE WHEN $6 IS NULL THEN t.github_pr_link_id ELSE $7 END,
+            commit_sha = COALESCE($8, t.commit_sha)
+        FROM issues i JOIN projects p ON p.id = i.project_id
+        WHERE t.id = $1 AND i.id = t.issue_id AND p.org_id = $2
+        RETURNING t.*
+        "#,
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(&body.summary)
+    .bind(&body.files_changed)
+    .bind(tests_status)
+    .bind(&body.pr_url)
+    .bind(github_pr_link_id)
+    .bind(&body.commit_sha)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "TLDR not found"}))))?;
+
+    crate::routes::webhooks::dispatch_event(
+        pool.clone(),
+        org_id.to_string(),
+        "tldr.updated",
+        serde_json::to_value(&tldr).unwrap_or_default(),
+    ).await;
+
+    Ok(Json(ApiResponse::new(tldr)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tests_status() {
+        assert_eq!(normalize_tests_status("PASS"), Some("passed"));
+        assert_eq!(normalize_tests_status(" passing "), Some("passed"));
+        assert_eq!(normalize_tests_status("green"), Some("passed"));
+        assert_eq!(normalize_tests_status("Failing"), Some("failed"));
+        assert_eq!(normalize_tests_status("skip"), Some("skipped"));
+        assert_eq!(normalize_tests_status("not run"), Some("none"));
+        assert_eq!(normalize_tests_status("flaky"), None);
+    }
+}
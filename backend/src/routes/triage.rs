@@ -14,13 +14,24 @@ use crate::routes::issues::fetch_user_org_ids;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TriageSuggestion {
+    #[serde(default)]
+    pub suggested_type: Option<String>,
     pub suggested_priority: Option<String>,
+    #[serde(default)]
     pub suggested_tags: Vec<String>,
     pub suggested_assignee: Option<String>,
+    #[serde(default)]
     pub similar_issues: Vec<SimilarIssue>,
+    /// One-line summary of the issue, for triage lists
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
     pub reasoning: String,
 }
 
+const ISSUE_TYPES: &[&str] = &["bug", "feature", "improvement", "question"];
+const PRIORITIES: &[&str] = &["urgent", "high", "medium", "low"];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SimilarIssue {
     pub id: String,
@@ -88,7 +99,7 @@ pub async fn run_triage_analysis(
         .join(", ");
 
     let prompt = format!(
-        r#"You are a triage assistant. Analyze this issue and suggest priority, tags, and assignee.
+        r#"You are a triage assistant. Analyze this issue and suggest type, priority, tags, and assignee, and summarize it in one sentence.
 
 ISSUE:
 Title: {}
@@ -102,10 +113,12 @@ RECENT ISSUES:
 
 Respond ONLY with valid JSON:
 {{
+  "suggested_type": "bug|feature|improvement|question",
   "suggested_priority": "urgent|high|medium|low",
   "suggested_tags": ["tag1"],
   "suggested_assignee": null,
   "similar_issues": [],
+  "summary": "One-sentence summary",
   "reasoning": "Brief explanation"
 }}"#,
         title,
@@ -132,8 +145,7 @@ Respond ONLY with valid JSON:
         return Err("GEMINI_API_KEY not set".to_string());
     }
 
-    let client = reqwest::Client::new();
-    let resp = client
+    let resp = crate::util::http::gemini()
         .post(format!("https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent?key={}", api_key))
         .json(&serde_json::json!({
             "contents": [{"parts": [{"text": prompt}]}],
@@ -142,6 +154,12 @@ Respond ONLY with valid JSON:
         .send()
         .await
         .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "AI service returned status {}",
+            resp.status().as_u16()
+        ));
+    }
 
     let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
     let text = body["candidates"][0]["content"]["parts"][0]["text"]
@@ -156,13 +174,25 @@ Respond ONLY with valid JSON:
 
     let mut suggestion: TriageSuggestion =
         serde_json::from_str(clean).unwrap_or(TriageSuggestion {
+            suggested_type: None,
             suggested_priority: Some("medium".into()),
             suggested_tags: vec![],
             suggested_assignee: None,
             similar_issues: vec![],
+            summary: None,
             reasoning: "AI parse failed".to_string(),
         });
 
+    // Drop values the issue columns would reject
+    suggestion.suggested_type = suggestion
+        .suggested_type
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| ISSUE_TYPES.contains(&t.as_str()));
+    suggestion.suggested_priority = suggestion
+        .suggested_priority
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| PRIORITIES.contains(&p.as_str()));
+
     // Enrich similar issues with IDs
     suggestion.similar_issues = suggestion
         .similar_issues
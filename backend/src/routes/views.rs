@@ -22,6 +22,10 @@ pub struct CustomView {
     pub visibility: String,
     pub pinned: bool,
     pub created_at: Option<DateTime<Utc>>,
+    /// `field` or `field:asc|desc` — see `view_order`.
+    pub sort: Option<String>,
+    /// The creator's default view for `project_id` (at most one per user/project).
+    pub is_default: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,6 +36,7 @@ pub struct CreateView {
     pub visibility: Option<String>,
     pub pinned: Option<bool>,
     pub project_id: Option<Uuid>,
+    pub sort: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,11 +46,13 @@ pub struct UpdateView {
     pub display_options: Option<serde_json::Value>,
     pub visibility: Option<String>,
     pub pinned: Option<bool>,
+    pub sort: Option<String>,
 }
 
 // ─── Handlers ─────────────────────────────────────────
 
-/// GET /views — personal + shared views for org
+/// GET /views — personal + shared views for org. `is_default` is only ever
+/// true on the caller's own default views.
 pub async fn list(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -53,7 +60,7 @@ pub async fn list(
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
 
-    let views = sqlx::query_as::<_, CustomView>(
+    let mut views = sqlx::query_as::<_, CustomView>(
         r#"SELECT * FROM custom_views
            WHERE org_id = $1
              AND (created_by = $2 OR visibility = 'shared')
@@ -68,6 +75,11 @@ pub async fn list(
         vec![]
     });
 
+    // Someone else's default is not the caller's
+    for view in views.iter_mut().filter(|v| v.created_by != auth.user_id) {
+        view.is_default = false;
+    }
+
     Ok(Json(ApiResponse::new(views)))
 }
 
@@ -84,10 +96,12 @@ pub async fn create(
     if !["personal", "shared"].contains(&visibility) {
         return Err((StatusCode::BAD_REQUEST, Json(json!({"error": "visibility must be 'personal' or 'shared'"}))));
     }
+    // Reject a sort GET /views/{id}/issues couldn't apply
+    view_order(body.sort.as_deref())?;
 
     let view = sqlx::query_as::<_, CustomView>(
-        r#"INSERT INTO custom_views (org_id, project_id, created_by, name, filters, display_options, visibility, pinned)
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        r#"INSERT INTO custom_views (org_id, project_id, created_by, name, filters, display_options, visibility, pinned, sort)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
            RETURNING *"#
     )
     .bind(org_id)
@@ -98,6 +112,7 @@ pub async fn create(
     .bind(body.display_options.unwrap_or_else(|| json!({})))
     .bind(visibility)
     .bind(body.pinned.unwrap_or(false))
+    .bind(body.sort.as_deref())
     .fetch_one(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
@@ -105,7 +120,40 @@ pub async fn create(
     Ok(Json(ApiResponse::new(view)))
 }
 
-/// PATCH /views/{id}
+/// Who may modify a view: its creator, or an org admin when the view is
+/// shared. Others get 403 on shared views and 404 on personal ones (which
+/// they can't see in the first place).
+async fn authorize_view_write(
+    pool: &PgPool,
+    auth: &AuthUser,
+    org_id: &str,
+    id: Uuid,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let row = sqlx::query_as::<_, (String, String)>(
+        "SELECT created_by, visibility FROM custom_views WHERE id = $1 AND org_id = $2"
+    )
+    .bind(id)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let is_org_admin = auth.org_role.as_deref()
+        .map(|r| r.contains("admin"))
+        .unwrap_or(false);
+
+    match row {
+        Some((created_by, _)) if created_by == auth.user_id => Ok(()),
+        Some((_, visibility)) if visibility == "shared" && is_org_admin => Ok(()),
+        Some((_, visibility)) if visibility == "shared" => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({"error": "Only the creator or an org admin can modify a shared view"})),
+        )),
+        _ => Err((StatusCode::NOT_FOUND, Json(json!({"error": "View not found"})))),
+    }
+}
+
+/// PATCH /views/{id} — creator, or org admins for shared views
 pub async fn update(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -114,6 +162,8 @@ pub async fn update(
 ) -> Result<Json<ApiResponse<CustomView>>, (StatusCode, Json<serde_json::Value>)> {
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    authorize_view_write(&pool, &auth, org_id, id).await?;
+    view_order(body.sort.as_deref())?;
 
     let view = sqlx::query_as::<_, CustomView>(
         r#"UPDATE custom_views
@@ -121,7 +171,8 @@ pub async fn update(
                filters         = COALESCE($2, filters),
                display_options = COALESCE($3, display_options),
                visibility      = COALESCE($4, visibility),
-               pinned          = COALESCE($5, pinned)
+               pinned          = COALESCE($5, pinned),
+               sort            = COALESCE($8, sort)
            WHERE id = $6 AND org_id = $7
            RETURNING *"#
     )
@@ -132,6 +183,7 @@ pub async fn update(
     .bind(body.pinned)
     .bind(id)
     .bind(org_id)
+    .bind(body.sort.as_deref())
     .fetch_optional(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
@@ -140,7 +192,7 @@ pub async fn update(
     Ok(Json(ApiResponse::new(view)))
 }
 
-/// DELETE /views/{id} — only creator can delete
+/// DELETE /views/{id} — creator, or org admins for shared views
 pub async fn remove(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -148,33 +200,120 @@ pub async fn remove(
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     let org_id = auth.org_id.as_deref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+    authorize_view_write(&pool, &auth, org_id, id).await?;
 
     let result = sqlx::query(
-        "DELETE FROM custom_views WHERE id = $1 AND org_id = $2 AND created_by = $3"
+        "DELETE FROM custom_views WHERE id = $1 AND org_id = $2"
     )
     .bind(id)
     .bind(org_id)
-    .bind(&auth.user_id)
     .execute(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
 
     if result.rows_affected() == 0 {
-        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "View not found or not authorized"}))));
+        return Err((StatusCode::NOT_FOUND, Json(json!({"error": "View not found"}))));
     }
 
     Ok(StatusCode::NO_CONTENT)
 }
 
-/// GET /views/{id}/issues — apply stored filters and return matching issues
+/// POST /views/{id}/make-default — make one of the caller's views their
+/// default for its project, clearing the previous default in the same
+/// transaction.
+pub async fn make_default(
+    Extension(auth): Extension<AuthUser>,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<CustomView>>, (StatusCode, Json<serde_json::Value>)> {
+    let org_id = auth.org_id.as_deref()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(json!({"error": "Organization required"}))))?;
+
+    let mut tx = pool.begin().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let project_id: Option<Uuid> = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT project_id FROM custom_views WHERE id = $1 AND org_id = $2 AND created_by = $3 FOR UPDATE"
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(&auth.user_id)
+    .fetch_optional(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?
+    .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "View not found or not authorized"}))))?;
+
+    sqlx::query(
+        r#"UPDATE custom_views SET is_default = false
+           WHERE org_id = $1 AND created_by = $2
+             AND project_id IS NOT DISTINCT FROM $3
+             AND is_default AND id <> $4"#
+    )
+    .bind(org_id)
+    .bind(&auth.user_id)
+    .bind(project_id)
+    .bind(id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    let view = sqlx::query_as::<_, CustomView>(
+        "UPDATE custom_views SET is_default = true WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    tx.commit().await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()}))))?;
+
+    Ok(Json(ApiResponse::new(view)))
+}
+
+/// String list under the first of `keys` present (the web app saves plural
+/// keys like `statuses`, the API documents singular ones like `status`).
+fn string_list(filters: &serde_json::Value, keys: &[&str]) -> Vec<String> {
+    keys.iter()
+        .find_map(|k| filters.get(*k).and_then(|v| v.as_array()))
+        .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+/// Resolve a view's `sort` through the same ordering rules as
+/// `GET /projects/{id}/issues`. Accepts the web app's sort modes too.
+fn view_order(sort: Option<&str>) -> Result<(&'static str, &'static str), (StatusCode, Json<serde_json::Value>)> {
+    let Some(sort) = sort.filter(|s| !s.is_empty()) else {
+        return Ok(("i.created_at", "DESC"));
+    };
+    let (field, direction) = match sort.split_once(':') {
+        Some((field, direction)) => (field, Some(direction)),
+        None => (sort, None),
+    };
+    let field = match field {
+        "manual" => "position",
+        "created" => "created_at",
+        "updated" => "updated_at",
+        other => other,
+    };
+    crate::routes::issues::issue_order(Some(field), direction)
+}
+
+/// GET /views/{id}/issues — apply stored filters and sort, return matching issues
 ///
-/// Filter keys supported:
-///   status        → []string
-///   priority      → []string
-///   assignee_ids  → []string  (any match)
-///   tags          → []string  (any match)
-///   due_before    → "YYYY-MM-DD"
-///   is_overdue    → bool
+/// Filter keys supported (plural aliases in parentheses):
+///   status (statuses)       → []string
+///   priority (priorities)   → []string
+///   type (types)            → []string
+///   assignee_ids (assignees)→ []string  (any match)
+///   tags                    → []string  (any match)
+///   category                → []string  (any match)
+///   projects                → []uuid    (org-wide views only)
+///   search                  → string    (title substring or display_id prefix)
+///   due_before              → "YYYY-MM-DD"
+///   is_overdue              → bool
+///   include_archived        → bool
+///   include_snoozed         → bool
 pub async fn get_issues(
     Extension(auth): Extension<AuthUser>,
     State(pool): State<PgPool>,
@@ -198,6 +337,7 @@ pub async fn get_issues(
     .ok_or_else(|| (StatusCode::NOT_FOUND, Json(json!({"error": "View not found"}))))?;
 
     let filters = &view.filters;
+    let (order_col, order_dir) = view_order(view.sort.as_deref())?;
 
     // ── Build dynamic query ───────────────────────────────
     let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
@@ -207,54 +347,63 @@ pub async fn get_issues(
            WHERE p.org_id = "#
     );
     qb.push_bind(org_id);
-    qb.push(" AND i.archived = false");
 
-    // filter: status
-    if let Some(statuses) = filters.get("status").and_then(|v| v.as_array()) {
-        let vals: Vec<String> = statuses.iter()
-            .filter_map(|s| s.as_str().map(String::from))
-            .collect();
-        if !vals.is_empty() {
-            qb.push(" AND i.status = ANY(");
-            qb.push_bind(vals);
-            qb.push(")");
-        }
+    if filters.get("include_archived").and_then(|v| v.as_bool()) != Some(true) {
+        qb.push(" AND i.archived = false");
+    }
+    if filters.get("include_snoozed").and_then(|v| v.as_bool()) != Some(true) {
+        qb.push(" AND (i.snoozed_until IS NULL OR i.snoozed_until <= now())");
     }
 
-    // filter: priority
-    if let Some(priorities) = filters.get("priority").and_then(|v| v.as_array()) {
-        let vals: Vec<String> = priorities.iter()
-            .filter_map(|s| s.as_str().map(String::from))
-            .collect();
+    // filter: status / priority / type (exact match against any value)
+    for (column, keys) in [
+        ("i.status", &["status", "statuses"][..]),
+        ("i.priority", &["priority", "priorities"][..]),
+        ("i.type", &["type", "types"][..]),
+    ] {
+        let vals = string_list(filters, keys);
         if !vals.is_empty() {
-            qb.push(" AND i.priority = ANY(");
+            qb.push(format!(" AND {} = ANY(", column));
             qb.push_bind(vals);
             qb.push(")");
         }
     }
 
-    // filter: assignee_ids (any match via &&)
-    if let Some(assignees) = filters.get("assignee_ids").and_then(|v| v.as_array()) {
-        let vals: Vec<String> = assignees.iter()
-            .filter_map(|s| s.as_str().map(String::from))
-            .collect();
+    // filter: assignee_ids / tags / category (array overlap via &&)
+    for (column, keys) in [
+        ("i.assignee_ids", &["assignee_ids", "assignees"][..]),
+        ("i.tags", &["tags"][..]),
+        ("i.category", &["category"][..]),
+    ] {
+        let vals = string_list(filters, keys);
         if !vals.is_empty() {
-            qb.push(" AND i.assignee_ids && ");
+            qb.push(format!(" AND {} && ", column));
             qb.push_bind(vals);
         }
     }
 
-    // filter: tags (any match via &&)
-    if let Some(tags) = filters.get("tags").and_then(|v| v.as_array()) {
-        let vals: Vec<String> = tags.iter()
-            .filter_map(|s| s.as_str().map(String::from))
+    // filter: projects (ignored when the view is pinned to one project)
+    if view.project_id.is_none() {
+        let project_ids: Vec<Uuid> = string_list(filters, &["projects"])
+            .iter()
+            .filter_map(|p| p.parse().ok())
             .collect();
-        if !vals.is_empty() {
-            qb.push(" AND i.tags && ");
-            qb.push_bind(vals);
+        if !project_ids.is_empty() {
+            qb.push(" AND i.project_id = ANY(");
+            qb.push_bind(project_ids);
+            qb.push(")");
         }
     }
 
+    // filter: search — same semantics as the issue list `search` param
+    if let Some(search) = filters.get("search").and_then(|v| v.as_str()).filter(|s| !s.trim().is_empty()) {
+        qb.push(" AND (i.title ILIKE '%' || ");
+        qb.push_bind(search.to_string());
+        qb.push(" || '%' OR i.display_id ILIKE ");
+        qb.push_bind(search.to_string());
+        qb.push(" || '%')");
+    }
+
     // filter: due_before
     if let Some(due_str) = filters.get("due_before").and_then(|v| v.as_str()) {
         if let Ok(due_date) = chrono::NaiveDate::parse_from_str(due_str, "%Y-%m-%d") {
@@ -274,7 +423,7 @@ pub async fn get_issues(
         qb.push_bind(project_id);
     }
 
-    qb.push(" ORDER BY i.created_at DESC LIMIT 500");
+    qb.push(format!(" ORDER BY {} {}, i.id LIMIT 500", order_col, order_dir));
 
     let issues = qb
         .build_query_as::<Issue>()
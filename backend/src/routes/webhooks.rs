@@ -3,16 +3,15 @@ use axum::{
     http::StatusCode,
     Extension, Json,
 };
-use hmac::{Hmac, Mac};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sha2::Sha256;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::ApiResponse;
+use crate::util::hmac::sign_hmac_sha256;
 
 // ─── Event types ────────────────────────────────────
 
@@ -41,6 +40,7 @@ pub const VALID_EVENT_TYPES: &[&str] = &[
     "sprint.completed",
     // TLDRs
     "tldr.created",
+    "tldr.updated",
     // Approval
     "approval.requested",
     "approval.responded",
@@ -162,10 +162,7 @@ pub async fn dispatch_event(pool: PgPool, org_id: String, event_type: &str, payl
             "delivery_id": delivery_id.to_string(),
         });
 
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .unwrap_or_default();
+        let client = crate::util::http::webhooks();
 
         let body_str = serde_json::to_string(&body).unwrap_or_default();
 
@@ -306,10 +303,7 @@ pub async fn retry_worker(pool: PgPool) {
 
         tracing::debug!(count = deliveries.len(), "webhook_retry: processing retries");
 
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .unwrap_or_default();
+        let client = crate::util::http::webhooks();
 
         for (delivery_id, webhook_id, url, payload, attempts) in deliveries {
             let body_str = serde_json::to_string(&payload).unwrap_or_default();
@@ -412,12 +406,7 @@ pub async fn retry_worker(pool: PgPool) {
 }
 
 fn compute_signature(secret: &str, body: &str) -> String {
-    type HmacSha256 = Hmac<Sha256>;
-    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
-        .expect("HMAC can take key of any size");
-    mac.update(body.as_bytes());
-    let result = mac.finalize();
-    format!("sha256={}", hex::encode(result.into_bytes()))
+    format!("sha256={}", sign_hmac_sha256(body.as_bytes(), secret))
 }
 
 // ─── Route handlers ─────────────────────────────────
@@ -0,0 +1,157 @@
+//! Minimal S3-compatible object storage client (AWS S3, Cloudflare R2, MinIO…)
+//! used for issue attachment uploads. Requests are signed with SigV4 and use
+//! path-style URLs (`{endpoint}/{bucket}/{key}`), which every provider accepts.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone)]
+pub struct StorageClient {
+    http: reqwest::Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    /// Base for the URLs handed back to clients (CDN / public bucket domain).
+    public_url: String,
+}
+
+impl StorageClient {
+    /// Returns None unless S3_ENDPOINT, S3_BUCKET, S3_ACCESS_KEY_ID and
+    /// S3_SECRET_ACCESS_KEY are all set (uploads are then disabled).
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("S3_ENDPOINT").ok()?;
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+        let bucket = std::env::var("S3_BUCKET").ok()?;
+        let access_key_id = std::env::var("S3_ACCESS_KEY_ID").ok()?;
+        let secret_access_key = std::env::var("S3_SECRET_ACCESS_KEY").ok()?;
+        let region = std::env::var("S3_REGION").unwrap_or_else(|_| "auto".to_string());
+        let public_url = std::env::var("S3_PUBLIC_URL")
+            .map(|u| u.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| format!("{}/{}", endpoint, bucket));
+
+        tracing::info!("Storage client initialized (bucket: {})", bucket);
+
+        Some(Self {
+            http: crate::util::http::storage().clone(),
+            endpoint,
+            bucket,
+            region,
+            access_key_id,
+            secret_access_key,
+            public_url,
+        })
+    }
+
+    /// Public URL of an object key.
+    pub fn url_for(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+
+    /// Inverse of `url_for` — None for URLs that aren't ours.
+    pub fn key_for_url<'a>(&self, url: &'a str) -> Option<&'a str> {
+        url.strip_prefix(&self.public_url)?.strip_prefix('/')
+    }
+
+    /// Upload an object and return its public URL.
+    pub async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> Result<String, String> {
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let request = self
+            .signed(reqwest::Method::PUT, key, &payload_hash)?
+            .header("content-type", content_type)
+            .body(body);
+        self.send(request).await?;
+        Ok(self.url_for(key))
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), String> {
+        let payload_hash = hex::encode(Sha256::digest(b""));
+        let request = self.signed(reqwest::Method::DELETE, key, &payload_hash)?;
+        self.send(request).await
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<(), String> {
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| format!("storage request failed: {}", e))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(format!("storage returned {}: {}", status, text));
+        }
+        Ok(())
+    }
+
+    /// Build a SigV4-signed request for `key`. Keys must already be URL-safe.
+    fn signed(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        payload_hash: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        let url = format!("{}/{}/{}", self.endpoint, self.bucket, key);
+        let parsed =
+            reqwest::Url::parse(&url).map_err(|e| format!("invalid S3_ENDPOINT: {}", e))?;
+        let host = match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err("invalid S3_ENDPOINT: missing host".to_string()),
+        };
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method,
+            parsed.path(),
+            host,
+            payload_hash,
+            amz_date,
+            signed_headers,
+            payload_hash
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut signing_key = format!("AWS4{}", self.secret_access_key).into_bytes();
+        for part in [date.as_str(), self.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part.as_bytes());
+        }
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        );
+
+        Ok(self
+            .http
+            .request(method, parsed)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization))
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
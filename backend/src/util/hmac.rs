@@ -0,0 +1,57 @@
+//! HMAC-SHA256 signatures for webhooks: verifying inbound ones (GitHub, Slack)
+//! and signing outbound Baaton webhook deliveries.
+//!
+//! Usage:
+//! ```rust
+//! if !verify_hmac_sha256(&body, &secret, hex_sig) {
+//!     return Err(StatusCode::UNAUTHORIZED);
+//! }
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hex-encoded HMAC-SHA256 of `body` keyed with `secret`.
+pub fn sign_hmac_sha256(body: &[u8], secret: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Whether `hex_sig` is the HMAC-SHA256 of `body` keyed with `secret`.
+/// Compared in constant time; malformed hex is simply a mismatch. Callers
+/// strip any scheme prefix (`sha256=`, `v0=`) first.
+pub fn verify_hmac_sha256(body: &[u8], secret: &str, hex_sig: &str) -> bool {
+    let Ok(sig_bytes) = hex::decode(hex_sig.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&sig_bytes).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256_round_trip() {
+        // RFC 4231, test case 2
+        let msg = b"what do ya want for nothing?";
+        let sig = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+        assert_eq!(sign_hmac_sha256(msg, "Jefe"), sig);
+        assert!(verify_hmac_sha256(msg, "Jefe", sig));
+        assert!(!verify_hmac_sha256(
+            b"what do ya want for nothing!",
+            "Jefe",
+            sig
+        ));
+        assert!(!verify_hmac_sha256(msg, "Jefe", "not-hex"));
+        assert!(!verify_hmac_sha256(msg, "Jefe", &sig[..32]));
+    }
+}
@@ -0,0 +1,90 @@
+//! Shared outbound HTTP clients, one per upstream. Building a client once keeps
+//! its connection pool and TLS sessions warm, and every client carries timeouts
+//! so a hung upstream can't pin a request handler.
+//!
+//! - `HTTP_CONNECT_TIMEOUT_SECS` (default 5): TCP + TLS connect.
+//! - `HTTP_TIMEOUT_SECS` (default 20): whole request, for JSON APIs.
+//! - `GEMINI_READ_TIMEOUT_SECS` (default 120): max gap between reads from
+//!   Gemini. No total timeout there, so long streams keep flowing.
+//!
+//! Outgoing webhooks keep their fixed 10s budget, and S3 uploads only get a
+//! read timeout so large attachments aren't cut off.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+fn env_secs(name: &str, default: u64) -> Duration {
+    let secs = std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&s| s > 0)
+        .unwrap_or(default);
+    Duration::from_secs(secs)
+}
+
+fn connect_timeout() -> Duration {
+    env_secs("HTTP_CONNECT_TIMEOUT_SECS", 5)
+}
+
+/// Client for request/response JSON APIs: connect and total timeouts.
+fn api_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(connect_timeout())
+        .timeout(env_secs("HTTP_TIMEOUT_SECS", 20))
+        .build()
+        .expect("failed to build HTTP client")
+}
+
+/// Clerk Backend API and JWKS.
+pub fn clerk() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(api_client)
+}
+
+/// Novu notification API.
+pub fn novu() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(api_client)
+}
+
+/// GitHub REST API (public repo metadata).
+pub fn github() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(api_client)
+}
+
+/// Customer webhook endpoints, for deliveries and retries.
+pub fn webhooks() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(connect_timeout())
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("failed to build HTTP client")
+    })
+}
+
+/// S3-compatible attachment storage.
+pub fn storage() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(connect_timeout())
+            .read_timeout(env_secs("HTTP_TIMEOUT_SECS", 20))
+            .build()
+            .expect("failed to build HTTP client")
+    })
+}
+
+/// Gemini, including streamed responses.
+pub fn gemini() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(connect_timeout())
+            .read_timeout(env_secs("GEMINI_READ_TIMEOUT_SECS", 120))
+            .build()
+            .expect("failed to build HTTP client")
+    })
+}
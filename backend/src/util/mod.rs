@@ -0,0 +1,4 @@
+//! Small helpers shared across routes and integrations.
+
+pub mod hmac;
+pub mod http;
@@ -268,6 +268,52 @@ echo "── Export ──"
 
 check "GET /projects/{id}/export"          200 GET "/projects/$PROJECT_ID/export"
 
+# ─── Concurrent creates (display_id allocation) ───────────────
+
+echo "── Concurrent Creates ──"
+
+CONCURRENT=${SMOKE_CONCURRENT:-10}
+rm -f /tmp/smoke_concurrent_*
+for n in $(seq 1 "$CONCURRENT"); do
+  curl -s -o "/tmp/smoke_concurrent_$n" -X POST "${BASE}/issues" -H "$AUTH" -H "$CT" \
+    -d "{\"project_id\":\"$PROJECT_ID\",\"title\":\"[SMOKE TEST] Concurrent $n\",\"priority\":\"low\",\"status\":\"backlog\"}" &
+done
+wait
+
+CONCURRENT_RESULT=$(python3 - "$CONCURRENT" <<'PY'
+import json, sys
+n = int(sys.argv[1])
+ids, numbers = [], []
+for i in range(1, n + 1):
+    try:
+        data = json.load(open(f"/tmp/smoke_concurrent_{i}"))["data"]
+    except Exception:
+        continue
+    ids.append(data["id"])
+    numbers.append(int(data["display_id"].rsplit("-", 1)[1]))
+numbers.sort()
+unique = len(set(numbers)) == len(numbers)
+gapless = bool(numbers) and numbers == list(range(numbers[0], numbers[0] + len(numbers)))
+ok = len(ids) == n and unique and gapless
+print("ok" if ok else f"created={len(ids)}/{n} numbers={numbers}")
+for issue_id in ids:
+    print(issue_id)
+PY
+)
+
+CONCURRENT_STATUS=$(echo "$CONCURRENT_RESULT" | head -n 1)
+while read -r id; do
+  [[ -n "$id" ]] && CLEANUP_IDS+=("$id")
+done < <(echo "$CONCURRENT_RESULT" | tail -n +2)
+
+if [[ "$CONCURRENT_STATUS" == "ok" ]]; then
+  printf "  ✅ %-45s %s\n" "POST /issues x$CONCURRENT (unique, gapless ids)" "ok"
+  PASS=$((PASS + 1))
+else
+  printf "  ❌ %-45s %s\n" "POST /issues x$CONCURRENT (unique, gapless ids)" "$CONCURRENT_STATUS"
+  FAIL=$((FAIL + 1))
+fi
+
 # ─── Validation (expect errors) ───────────────────────────────
 
 echo "── Validation ──"
@@ -285,7 +331,7 @@ check "GET /issues/00000000-0000-0000-0000-000000000000" 404 GET "/issues/000000
 echo "── Cleanup ──"
 
 for id in "${CLEANUP_IDS[@]}"; do
-  check "DELETE test issue $DISPLAY_ID"    200 DELETE "/issues/$id"
+  check "DELETE test issue $id"           200 DELETE "/issues/$id"
 done
 
 # ─── Summary ──────────────────────────────────────────────────
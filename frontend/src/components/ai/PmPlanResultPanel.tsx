@@ -18,8 +18,8 @@ export interface PmReviewBucket {
 export interface PmReviewSprint {
   key: string;
   name: string;
-  start_date: string;
-  end_date: string;
+  start_date: string | null;
+  end_date: string | null;
   issue_ids: string[];
   issues: PmReviewIssue[];
 }
@@ -196,7 +196,7 @@ export function PmPlanResultPanel({
                             </span>
                           </div>
                           <p className="mt-0.5 text-[9px] text-muted">
-                            {sprint.start_date} → {sprint.end_date}
+                            {sprint.start_date ?? '?'} → {sprint.end_date ?? '?'}
                           </p>
                         </motion.div>
                       ))}
@@ -3,7 +3,7 @@ import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { useApi } from '@/hooks/useApi';
 import { useTranslation } from '@/hooks/useTranslation';
 import { Shield, Plus, Trash2, Loader2, BarChart2 } from 'lucide-react';
-import type { SlaRule, SlaStats } from '@/lib/types';
+import type { SlaPolicy, SlaStats } from '@/lib/types';
 
 const PRIORITY_OPTIONS = ['urgent', 'high', 'medium', 'low'];
 
@@ -14,18 +14,25 @@ const PRIORITY_COLORS: Record<string, string> = {
   low: 'text-muted',
 };
 
+function formatHours(minutes: number | null): string {
+  if (minutes === null) return '—';
+  const hours = minutes / 60;
+  return `${Number.isInteger(hours) ? hours : hours.toFixed(1)}h`;
+}
+
 export function SlaSection({ projectId }: { projectId: string }) {
   const { t } = useTranslation();
   const apiClient = useApi();
   const queryClient = useQueryClient();
   const [newPriority, setNewPriority] = useState('urgent');
   const [newHours, setNewHours] = useState('24');
+  const [newResponseHours, setNewResponseHours] = useState('');
   const [saving, setSaving] = useState(false);
   const [savedId, setSavedId] = useState<string | null>(null);
 
   const { data: rules = [], isLoading: rulesLoading } = useQuery({
-    queryKey: ['sla-rules', projectId],
-    queryFn: () => apiClient.get<SlaRule[]>(`/projects/${projectId}/sla-rules`),
+    queryKey: ['sla-policies', projectId],
+    queryFn: () => apiClient.get<SlaPolicy[]>(`/projects/${projectId}/sla-policies`),
     staleTime: 60_000,
     enabled: !!projectId,
   });
@@ -38,20 +45,23 @@ export function SlaSection({ projectId }: { projectId: string }) {
   });
 
   const deleteMutation = useMutation({
-    mutationFn: (id: string) => apiClient.del(`/sla-rules/${id}`),
-    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['sla-rules', projectId] }),
+    mutationFn: (id: string) => apiClient.del(`/sla-policies/${id}`),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['sla-policies', projectId] }),
   });
 
   const handleAdd = async () => {
     const hours = parseInt(newHours, 10);
+    const responseHours = newResponseHours ? parseInt(newResponseHours, 10) : null;
     if (!newPriority || isNaN(hours) || hours <= 0) return;
+    if (responseHours !== null && (isNaN(responseHours) || responseHours <= 0)) return;
     setSaving(true);
     try {
-      const result = await apiClient.post<SlaRule>(`/projects/${projectId}/sla-rules`, {
+      const result = await apiClient.post<SlaPolicy>(`/projects/${projectId}/sla-policies`, {
         priority: newPriority,
-        deadline_hours: hours,
+        response_minutes: responseHours !== null ? responseHours * 60 : null,
+        resolution_minutes: hours * 60,
       });
-      queryClient.invalidateQueries({ queryKey: ['sla-rules', projectId] });
+      queryClient.invalidateQueries({ queryKey: ['sla-policies', projectId] });
       setSavedId(result.id);
       setTimeout(() => setSavedId(null), 2000);
       // Reset to next unused priority
@@ -59,6 +69,7 @@ export function SlaSection({ projectId }: { projectId: string }) {
       const next = PRIORITY_OPTIONS.find((p) => !usedPriorities.has(p));
       if (next) setNewPriority(next);
       setNewHours('24');
+      setNewResponseHours('');
     } finally {
       setSaving(false);
     }
@@ -123,6 +134,9 @@ export function SlaSection({ projectId }: { projectId: string }) {
                     <th className="px-4 py-2 text-left text-xs font-semibold text-secondary uppercase tracking-wider">
                       {t('slaRules.priority')}
                     </th>
+                    <th className="px-4 py-2 text-left text-xs font-semibold text-secondary uppercase tracking-wider">
+                      {t('slaRules.responseHours')}
+                    </th>
                     <th className="px-4 py-2 text-left text-xs font-semibold text-secondary uppercase tracking-wider">
                       {t('slaRules.deadlineHours')}
                     </th>
@@ -144,7 +158,10 @@ export function SlaSection({ projectId }: { projectId: string }) {
                         )}
                       </td>
                       <td className="px-4 py-3">
-                        <span className="text-sm text-primary tabular-nums">{rule.deadline_hours}h</span>
+                        <span className="text-sm text-primary tabular-nums">{formatHours(rule.response_minutes)}</span>
+                      </td>
+                      <td className="px-4 py-3">
+                        <span className="text-sm text-primary tabular-nums">{formatHours(rule.resolution_minutes)}</span>
                       </td>
                       <td className="px-4 py-3">
                         <button
@@ -178,6 +195,17 @@ export function SlaSection({ projectId }: { projectId: string }) {
                   ))}
                 </select>
               </div>
+              <div>
+                <label className="block text-xs font-medium text-secondary mb-1">{t('slaRules.responseHours')}</label>
+                <input
+                  type="number"
+                  min="1"
+                  value={newResponseHours}
+                  onChange={(e) => setNewResponseHours(e.target.value)}
+                  placeholder="—"
+                  className="w-24 rounded-lg border border-border bg-bg px-3 py-2 text-sm text-primary focus:outline-none focus:ring-1 focus:ring-accent"
+                />
+              </div>
               <div>
                 <label className="block text-xs font-medium text-secondary mb-1">{t('slaRules.deadlineHours')}</label>
                 <input
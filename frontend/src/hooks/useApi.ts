@@ -4,9 +4,11 @@ import { api, ApiError } from '@/lib/api';
 import type {
   Project,
   ProjectAutoAssignSettings,
+  ProjectMember,
   PublicSubmitSettings,
   Issue,
   IssueDetail,
+  IssuePosition,
   ApiKey,
   CreateIssueRequest,
   UpdateIssueRequest,
@@ -14,6 +16,8 @@ import type {
   CreateCommentRequest,
   Comment,
   ProjectTag,
+  ProjectCategory,
+  CategoryUsage,
   GitHubInstallation,
   GitHubRepository,
   GitHubRepoMapping,
@@ -28,6 +32,11 @@ import type {
   SavedView,
   Automation,
   ProjectGamificationStats,
+  CurrentOrg,
+  RecentIssue,
+  FavoriteEntityType,
+  Favorites,
+  TimeEntry,
 } from '@/lib/types';
 
 /**
@@ -193,6 +202,24 @@ export function useApi() {
           return api.delete(`/projects/${id}`, token);
         }),
 
+      listMembers: async (id: string): Promise<ProjectMember[]> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get<ProjectMember[]>(`/projects/${id}/members`, token);
+        }),
+
+      addMember: async (id: string, body: { user_id: string; role?: ProjectMember['role'] }): Promise<ProjectMember> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post<ProjectMember>(`/projects/${id}/members`, body, token);
+        }),
+
+      removeMember: async (id: string, userId: string): Promise<void> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.delete(`/projects/${id}/members/${encodeURIComponent(userId)}`, token);
+        }),
+
       getGamification: async (id: string): Promise<ProjectGamificationStats> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
@@ -223,7 +250,7 @@ export function useApi() {
 
       updatePublicSubmit: async (
         id: string,
-        body: { enabled?: boolean; rotate_token?: boolean },
+        body: { enabled?: boolean; rotate_token?: boolean; spam_filter?: boolean; notify_reporters?: boolean },
       ): Promise<PublicSubmitSettings> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
@@ -233,11 +260,14 @@ export function useApi() {
 
     // ─── Issues ────────────────────────────────
     issues: {
-      listAll: async (params?: { limit?: number }): Promise<Issue[]> =>
+      listAll: async (params?: { limit?: number; projectIds?: string[] }): Promise<Issue[]> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
-          const qs = params?.limit ? `?limit=${params.limit}` : '';
-          return api.get<Issue[]>(`/issues${qs}`, token);
+          const query = new URLSearchParams();
+          if (params?.limit) query.set('limit', String(params.limit));
+          if (params?.projectIds?.length) query.set('project_ids', params.projectIds.join(','));
+          const qs = query.toString();
+          return api.get<Issue[]>(`/issues${qs ? `?${qs}` : ''}`, token);
         }),
 
       listByProject: async (
@@ -273,6 +303,13 @@ export function useApi() {
           return api.get<IssueDetail>(`/issues/${id}`, token);
         }),
 
+      listRecent: async (limit?: number): Promise<RecentIssue[]> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          const qs = limit ? `?limit=${limit}` : '';
+          return api.get<RecentIssue[]>(`/me/recent-issues${qs}`, token);
+        }),
+
       listMine: async (assigneeId: string): Promise<Issue[]> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
@@ -304,6 +341,38 @@ export function useApi() {
           }, token);
         }),
 
+      moveRelative: async (
+        id: string,
+        anchor: { before_id: string } | { after_id: string },
+        status?: string,
+      ): Promise<Issue> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.patch<Issue>(`/issues/${id}/position`, { ...anchor, status }, token);
+        }),
+
+      reindex: async (
+        projectId: string,
+        status?: string,
+      ): Promise<IssuePosition[]> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          const query = status ? `?status=${encodeURIComponent(status)}` : '';
+          return api.post<IssuePosition[]>(`/projects/${projectId}/issues/reindex${query}`, {}, token);
+        }),
+
+      snooze: async (id: string, until: string): Promise<Issue> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post<Issue>(`/issues/${id}/snooze`, { until }, token);
+        }),
+
+      unsnooze: async (id: string): Promise<Issue> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.delete<Issue>(`/issues/${id}/snooze`, token);
+        }),
+
       delete: async (id: string): Promise<void> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
@@ -320,6 +389,24 @@ export function useApi() {
         }),
     },
 
+    // ─── Time Tracking ─────────────────────────
+    time: {
+      list: async (issueId: string): Promise<{ total_minutes: number; entries: TimeEntry[] }> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get(`/issues/${issueId}/time`, token);
+        }),
+
+      log: async (
+        issueId: string,
+        body: { minutes: number; note?: string; logged_at?: string },
+      ): Promise<TimeEntry> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post<TimeEntry>(`/issues/${issueId}/time`, body, token);
+        }),
+    },
+
     // ─── API Keys ──────────────────────────────
     apiKeys: {
       list: async (): Promise<ApiKey[]> =>
@@ -403,6 +490,64 @@ export function useApi() {
         }),
     },
 
+    // ─── Categories ────────────────────────────
+    categories: {
+      listByProject: async (projectId: string): Promise<ProjectCategory[]> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get<ProjectCategory[]>(`/projects/${projectId}/categories`, token);
+        }),
+
+      usage: async (projectId: string): Promise<CategoryUsage[]> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get<CategoryUsage[]>(`/projects/${projectId}/categories/usage`, token);
+        }),
+
+      create: async (projectId: string, body: { name: string; color?: string; description?: string }): Promise<ProjectCategory> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post<ProjectCategory>(`/projects/${projectId}/categories`, body, token);
+        }),
+
+      delete: async (categoryId: string): Promise<void> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.delete(`/categories/${categoryId}`, token);
+        }),
+    },
+
+    // ─── Favorites ─────────────────────────────
+    favorites: {
+      list: async (): Promise<Favorites> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get<Favorites>('/me/favorites', token);
+        }),
+
+      add: async (entityType: FavoriteEntityType, entityId: string): Promise<void> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post('/favorites', { entity_type: entityType, entity_id: entityId }, token);
+        }),
+
+      remove: async (entityType: FavoriteEntityType, entityId: string): Promise<void> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          const query = new URLSearchParams({ entity_type: entityType, entity_id: entityId });
+          return api.delete(`/favorites?${query}`, token);
+        }),
+    },
+
+    // ─── Org ───────────────────────────────────
+    org: {
+      current: async (): Promise<CurrentOrg> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.get<CurrentOrg>('/org', token);
+        }),
+    },
+
     // ─── Invites ───────────────────────────────
     invites: {
       list: async (): Promise<Array<{
@@ -430,6 +575,28 @@ export function useApi() {
           const token = await getAuthToken();
           return api.post('/invites', body, token);
         }),
+
+      bulk: async (invites: Array<{ email: string; role?: string }>): Promise<{
+        succeeded: number;
+        failed: number;
+        results: Array<{
+          email: string;
+          success: boolean;
+          invite: {
+            id: string;
+            email_address: string;
+            status: string;
+            role: string | null;
+            url: string | null;
+            short_url: string | null;
+          } | null;
+          error: string | null;
+        }>;
+      }> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post('/invites/bulk', { invites }, token);
+        }),
     },
 
     // ─── Webhooks ──────────────────────────────
@@ -533,10 +700,17 @@ export function useApi() {
           return api.get<ActivityEntry[]>(`/issues/${issueId}/activity`, token);
         }),
 
-      listRecent: async (): Promise<ActivityEntry[]> =>
+      listRecent: async (
+        filters: { action?: string; user_id?: string; project_id?: string } = {},
+      ): Promise<ActivityEntry[]> =>
         withErrorHandling(async () => {
           const token = await getAuthToken();
-          return api.get<ActivityEntry[]>('/activity', token);
+          const params = new URLSearchParams();
+          Object.entries(filters).forEach(([k, v]) => {
+            if (v) params.set(k, v);
+          });
+          const qs = params.toString();
+          return api.get<ActivityEntry[]>(qs ? `/activity?${qs}` : '/activity', token);
         }),
     },
 
@@ -678,6 +852,12 @@ export function useApi() {
           const token = await getAuthToken();
           return api.delete(`/views/${id}`, token);
         }),
+
+      makeDefault: async (id: string): Promise<SavedView> =>
+        withErrorHandling(async () => {
+          const token = await getAuthToken();
+          return api.post<SavedView>(`/views/${id}/make-default`, {}, token);
+        }),
     },
 
     // ─── Templates ─────────────────────────────
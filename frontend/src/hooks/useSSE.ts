@@ -2,11 +2,25 @@ import { useEffect, useRef } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
 import { useAuth, useUser } from '@clerk/clerk-react';
 import { useNotificationStore } from '@/stores/notifications';
-import type { SSEEvent } from '@/lib/types';
+import type { SSEEvent, SSEEventType } from '@/lib/types';
 
 import { resolveApiOrigin } from '@/lib/api-origin';
 const API_URL = resolveApiOrigin();
 
+// The backend emits named SSE events (`event: issue.created`), which
+// `onmessage` never sees — each name needs its own listener.
+const SSE_EVENT_TYPES: SSEEventType[] = [
+  'issue.created',
+  'issue.updated',
+  'issue.status_changed',
+  'issue.deleted',
+  'issue.archived',
+  'issue.unarchived',
+  'issue.position_changed',
+  'comment.created',
+  'comment.deleted',
+];
+
 /**
  * Global SSE hook — connects to the backend event stream and:
  * 1. Invalidates relevant TanStack Query caches on changes
@@ -37,18 +51,18 @@ export function useSSE() {
 
         // EventSource doesn't support Authorization headers,
         // so we pass the token as a query param
-        const url = `${API_URL}/api/v1/events?token=${token}`;
+        const url = `${API_URL}/api/v1/events?access_token=${encodeURIComponent(token)}`;
         const es = new EventSource(url);
         eventSourceRef.current = es;
 
-        es.onmessage = (event) => {
+        const handleEvent = (event: MessageEvent) => {
           if (cancelled) return;
 
           try {
             const data: SSEEvent = JSON.parse(event.data);
 
             // ── Invalidate queries ──
-            if (data.type === 'issue_created' || data.type === 'issue_updated') {
+            if (data.type.startsWith('issue.')) {
               queryClient.invalidateQueries({ queryKey: ['issues'] });
               queryClient.invalidateQueries({ queryKey: ['all-issues'] });
               if (data.issue_id) {
@@ -67,7 +81,7 @@ export function useSSE() {
               }
             }
 
-            if (data.type === 'comment_created') {
+            if (data.type === 'comment.created' || data.type === 'comment.deleted') {
               if (data.issue_id) {
                 queryClient.invalidateQueries({ queryKey: ['issue', data.issue_id] });
                 queryClient.invalidateQueries({ queryKey: ['activity', data.issue_id] });
@@ -76,7 +90,7 @@ export function useSSE() {
             }
 
             // ── Notifications (only for events caused by others) ──
-            if (data.type === 'issue_created' && data.title) {
+            if (data.type === 'issue.created' && data.title) {
               addNotification({
                 type: 'info',
                 title: 'New issue created',
@@ -84,7 +98,7 @@ export function useSSE() {
               });
             }
 
-            if (data.type === 'comment_created' && data.author_name) {
+            if (data.type === 'comment.created' && data.author_name) {
               // Don't notify for own comments
               const isOwnComment = user?.fullName === data.author_name
                 || user?.firstName === data.author_name;
@@ -101,6 +115,10 @@ export function useSSE() {
           }
         };
 
+        for (const type of SSE_EVENT_TYPES) {
+          es.addEventListener(type, handleEvent);
+        }
+
         es.onerror = () => {
           if (cancelled) return;
           es.close();
@@ -1,27 +1,17 @@
 // @deprecated — AI processing moved to backend (POST /api/v1/ai/agent). This file is kept for backward compat.
 /**
- * Baaton AI Engine v2 — Vercel AI SDK (@ai-sdk/google)
+ * Baaton AI Engine v2 — client-side context and prompt building; generation
+ * goes through the backend Gemini proxy so the API key never reaches the
+ * browser.
  *
- * Migrated from @google/generative-ai to Vercel AI SDK for:
- * - Built-in agentic loop (maxSteps)
- * - Typed tool results
- * - Unified provider interface (swap Gemini/Claude/GPT)
- * - Proper token usage from response metadata
- * - Automatic retry handling
- *
- * Keeps all existing features:
+ * Keeps:
  * - State machine integration (ai-state.ts)
- * - Tool masking (5 contexts via ai-skills.ts)
  * - Conversation summarization
  * - Rate limiting + token budget
  * - 5-block Manus system prompt
  */
 
-import { generateText, tool as aiTool, stepCountIs, type ModelMessage, type ToolSet } from 'ai';
-import { createGoogleGenerativeAI } from '@ai-sdk/google';
 import type { Issue, Project, Milestone } from './types';
-import { getZodToolsForContext, type SkillContext } from './ai-skills';
-import { executeSkill } from './ai-executor';
 import type { SkillResult } from './ai-skills';
 import {
   type AIStateContext,
@@ -34,31 +24,6 @@ import {
   summarizeHistory,
 } from './ai-state';
 
-// ─── API Key (fetched from backend) ───────────
-import { resolveApiOrigin } from './api-origin';
-let _cachedApiKey: string | null = null;
-const API_URL = resolveApiOrigin();
-
-async function getGeminiApiKey(authToken?: string): Promise<string> {
-  if (_cachedApiKey) return _cachedApiKey;
-  if (API_URL && authToken) {
-    try {
-      const res = await fetch(`${API_URL}/api/v1/ai/key`, {
-        headers: { Authorization: `Bearer ${authToken}` },
-        signal: AbortSignal.timeout(3000),
-      });
-      if (res.ok) {
-        const data = await res.json();
-        if (data.key) {
-          _cachedApiKey = data.key;
-          return _cachedApiKey as string;
-        }
-      }
-    } catch { /* backend unreachable */ }
-  }
-  throw new Error('AI non configuré. Clé API manquante.');
-}
-
 // ─── Errors ───────────────────────────────────
 export class RateLimitError extends Error {
   constructor() {
@@ -86,38 +51,6 @@ function mapProviderErrorToUserMessage(errorLike: unknown): string {
   return 'AI request failed unexpectedly. Please try again.';
 }
 
-// ─── Tool Builder (Zod schemas → AI SDK ToolSet) ─────────
-
-function buildTools(
-  skillContext: SkillContext,
-  executor: (name: string, args: Record<string, unknown>) => Promise<any>,
-): ToolSet {
-  const definitions = getZodToolsForContext(skillContext);
-  const tools: Record<string, any> = {};
-
-  for (const [name, def] of Object.entries(definitions)) {
-    tools[name] = aiTool({
-      description: def.description,
-      inputSchema: def.inputSchema,
-      execute: async (args: any) => {
-        try {
-          const result = await executor(name, args);
-          // Return formattedForModel if available — this is what the model sees
-          if (result?.formattedForModel) {
-            return result.formattedForModel;
-          }
-          return result;
-        } catch (error) {
-          console.error('[AI][ToolInvocationFailed]', { tool: name, error });
-          return `❌ ${name} failed: ${mapProviderErrorToUserMessage(error)}`;
-        }
-      },
-    });
-  }
-
-  return tools as ToolSet;
-}
-
 // ─── Context Builder ──────────────────────────
 
 export function buildProjectContext(projects: Project[], allIssues: Record<string, Issue[]>): string {
@@ -321,13 +254,11 @@ export interface AIResponse {
   stateContext: AIStateContext;
 }
 
-const APPROVAL_REQUIRED_SKILLS = new Set([
-  'create_issue',
-  'update_issue',
-  'bulk_update_issues',
-  'add_comment',
-  'create_milestones_batch',
-]);
+/** Non-streaming response of the backend Gemini proxy (POST /ai/chat-legacy). */
+interface GeminiProxyResponse {
+  candidates?: { content?: { parts?: { text?: string }[] } }[];
+  usageMetadata?: { promptTokenCount?: number; candidatesTokenCount?: number };
+}
 
 interface PmFullReviewIssue {
   id: string;
@@ -348,6 +279,8 @@ interface PmFullReviewIssue {
 interface PmFullReviewBucket {
   key: string;
   name: string;
+  milestone_id?: string;
+  target_date?: string | null;
   issue_ids: string[];
   issues: PmFullReviewIssue[];
 }
@@ -355,8 +288,9 @@ interface PmFullReviewBucket {
 interface PmFullReviewSprint {
   key: string;
   name: string;
-  start_date: string;
-  end_date: string;
+  sprint_id?: string;
+  start_date: string | null;
+  end_date: string | null;
   issue_ids: string[];
   issues: PmFullReviewIssue[];
 }
@@ -377,7 +311,9 @@ interface PmFullReviewSuggestion {
 }
 
 interface PmFullReviewData {
+  id: string;
   generated_at: string;
+  mode: 'heuristic' | 'existing';
   horizon_days: number;
   sprint_length_days: number;
   period: {
@@ -403,6 +339,15 @@ interface PmFullReviewData {
   };
   projects: PmFullReviewProject[];
   priority_suggestions: PmFullReviewSuggestion[];
+  workload: {
+    overload_threshold: number;
+    assignees: Array<{
+      assignee_id: string;
+      open_issue_count: number;
+      estimate_total: number;
+      overloaded: boolean;
+    }>;
+  };
 }
 
 type ApiClientType = {
@@ -476,7 +421,7 @@ function renderPmFullReviewMarkdown(plan: PmFullReviewData): string {
     lines.push('');
     lines.push('#### Sprints');
     for (const sprint of project.sprints) {
-      lines.push(`- **${sprint.name}** (${sprint.start_date} → ${sprint.end_date}) — ${sprint.issue_ids.length} issues`);
+      lines.push(`- **${sprint.name}** (${sprint.start_date ?? '?'} → ${sprint.end_date ?? '?'}) — ${sprint.issue_ids.length} issues`);
       if (sprint.issue_ids.length > 0) {
         lines.push(`  - IDs: ${sprint.issue_ids.map((id) => `\`${id}\``).join(', ')}`);
         sprint.issues.forEach((issue) => lines.push(`  ${renderIssueLine(issue)}`));
@@ -544,7 +489,10 @@ function buildLegacyPmFallback(projects: Project[], allIssuesByProject: Record<s
   ].join('\n');
 }
 
-// ─── Main Generate Function (Vercel AI SDK) ───
+// ─── Main Generate Function (backend proxy) ───
+// The Gemini key stays server-side: generation goes through POST
+// /ai/chat-legacy, which takes plain-text turns only, so no skills are offered
+// here. Tool-driven chat lives in POST /ai/agent.
 
 export async function generateAIResponse(
   userMessage: string,
@@ -553,8 +501,8 @@ export async function generateAIResponse(
   conversationHistory: { role: string; content: string }[],
   apiClient: ApiClientType,
   stateContext?: AIStateContext,
-  authToken?: string,
-  options?: { requireApproval?: boolean },
+  _authToken?: string,
+  _options?: { requireApproval?: boolean },
 ): Promise<AIResponse> {
   // ── Rate Limiting ──
   const rateCheck = checkRateLimit();
@@ -562,8 +510,6 @@ export async function generateAIResponse(
     throw new RateLimitError();
   }
 
-  const requireApproval = options?.requireApproval ?? false;
-
   // ── State Machine: init or use provided ──
   let state = stateContext ? { ...stateContext } : createInitialState();
   state = transition(state, { type: 'USER_MESSAGE', tokens: estimateTokens(userMessage) });
@@ -587,13 +533,13 @@ export async function generateAIResponse(
   const optimizedHistory = summarizeHistory(conversationHistory);
 
   // ── Build messages ──
-  const messages: ModelMessage[] = optimizedHistory.slice(-8).map((m) => ({
+  const messages = optimizedHistory.slice(-8).map((m) => ({
     role: (m.role === 'user' ? 'user' : 'assistant') as 'user' | 'assistant',
     content: m.content,
   }));
   messages.push({ role: 'user', content: userMessage });
 
-  // ── Tool masking from state ──
+  // ── Skill context from state (logged on failure) ──
   const skillContext = stateToSkillContext(state, userMessage);
   const skillsExecuted: SkillResult[] = [];
 
@@ -647,120 +593,23 @@ export async function generateAIResponse(
     };
   }
 
-  // ── Get API key & create provider ──
-  const apiKey = await getGeminiApiKey(authToken);
-  const google = createGoogleGenerativeAI({ apiKey });
-
-  // ── Build tools with executor bridge ──
-  const executor = async (name: string, args: Record<string, unknown>) => {
-    state = transition(state, { type: 'SKILL_STARTED', name });
-    const startTime = performance.now();
-
-    if (requireApproval && APPROVAL_REQUIRED_SKILLS.has(name)) {
-      const pendingData = { pending: true, args } as Record<string, unknown>;
-      const executionTime = Math.round(performance.now() - startTime);
-      const pendingResult: SkillResult = {
-        skill: name,
-        success: true,
-        summary: 'Pending approval',
-        data: pendingData,
-      };
-      skillsExecuted.push({ ...pendingResult, executionTimeMs: executionTime } as any);
-      state = transition(state, { type: 'SKILL_COMPLETED', name, data: pendingData });
-      return pendingData;
-    }
-
-    try {
-      const result = await executeSkill(name, args, apiClient, allIssuesByProject, projects);
-      const executionTime = Math.round(performance.now() - startTime);
-      const enriched = { ...result, executionTimeMs: executionTime };
-      skillsExecuted.push(enriched);
-
-      if (result.success) {
-        state = transition(state, { type: 'SKILL_COMPLETED', name, data: result.data });
-      } else {
-        console.warn('[AI][SkillFailed]', { name, args, error: result.error, summary: result.summary });
-        state = transition(state, { type: 'SKILL_FAILED', name, error: result.error || 'Unknown error' });
-      }
-
-      return result; // Return full SkillResult — buildTools will extract formattedForModel
-    } catch (error) {
-      const executionTime = Math.round(performance.now() - startTime);
-      console.error('[AI][SkillCrash]', {
-        name,
-        args,
-        executionTimeMs: executionTime,
-        error: error instanceof Error ? error.message : String(error),
-      });
-
-      const friendly = mapProviderErrorToUserMessage(error);
-      const fallbackResult: SkillResult = {
-        skill: name,
-        success: false,
-        error: friendly,
-        summary: `${name} failed safely`,
-      };
-      skillsExecuted.push({ ...fallbackResult, executionTimeMs: executionTime } as any);
-      state = transition(state, { type: 'SKILL_FAILED', name, error: friendly });
-      return { success: false, error: friendly };
-    }
-  };
-
-  const tools = buildTools(skillContext, executor);
-
   try {
-    // ── Vercel AI SDK: generateText with tools + maxSteps ──
-    // maxSteps = 5 → agentic loop (same as our old 5-round loop)
-    // The SDK handles: tool call → execute → feed result → get next response
-    const result = await generateText({
-      model: google('gemini-3-flash-preview'),
-      system: systemPrompt,
+    const result = await apiClient.post<GeminiProxyResponse>('/ai/chat-legacy', {
       messages,
-      tools,
-      stopWhen: stepCountIs(5),
-      temperature: 0.4,
-      maxOutputTokens: 8000,
-      onStepFinish({ stepNumber, finishReason, usage: stepUsage, toolCalls }) {
-        console.log(`[AI][Step ${stepNumber}]`, {
-          finishReason,
-          toolCalls: toolCalls?.map((tc: any) => tc.toolName),
-          inputTokens: stepUsage?.inputTokens,
-          outputTokens: stepUsage?.outputTokens,
-        });
-      },
+      systemInstruction: systemPrompt,
+      stream: false,
     });
+    const text = (result.candidates?.[0]?.content?.parts ?? [])
+      .map((part) => part.text ?? '')
+      .join('')
+      .trim();
 
-    // ── Track usage from response metadata ──
-    const usage = result.usage;
-    const inputTokens = usage?.inputTokens ?? estimateTokens(systemPrompt + userMessage);
-    const outputTokens = usage?.outputTokens ?? estimateTokens(result.text || '');
-
+    const inputTokens = result.usageMetadata?.promptTokenCount ?? estimateTokens(systemPrompt + userMessage);
+    const outputTokens = result.usageMetadata?.candidatesTokenCount ?? estimateTokens(text);
     state = transition(state, { type: 'AI_RESPONSE', tokens: outputTokens });
 
-    const pendingSkills = skillsExecuted.filter((s) => (s.data as any)?.pending);
-    if (pendingSkills.length > 0) {
-      const pendingList = pendingSkills.map((s) => s.skill).join(', ');
-      return {
-        text: `⚠️ Validation requise avant d'exécuter: ${pendingList}.`,
-        skillsExecuted,
-        usage: {
-          inputTokens,
-          outputTokens,
-          totalTokens: inputTokens + outputTokens,
-          turnCount: state.usage.turnCount,
-        },
-        stateContext: state,
-      };
-    }
-
-    const fallbackSkillText = skillsExecuted.length > 0
-      ? skillsExecuted
-          .map((s) => `• ${s.summary}${s.success ? '' : s.error ? ` (${s.error})` : ''}`)
-          .join('\n')
-      : "Je n'ai pas pu générer de réponse. Réessaie avec plus de détails.";
-
     return {
-      text: result.text?.trim() ? result.text : fallbackSkillText,
+      text: text || "Je n'ai pas pu générer de réponse. Réessaie avec plus de détails.",
       skillsExecuted,
       usage: {
         inputTokens,
@@ -778,94 +627,8 @@ export async function generateAIResponse(
       throw new RateLimitError();
     }
 
-    // Gemini function-calling can fail on schema validation in some deployments.
-    // Fail soft with a NO-TOOLS fallback that still produces actionable PM output.
-    const lowerMsg = msg.toLowerCase();
-    const isToolSchemaError =
-      (lowerMsg.includes('schema') && lowerMsg.includes('function')) ||
-      lowerMsg.includes('functiondeclaration') ||
-      lowerMsg.includes('parameters schema should be of type object') ||
-      lowerMsg.includes('tool schema mismatch');
-
-    if (isToolSchemaError) {
-      console.warn('[AI][GenerateTextFallbackNoTools]', {
-        reason: 'tool schema mismatch',
-        skillContext,
-        message: msg,
-      });
-
-      try {
-        const [metricsResult, prioritiesResult] = await Promise.all([
-          executeSkill('get_project_metrics', {}, apiClient, allIssuesByProject, projects),
-          executeSkill('suggest_priorities', {}, apiClient, allIssuesByProject, projects),
-        ]);
-
-        // Hard fallback: DO NOT call Gemini again.
-        // Build a deterministic PM review from live metrics so user always gets output.
-        const m: any = metricsResult?.data || {};
-        const p: any = prioritiesResult?.data || {};
-        const projectCount = Array.isArray(m.projects) ? m.projects.length : (m.project_count ?? 0);
-        const openCount = m.open_issues ?? m.openCount ?? 0;
-        const doneCount = m.done_issues ?? m.doneCount ?? 0;
-        const completion = m.completion_rate ?? m.completionRate ?? null;
-        const suggestions = Array.isArray(p.suggestions) ? p.suggestions : [];
-
-        const lines: string[] = [
-          '⚠️ AI function-calling indisponible temporairement — mode fallback activé.',
-          '',
-          '## Revue PM (fallback live data)',
-          `- Projets analysés: ${projectCount}`,
-          `- Tickets ouverts: ${openCount}`,
-          `- Tickets terminés: ${doneCount}`,
-          completion != null ? `- Taux de completion: ${completion}%` : '- Taux de completion: n/a',
-          '',
-          '## Priorités suggérées',
-        ];
-
-        if (suggestions.length === 0) {
-          lines.push('- Aucune suggestion automatique disponible.');
-        } else {
-          for (const s of suggestions.slice(0, 10)) {
-            if (typeof s === 'string') lines.push(`- ${s}`);
-            else lines.push(`- ${s.title || s.issue || 'Issue'} → ${s.priority || 'review'}`);
-          }
-        }
-
-        lines.push('', '## Next', '- Rafraîchir la session puis relancer pour plan milestones/sprints détaillé.');
-
-        const text = lines.join('\n');
-        const outputTokens = estimateTokens(text);
-        const inputTokens = estimateTokens(userMessage);
-
-        state = transition(state, { type: 'AI_RESPONSE', tokens: outputTokens });
-
-        return {
-          text,
-          skillsExecuted,
-          usage: {
-            inputTokens,
-            outputTokens,
-            totalTokens: inputTokens + outputTokens,
-            turnCount: state.usage.turnCount,
-          },
-          stateContext: state,
-        };
-      } catch (retryErr: any) {
-        console.error('[AI][GenerateTextRetryFailed]', {
-          message: retryErr?.message || String(retryErr),
-          skillContext,
-        });
-      }
-    }
-
     const friendly = mapProviderErrorToUserMessage(err);
-    console.error('[AI][GenerateTextFailed]', {
-      message: msg,
-      friendly,
-      skillContext,
-      skillCount: Object.keys(tools).length,
-    });
-
+    console.error('[AI][GenerateTextFailed]', { message: msg, friendly, skillContext });
     throw new Error(friendly);
   }
 }
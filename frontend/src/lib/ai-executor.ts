@@ -1434,19 +1434,25 @@ async function executeManageSla(args: Record<string, unknown>, api: ApiClient):
   if (!projectId) return { skill: 'manage_sla', success: false, error: 'project_id required', summary: 'Missing project_id' };
   switch (action) {
     case 'list_rules': {
-      const data = await api.get(`/projects/${projectId}/sla-rules`);
-      return { skill: 'manage_sla', success: true, data, summary: `Found ${Array.isArray(data) ? data.length : 0} SLA rules`, formattedForModel: Array.isArray(data) && data.length > 0 ? `Found ${data.length} SLA rules:\n${(data as Array<Record<string, unknown>>).map(r => `- ${r.priority}: ${r.deadline_hours}h`).join('\n')}\n\nYou can add, delete, or check SLA stats.` : 'No SLA rules configured. You can create one with manage_sla(action=create_rule).' };
+      const data = await api.get(`/projects/${projectId}/sla-policies`);
+      return { skill: 'manage_sla', success: true, data, summary: `Found ${Array.isArray(data) ? data.length : 0} SLA rules`, formattedForModel: Array.isArray(data) && data.length > 0 ? `Found ${data.length} SLA rules:\n${(data as Array<Record<string, unknown>>).map(r => `- ${r.priority}: resolve within ${r.resolution_minutes != null ? `${Number(r.resolution_minutes) / 60}h` : 'n/a'}${r.response_minutes != null ? `, respond within ${Number(r.response_minutes) / 60}h` : ''}`).join('\n')}\n\nYou can add, delete, or check SLA stats.` : 'No SLA rules configured. You can create one with manage_sla(action=create_rule).' };
     }
     case 'stats': {
       const data = await api.get(`/projects/${projectId}/sla-stats`);
       return { skill: 'manage_sla', success: true, data, summary: `SLA stats retrieved`, formattedForModel: `SLA stats retrieved for project. Check the data for breach rates and compliance details.` };
     }
     case 'create_rule': {
-      const data = await api.post(`/projects/${projectId}/sla-rules`, { priority: args.priority, deadline_hours: args.deadline_hours });
+      const hours = Number(args.deadline_hours);
+      const responseHours = args.response_hours != null ? Number(args.response_hours) : null;
+      const data = await api.post(`/projects/${projectId}/sla-policies`, {
+        priority: args.priority,
+        response_minutes: responseHours !== null ? responseHours * 60 : null,
+        resolution_minutes: hours * 60,
+      });
       return { skill: 'manage_sla', success: true, data, summary: `Created SLA rule: ${args.priority} = ${args.deadline_hours}h`, formattedForModel: `✅ SLA rule created: ${args.priority} issues must be resolved within ${args.deadline_hours}h.` };
     }
     case 'delete_rule': {
-      await api.delete(`/sla-rules/${args.rule_id}`);
+      await api.delete(`/sla-policies/${args.rule_id}`);
       return { skill: 'manage_sla', success: true, data: {}, summary: `Deleted SLA rule`, formattedForModel: `✅ SLA rule deleted successfully.` };
     }
     default:
@@ -238,6 +238,7 @@ export const TOOL_SCHEMAS: Record<string, { description: string; inputSchema: z.
       rule_id: z.string().optional().describe('UUID of the SLA rule to delete. Required for delete_rule action only.'),
       priority: z.enum(['urgent', 'high', 'medium', 'low']).optional().describe('Priority level this rule applies to. Required for create_rule.'),
       deadline_hours: z.number().optional().describe('Maximum allowed resolution time in hours for the specified priority. Required for create_rule. Examples: 4 (urgent), 24 (high), 72 (medium), 168 (low/one week).'),
+      response_hours: z.number().optional().describe('Optional time to first response in hours for create_rule. Example: 1 for urgent.'),
     }),
   },
 
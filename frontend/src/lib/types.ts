@@ -17,10 +17,44 @@ export interface Organization {
   created_at: string;
 }
 
+/** Entry from `GET /me/recent-issues`. */
+export interface RecentIssue {
+  id: string;
+  display_id: string;
+  title: string;
+  status: string;
+  priority: string | null;
+  project_id: string;
+  project_name: string;
+  project_slug: string;
+  project_prefix: string;
+  viewed_at: string;
+}
+
+/** Active org as resolved from Clerk (`GET /org`). */
+export interface CurrentOrg {
+  id: string;
+  name: string;
+  slug: string;
+  image_url: string | null;
+  /** False when Clerk was unreachable and `name` is a local fallback. */
+  resolved: boolean;
+}
+
+export interface ProjectMember {
+  user_id: string;
+  role: 'admin' | 'member';
+  added_by: string | null;
+  created_at: string;
+  display_name: string | null;
+  email: string | null;
+}
+
 export interface ProjectStatus {
   key: string;
   label: string;
   color: string;
+  category?: 'backlog' | 'unstarted' | 'started' | 'completed' | 'cancelled';
   hidden: boolean;
 }
 
@@ -36,6 +70,12 @@ export interface Project {
   statuses: ProjectStatus[];
   auto_assign_mode: AutoAssignMode;
   default_assignee_id: string | null;
+  archived_at?: string | null;
+  validate_categories?: boolean;
+  display_id_padding?: number;
+  /** ISO weekdays (1 = Monday) counted for auto due dates; null = every day */
+  working_days?: number[] | null;
+  holidays?: string[];
   github_repo_url?: string;
   github_metadata?: {
     full_name?: string;
@@ -51,18 +91,23 @@ export interface Project {
     fetched_at?: string;
   };
   created_at: string;
+  /** Whether you starred the project; only on `GET /projects/{id}`. */
+  favorited?: boolean;
 }
 
 export interface ProjectAutoAssignSettings {
   project_id: string;
   auto_assign_mode: AutoAssignMode;
   default_assignee_id: string | null;
+  strict_assignees: boolean;
 }
 
 export interface PublicSubmitSettings {
   enabled: boolean;
   token: string | null;
   slug: string;
+  spam_filter: boolean;
+  notify_reporters: boolean;
 }
 
 export interface OpenClawConnection {
@@ -125,8 +170,19 @@ export interface Issue {
   qualified_at: string | null;
   qualified_by: string | null;
   estimate: number | null;
+  /** Sum of children's estimates; only present on parent issues. */
+  rollup_estimate?: number;
   status_changed_at: string | null;
   closed_at: string | null;
+  /** Hidden from default lists and boards until this time. */
+  snoozed_until?: string | null;
+  snoozed_by?: string | null;
+  /** SLA targets from the project's policy for this priority. */
+  sla_response_due_at?: string | null;
+  sla_resolution_due_at?: string | null;
+  sla_responded_at?: string | null;
+  /** Set once a target is missed. */
+  sla_breached?: boolean | null;
   created_at: string;
   updated_at: string;
   // Relations (optional, loaded on detail)
@@ -140,6 +196,10 @@ export interface Attachment {
   name: string;
   size: number;
   mime_type: string;
+  /** Set on files uploaded through POST /issues/{id}/attachments */
+  id?: string;
+  filename?: string;
+  content_type?: string;
 }
 
 export interface TLDR {
@@ -153,6 +213,8 @@ export interface TLDR {
   decisions_made: string[];
   edge_cases: string[];
   context_updates: string[];
+  github_pr_link_id: string | null;
+  commit_sha: string | null;
   created_at: string;
 }
 
@@ -195,11 +257,36 @@ export interface ApiKey {
   key_prefix: string;
   permissions: string[];
   project_ids: string[];
+  /** Prefixes of the projects in project_ids */
+  project_prefixes: string[];
   last_used_at: string | null;
   expires_at: string | null;
   created_at: string;
 }
 
+export interface ApiKeyUsage {
+  api_key_id: string;
+  since: string;
+  /** Share of successful requests logged; errors are always logged */
+  sample_rate: number;
+  requests: number;
+  errors: number;
+  /** Grouped by method and path, IDs collapsed to {id} */
+  routes: {
+    method: string;
+    path: string;
+    requests: number;
+    errors: number;
+    last_at: string;
+  }[];
+  entries: {
+    method: string;
+    path: string;
+    status: number;
+    created_at: string;
+  }[];
+}
+
 export interface ActivityEntry {
   id: string;
   org_id: string;
@@ -232,15 +319,31 @@ export interface ProjectGamificationStats {
   heatmap_30d: Array<{ date: string; count: number }>;
 }
 
-export type SSEEventType = 'issue_created' | 'issue_updated' | 'comment_created';
+export type SSEEventType =
+  | 'issue.created'
+  | 'issue.updated'
+  | 'issue.status_changed'
+  | 'issue.deleted'
+  | 'issue.archived'
+  | 'issue.unarchived'
+  | 'comment.created'
+  | 'comment.deleted'
+  | 'issue.presence'
+  | 'issue.position_changed';
 
 export interface SSEEvent {
   type: SSEEventType;
   issue_id?: string;
   project_id?: string;
+  display_id?: string;
   title?: string;
+  status?: string;
   comment_id?: string;
-  author_name?: string;
+  author_name?: string | null;
+  user_id?: string;
+  display_name?: string | null;
+  position?: number;
+  reindexed?: boolean;
 }
 
 export interface SavedView {
@@ -256,6 +359,7 @@ export interface SavedView {
   };
   sort?: string;
   is_shared: boolean;
+  is_default?: boolean;
   created_at: string;
 }
 
@@ -387,15 +491,72 @@ export interface ProjectTag {
   created_at: string;
 }
 
+export interface ProjectCategory {
+  id: string;
+  project_id: string;
+  name: string;
+  color: string;
+  description: string | null;
+  created_at: string;
+}
+
+export interface CategoryUsage {
+  name: string;
+  color: string | null;
+  defined: boolean;
+  issue_count: number;
+}
+
 export interface CreateCommentRequest {
   author_id: string;
   author_name: string;
   body: string;
 }
 
+export interface DependencyRef {
+  id: string;
+  display_id: string;
+  title: string;
+  status: string;
+  priority: IssuePriority | null;
+}
+
+/** Returned by POST /projects/{id}/issues/reindex */
+export interface IssuePosition {
+  id: string;
+  display_id: string;
+  status: string;
+  position: number;
+}
+
 export interface IssueDetail extends Issue {
   tldrs: TLDR[];
   comments: Comment[];
+  dependencies: {
+    blocked_by: DependencyRef[];
+    blocks: DependencyRef[];
+  };
+  favorited: boolean;
+  /** Minutes logged via `POST /issues/{id}/time`. */
+  total_minutes: number;
+}
+
+export interface TimeEntry {
+  id: string;
+  issue_id: string;
+  user_id: string;
+  user_name: string | null;
+  minutes: number;
+  note: string | null;
+  logged_at: string;
+  created_at: string;
+}
+
+export type FavoriteEntityType = 'issue' | 'project';
+
+export interface Favorites {
+  projects: Project[];
+  issues: Issue[];
 }
 
 export interface PublicSubmission {
@@ -421,6 +582,7 @@ export interface GitHubInstallation {
   github_account_id: number;
   github_account_login: string;
   github_account_type: string;
+  github_account_avatar_url: string | null;
   permissions: Record<string, string>;
   status: GitHubInstallationStatus;
   installed_by: string | null;
@@ -437,6 +599,8 @@ export interface GitHubRepository {
   full_name: string;
   default_branch: string;
   is_private: boolean;
+  description: string | null;
+  html_url: string | null;
   last_synced_at: string | null;
   created_at: string;
   updated_at: string;
@@ -452,6 +616,11 @@ export interface GitHubRepoMapping {
   sync_comments: boolean;
   auto_create_issues: boolean;
   status_mapping: Record<string, string | null>;
+  default_issue_type: IssueType | null;
+  default_priority: IssuePriority | null;
+  default_tags: string[];
+  /** GitHub label → tag, or → { type, priority, tags } */
+  label_mapping: Record<string, GitHubLabelMapping>;
   is_active: boolean;
   created_at: string;
   updated_at: string;
@@ -460,6 +629,41 @@ export interface GitHubRepoMapping {
   project?: Project;
 }
 
+export interface GitHubPreviewIssue {
+  id: string;
+  display_id: string;
+  title: string;
+  status: string;
+}
+
+export interface GitHubPrSyncPreview {
+  pr_number: number;
+  pr_title: string;
+  pr_url: string;
+  pr_state: GitHubPrState;
+  head_branch: string;
+  issue: GitHubPreviewIssue | null;
+  new_status: string | null;
+  actions: ('link_pr' | 'change_status')[];
+}
+
+export interface GitHubCommitSyncPreview {
+  sha: string;
+  message: string;
+  url: string;
+  issue: GitHubPreviewIssue | null;
+  actions: 'link_commit'[];
+}
+
+/** Dry run of a repo mapping (POST /github/mappings/{id}/preview) */
+export interface GitHubRepoMappingPreview {
+  mapping_id: string;
+  repo_full_name: string;
+  status_mapping: Record<string, string | null>;
+  pull_requests: GitHubPrSyncPreview[];
+  commits: GitHubCommitSyncPreview[];
+}
+
 export interface GitHubPrLink {
   id: string;
   issue_id: string;
@@ -510,10 +714,20 @@ export interface GitHubIssueLink {
   created_at: string;
 }
 
+export interface TldrVcsLink {
+  tldr_id: string;
+  agent_name: string;
+  github_pr_link_id: string | null;
+  pr_number: number | null;
+  commit_sha: string | null;
+  created_at: string;
+}
+
 export interface IssueGitHubData {
   github_issue: GitHubIssueLink | null;
   pull_requests: GitHubPrLink[];
   commits: GitHubCommitLink[];
+  tldrs: TldrVcsLink[];
   branch_name: string;
 }
 
@@ -526,8 +740,16 @@ export interface CreateRepoMappingRequest {
   sync_comments?: boolean;
   auto_create_issues?: boolean;
   status_mapping?: Record<string, string | null>;
+  default_issue_type?: IssueType;
+  default_priority?: IssuePriority;
+  default_tags?: string[];
+  label_mapping?: Record<string, GitHubLabelMapping>;
 }
 
+export type GitHubLabelMapping =
+  | string
+  | { type?: IssueType; priority?: IssuePriority; tags?: string[] };
+
 export interface UpdateRepoMappingRequest {
   sync_direction?: GitHubSyncDirection;
   sync_issues?: boolean;
@@ -535,6 +757,12 @@ export interface UpdateRepoMappingRequest {
   sync_comments?: boolean;
   auto_create_issues?: boolean;
   status_mapping?: Record<string, string | null>;
+  /** '' clears */
+  default_issue_type?: IssueType | '';
+  /** '' clears */
+  default_priority?: IssuePriority | '';
+  default_tags?: string[];
+  label_mapping?: Record<string, GitHubLabelMapping>;
   is_active?: boolean;
 }
 
@@ -570,11 +798,12 @@ export interface Automation {
 }
 
 // ─── SLA Rules (BAA-8) ───────────────────────
-export interface SlaRule {
+export interface SlaPolicy {
   id: string;
   project_id: string;
   priority: string;
-  deadline_hours: number;
+  response_minutes: number | null;
+  resolution_minutes: number | null;
   created_at: string;
 }
 
@@ -86,6 +86,7 @@ const en = {
   'apiKeys.permGroup.billing': 'Billing',
   'apiKeys.permGroup.context': 'Context',
   'apiKeys.permGroup.templates': 'Templates',
+  'apiKeys.permGroup.views': 'Views & favorites',
   'apiKeys.permGroup.admin': 'Admin',
   // Individual permissions
   'apiKeys.perm.issues:read': 'Read',
@@ -116,6 +117,8 @@ const en = {
   'apiKeys.perm.context:write': 'Write',
   'apiKeys.perm.templates:read': 'Read',
   'apiKeys.perm.templates:write': 'Write',
+  'apiKeys.perm.views:read': 'Read',
+  'apiKeys.perm.views:write': 'Write',
   'apiKeys.perm.admin:full': 'Full access (superkey)',
   // Misc
   'apiKeys.neverExpires': 'Never',
@@ -1625,7 +1628,8 @@ As a [user], I want to [action] so that [benefit].
   'slaRules.title': 'SLA Rules',
   'slaRules.subtitle': 'Define response time targets per priority level',
   'slaRules.priority': 'Priority',
-  'slaRules.deadlineHours': 'Deadline (hours)',
+  'slaRules.deadlineHours': 'Resolution (hours)',
+  'slaRules.responseHours': 'Response (hours)',
   'slaRules.save': 'Save',
   'slaRules.saving': 'Saving…',
   'slaRules.saved': 'Saved!',
@@ -86,6 +86,7 @@ const fr = {
   'apiKeys.permGroup.billing': 'Facturation',
   'apiKeys.permGroup.context': 'Contexte',
   'apiKeys.permGroup.templates': 'Modèles',
+  'apiKeys.permGroup.views': 'Vues et favoris',
   'apiKeys.permGroup.admin': 'Admin',
   // Individual permissions
   'apiKeys.perm.issues:read': 'Lire',
@@ -116,6 +117,8 @@ const fr = {
   'apiKeys.perm.context:write': 'Écrire',
   'apiKeys.perm.templates:read': 'Lire',
   'apiKeys.perm.templates:write': 'Écrire',
+  'apiKeys.perm.views:read': 'Lire',
+  'apiKeys.perm.views:write': 'Écrire',
   'apiKeys.perm.admin:full': 'Accès complet (superclé)',
   // Misc
   'apiKeys.neverExpires': 'Jamais',
@@ -1625,7 +1628,8 @@ En tant que [utilisateur], je veux [action] afin de [bénéfice].
   'slaRules.title': 'Règles SLA',
   'slaRules.subtitle': 'Définir des objectifs de temps de réponse par niveau de priorité',
   'slaRules.priority': 'Priorité',
-  'slaRules.deadlineHours': 'Délai (heures)',
+  'slaRules.deadlineHours': 'Résolution (heures)',
+  'slaRules.responseHours': 'Réponse (heures)',
   'slaRules.save': 'Enregistrer',
   'slaRules.saving': 'Sauvegarde…',
   'slaRules.saved': 'Enregistré !',
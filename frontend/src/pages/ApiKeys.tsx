@@ -70,6 +70,11 @@ const PERMISSION_GROUPS = [
     perms: ['templates:read', 'templates:write'],
     color: 'bg-fuchsia-500/15 text-fuchsia-600 border-fuchsia-500/25',
   },
+  {
+    key: 'views',
+    perms: ['views:read', 'views:write'],
+    color: 'bg-rose-500/15 text-rose-600 border-rose-500/25',
+  },
   {
     key: 'ai',
     perms: ['ai:chat', 'ai:triage'],
@@ -112,6 +117,7 @@ const PRESETS: Record<string, string[]> = {
     'members:read', 'members:invite',
     'context:read', 'context:write',
     'templates:read', 'templates:write',
+    'views:read', 'views:write',
     'ai:chat', 'ai:triage',
     'billing:read',
   ],
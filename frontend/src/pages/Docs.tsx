@@ -245,7 +245,6 @@ const ALL_ENDPOINT_GROUPS: EndpointGroup[] = [
     cat: 'AI',
     endpoints: [
       { method: 'POST',   path: '/ai/chat',                           desc: 'Chat with the AI assistant (streaming)' },
-      { method: 'GET',    path: '/ai/key',                            desc: 'Get AI key/model configuration' },
       { method: 'POST',   path: '/ai/pm-full-review',                 desc: 'Full PM review of a project backlog' },
     ],
   },
@@ -287,9 +286,9 @@ const ALL_ENDPOINT_GROUPS: EndpointGroup[] = [
   {
     cat: 'SLA',
     endpoints: [
-      { method: 'GET',    path: '/projects/:id/sla-rules',            desc: 'List SLA rules (per-priority response times)' },
-      { method: 'POST',   path: '/projects/:id/sla-rules',            desc: 'Create an SLA rule' },
-      { method: 'DELETE', path: '/sla-rules/:id',                     desc: 'Delete an SLA rule' },
+      { method: 'GET',    path: '/projects/:id/sla-policies',         desc: 'List SLA policies (per-priority response and resolution times)' },
+      { method: 'POST',   path: '/projects/:id/sla-policies',         desc: 'Create or replace the SLA policy for a priority' },
+      { method: 'DELETE', path: '/sla-policies/:id',                  desc: 'Delete an SLA policy' },
       { method: 'GET',    path: '/projects/:id/sla-stats',            desc: 'SLA achievement %, on-time, breached counts' },
     ],
   },
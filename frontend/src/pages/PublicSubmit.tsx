@@ -73,6 +73,8 @@ export function PublicSubmit() {
   const [description, setDescription] = useState('');
   const [name, setName] = useState('');
   const [email, setEmail] = useState('');
+  // Honeypot: hidden from people, filled in by naive bots
+  const [website, setWebsite] = useState('');
 
   // ── Attachments ──
   const [attachments, setAttachments] = useState<Attachment[]>([]);
@@ -204,6 +206,7 @@ export function PublicSubmit() {
         reporter_email: email.trim() || undefined,
         token,
         attachments: attachments.length > 0 ? attachments : undefined,
+        website: website || undefined,
       });
       setSubmitted(true);
     } catch (err) {
@@ -306,6 +309,17 @@ export function PublicSubmit() {
             </div>
           )}
 
+          <input
+            type="text"
+            name="website"
+            value={website}
+            onChange={(e) => setWebsite(e.target.value)}
+            tabIndex={-1}
+            autoComplete="off"
+            aria-hidden="true"
+            className="absolute -left-[9999px] h-0 w-0 opacity-0"
+          />
+
           {!token && (
             <div className="flex items-start gap-2 rounded-lg border border-amber-500/20 bg-amber-500/10 px-4 py-3">
               <AlertTriangle size={16} className="text-amber-400 mt-0.5 shrink-0" />